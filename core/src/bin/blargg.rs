@@ -0,0 +1,66 @@
+use core::blargg::{self, CLOCK_SPEED, DEFAULT_CYCLES};
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: blargg <rom> [--cycles N | --seconds N]";
+
+fn parse_args() -> Result<(String, usize), String> {
+    let mut args = env::args().skip(1);
+    let mut rom = None;
+    let mut cycles = DEFAULT_CYCLES;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cycles" | "--seconds" => {
+                let value = args
+                    .next()
+                    .and_then(|value| value.parse::<usize>().ok())
+                    .ok_or(format!("{} needs a number", arg))?;
+
+                cycles = match arg.as_str() {
+                    "--seconds" => value * CLOCK_SPEED,
+                    _ => value,
+                };
+            }
+            _ if rom.is_none() => rom = Some(arg),
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    Ok((rom.ok_or("Missing ROM path")?, cycles))
+}
+
+fn main() -> ExitCode {
+    let (path, cycles) = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let rom = match fs::read(&path) {
+        Ok(rom) => rom,
+        Err(err) => {
+            eprintln!("Could not read {}: {}", path, err);
+            return ExitCode::from(2);
+        }
+    };
+
+    match blargg::run(&rom, cycles) {
+        Ok(report) => {
+            println!("{}", report);
+
+            if report.passed() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(err) => {
+            eprintln!("Could not run {}: {}", path, err);
+            ExitCode::from(2)
+        }
+    }
+}
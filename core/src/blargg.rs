@@ -0,0 +1,205 @@
+use crate::cartridge::header::HeaderError;
+use crate::cpu::registers::WordReg;
+use crate::cpu::sm83::{CpuError, SM83};
+use crate::serial::Capture;
+use std::fmt;
+
+pub const CLOCK_SPEED: usize = 4_194_304;
+
+// Plenty for any of the Blargg ROMs, cpu_instrs takes under a minute of emulated time
+pub const DEFAULT_CYCLES: usize = 120 * CLOCK_SPEED;
+
+// Results are checked every frame worth of cycles
+const CHECK_INTERVAL: usize = 70_224;
+
+// Newer ROMs also report through cartridge RAM: a status byte at 0xA000 that stays
+// 0x80 while running, a signature at 0xA001-0xA003 and a zero terminated text at 0xA004
+const RESULT_STATUS: u16 = 0xA000;
+const RESULT_SIGNATURE: u16 = 0xA001;
+const RESULT_TEXT: u16 = 0xA004;
+const SIGNATURE: [u8; 3] = [0xDE, 0xB0, 0x61];
+const RUNNING: u8 = 0x80;
+
+// Longest result text read from cartridge RAM
+const MAX_TEXT: usize = 0x1000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Passed,
+    Failed,
+    TimedOut,
+    Crashed(CpuError),
+}
+
+// One "NN:result" entry from the summary multi-ROMs print, like "01:ok" or "02:05"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subtest {
+    pub name: String,
+    pub result: String,
+}
+
+impl Subtest {
+    pub fn passed(&self) -> bool {
+        self.result == "ok"
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub status: Status,
+    pub cycles: usize,
+    pub serial: String,
+    // Status code and text from cartridge RAM, if the ROM uses that protocol
+    pub memory_status: Option<u8>,
+    pub memory: Option<String>,
+    // Non-empty lines of whichever output the ROM produced
+    pub lines: Vec<String>,
+    pub subtests: Vec<Subtest>,
+}
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        self.status == Status::Passed
+    }
+
+    pub fn output(&self) -> &str {
+        match &self.memory {
+            Some(text) => text,
+            None => &self.serial,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+
+        match &self.status {
+            Status::Passed => write!(f, "PASSED")?,
+            Status::Failed => write!(f, "FAILED")?,
+            Status::TimedOut => write!(f, "TIMED OUT")?,
+            Status::Crashed(err) => write!(f, "CRASHED: {}", err)?,
+        }
+
+        write!(f, " after {} cycles", self.cycles)
+    }
+}
+
+// Runs the ROM headlessly until it reports a result or the cycle limit is reached
+pub fn run(rom: &[u8], max_cycles: usize) -> Result<Report, HeaderError> {
+    let mut cpu = SM83::new();
+    let capture = Capture::new();
+
+    cpu.bus.load_rom(rom)?;
+    cpu.bus.serial.connect(Box::new(capture.clone()));
+    post_boot(&mut cpu);
+
+    let mut status = Status::TimedOut;
+
+    while cpu.cycles < max_cycles {
+        let chunk = CHECK_INTERVAL.min(max_cycles - cpu.cycles);
+
+        if let Err(err) = cpu.run_for(chunk) {
+            status = Status::Crashed(err);
+            break;
+        }
+
+        if let Some(done) = finished(&cpu, &capture.text()) {
+            status = done;
+            break;
+        }
+    }
+
+    let serial = capture.text();
+    let memory_status = memory_status(&cpu);
+    let memory = memory_status.map(|_| memory_text(&cpu));
+
+    let output = memory.as_deref().unwrap_or(&serial);
+    let lines = output
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let subtests = parse_subtests(output);
+
+    Ok(Report {
+        status,
+        cycles: cpu.cycles,
+        serial,
+        memory_status,
+        memory,
+        lines,
+        subtests,
+    })
+}
+
+pub fn parse_subtests(text: &str) -> Vec<Subtest> {
+    text.split_whitespace()
+        .filter_map(|token| {
+            let (name, result) = token.split_once(':')?;
+
+            if name.is_empty() || result.is_empty() || !name.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            Some(Subtest {
+                name: name.to_string(),
+                result: result.to_string(),
+            })
+        })
+        .collect()
+}
+
+// Register values the DMG boot ROM leaves behind
+fn post_boot(cpu: &mut SM83) {
+    cpu.reg.set_word(WordReg::AF, 0x01B0);
+    cpu.reg.set_word(WordReg::BC, 0x0013);
+    cpu.reg.set_word(WordReg::DE, 0x00D8);
+    cpu.reg.set_word(WordReg::HL, 0x014D);
+    cpu.reg.set_word(WordReg::SP, 0xFFFE);
+    cpu.pc = 0x100;
+}
+
+fn finished(cpu: &SM83, serial: &str) -> Option<Status> {
+    match memory_status(cpu) {
+        Some(RUNNING) => None,
+        Some(0) => Some(Status::Passed),
+        Some(_) => Some(Status::Failed),
+        None if finished_line(serial, "Passed") => Some(Status::Passed),
+        None if finished_line(serial, "Failed") => Some(Status::Failed),
+        None => None,
+    }
+}
+
+// The verdict is only final once the rest of its line, like "all tests", came through
+fn finished_line(serial: &str, verdict: &str) -> bool {
+    match serial.find(verdict) {
+        Some(start) => serial[start..].contains('\n'),
+        None => false,
+    }
+}
+
+fn memory_status(cpu: &SM83) -> Option<u8> {
+    let signature = [
+        cpu.bus.read(RESULT_SIGNATURE),
+        cpu.bus.read(RESULT_SIGNATURE + 1),
+        cpu.bus.read(RESULT_SIGNATURE + 2),
+    ];
+
+    if signature == SIGNATURE {
+        Some(cpu.bus.read(RESULT_STATUS))
+    } else {
+        None
+    }
+}
+
+fn memory_text(cpu: &SM83) -> String {
+    let bytes: Vec<u8> = (0..MAX_TEXT as u16)
+        .map(|i| cpu.bus.read(RESULT_TEXT + i))
+        .take_while(|byte| *byte != 0)
+        .collect();
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
@@ -1,71 +1,426 @@
+use crate::cartridge::header::HeaderError;
+use crate::cartridge::mbc::RumbleCallback;
+use crate::cartridge::rtc::Rtc;
 use crate::cartridge::Cartridge;
+use crate::cpu::interface::MemoryInterface;
+use crate::interrupts::{Interrupt, Interrupts};
+use crate::io::{self, Device, IoRegisters};
+use crate::joypad::{Button, Joypad};
+use crate::log;
 use crate::memory::Memory;
-use crate::types::Size;
+use crate::scheduler::{Event, Scheduler};
+use crate::serial::Serial;
+use crate::timer::Timer;
 
-const ROM_BANK_00: usize = 0x0000;
-const ROM_BANK_00_END: usize = 0x3fff;
+const ROM_BANK_00: u16 = 0x0000;
+const ROM_BANK_NN_END: u16 = 0x7FFF;
 
-const WRAM_00: usize = 0xC000;
-const WRAM_00_END: usize = 0xCFFF;
+const VRAM: u16 = 0x8000;
+const VRAM_END: u16 = 0x9FFF;
 
-const WRAM_01: usize = 0xD000;
-const WRAM_01_END: usize = 0xDFFF;
+const EXT_RAM: u16 = 0xA000;
+const EXT_RAM_END: u16 = 0xBFFF;
 
-const IO_PORTS: usize = 0xFF00;
-const IO_PORTS_END: usize = 0xFF7F;
+const WRAM_00: u16 = 0xC000;
+const WRAM_01_END: u16 = 0xDFFF;
+
+// Mirror of 0xC000-0xDDFF
+const ECHO_RAM: u16 = 0xE000;
+const ECHO_RAM_END: u16 = 0xFDFF;
+
+const OAM: u16 = 0xFE00;
+const OAM_END: u16 = 0xFE9F;
+
+const UNUSABLE: u16 = 0xFEA0;
+const UNUSABLE_END: u16 = 0xFEFF;
+
+const IO_PORTS: u16 = 0xFF00;
+const IO_PORTS_END: u16 = 0xFF7F;
+
+const HRAM: u16 = 0xFF80;
+const HRAM_END: u16 = 0xFFFE;
+
+const IE: u16 = 0xFFFF;
+
+const PAGE_SIZE: usize = 0x100;
+const PAGES: usize = 0x100;
+
+// Where each 256 byte page of the address space is backed, offsets point to the
+// start of the page inside the backing memory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Page {
+    Rom(usize),
+    Vram(usize),
+    Ram(usize),
+    Wram(usize),
+    // Bank controller registers mapped over the cartridge RAM area
+    Mbc,
+    OpenBus,
+    // OAM, I/O, HRAM and IE share the last two pages and are decoded per address
+    High,
+}
 
 pub struct Bus {
-    pub mem: Memory,
-    pub rom: Cartridge,
+    pub vram: Memory,
+    pub wram: Memory,
+    pub oam: Memory,
+    pub hram: Memory,
+    // Only reachable through Bus so the page table always follows the cartridge
+    rom: Cartridge,
+    pub interrupts: Interrupts,
+    pub io: IoRegisters,
+    pub joypad: Joypad,
+    pub serial: Serial,
+    pub timer: Timer,
+    pub scheduler: Scheduler,
     pub cycles: usize,
+    // Color only features are not there on a DMG. Loading a cartridge leaves this
+    // alone, a color cartridge may still be run in DMG mode
+    pub cgb: bool,
+    pages: [Page; PAGES],
 }
 
 impl Bus {
     pub fn new(cart: Option<Cartridge>) -> Self {
-        Self {
-            mem: Memory::default(),
+        let mut bus = Self {
+            vram: Memory::new((VRAM_END - VRAM) as usize + 1),
+            wram: Memory::new((WRAM_01_END - WRAM_00) as usize + 1),
+            oam: Memory::new((OAM_END - OAM) as usize + 1),
+            hram: Memory::new((HRAM_END - HRAM) as usize + 1),
             rom: cart.unwrap_or_default(),
+            interrupts: Interrupts::new(),
+            io: IoRegisters::new(),
+            joypad: Joypad::new(),
+            serial: Serial::new(),
+            timer: Timer::new(),
+            scheduler: Scheduler::new(),
             cycles: 0,
+            cgb: false,
+            pages: [Page::OpenBus; PAGES],
+        };
+
+        bus.map_pages();
+
+        bus
+    }
+
+    // The cartridge must be swapped through here so the page table follows it
+    pub fn load_cartridge(&mut self, cart: Cartridge) {
+        self.rom = cart;
+        self.map_pages();
+    }
+
+    pub fn load_rom(&mut self, rom: &[u8]) -> Result<(), HeaderError> {
+        self.load_cartridge(Cartridge::new(rom)?);
+        Ok(())
+    }
+
+    pub fn load_rom_verified(&mut self, rom: &[u8]) -> Result<(), HeaderError> {
+        self.load_cartridge(Cartridge::new_verified(rom)?);
+        Ok(())
+    }
+
+    pub fn cartridge(&self) -> &Cartridge {
+        &self.rom
+    }
+
+    // None of these change what is mapped where, so they can skip the page table
+    pub fn rtc(&mut self) -> Option<&mut Rtc> {
+        self.rom.rtc()
+    }
+
+    pub fn on_rumble(&mut self, callback: RumbleCallback) {
+        self.rom.on_rumble(callback)
+    }
+
+    pub fn load_battery_ram(&mut self, data: &[u8]) {
+        self.rom.load_battery_ram(data)
+    }
+
+    fn map_pages(&mut self) {
+        for page in 0..PAGES {
+            let addr = (page * PAGE_SIZE) as u16;
+
+            self.pages[page] = match addr {
+                ROM_BANK_00..=ROM_BANK_NN_END => Page::Rom(self.rom.rom_offset(addr)),
+                VRAM..=VRAM_END => Page::Vram((addr - VRAM) as usize),
+                EXT_RAM..=EXT_RAM_END if self.rom.registers_mapped() => Page::Mbc,
+                EXT_RAM..=EXT_RAM_END => match self.rom.ram_offset(addr) {
+                    Some(offset) => Page::Ram(offset),
+                    None => Page::OpenBus,
+                },
+                WRAM_00..=WRAM_01_END => Page::Wram((addr - WRAM_00) as usize),
+                ECHO_RAM..=ECHO_RAM_END => Page::Wram((addr - ECHO_RAM) as usize),
+                _ => Page::High,
+            };
         }
     }
 
-    pub fn read(&self, size: Size, addr: usize) -> usize {
-        match addr {
-            ROM_BANK_00..=ROM_BANK_00_END => self.rom.read(size, addr),
-            WRAM_00..=WRAM_00_END => self.mem.read(size, addr - WRAM_00),
-            WRAM_01..=WRAM_01_END => self.rom.ram.read(size, addr - WRAM_01),
-            IO_PORTS..=IO_PORTS_END => {
-                println!("Read from IO port: {:04X}", addr);
-                1
+    #[inline]
+    pub fn read(&self, addr: u16) -> u8 {
+        let offset = addr as usize % PAGE_SIZE;
+
+        let data = match self.pages[addr as usize / PAGE_SIZE] {
+            Page::Rom(base) => self.rom.read_rom(base + offset),
+            Page::Vram(base) => self.vram.read(base + offset),
+            Page::Ram(base) => self.rom.read_ram(base + offset),
+            Page::Wram(base) => self.wram.read(base + offset),
+            Page::Mbc => self.rom.read_register(addr),
+            Page::OpenBus => 0xFF,
+            Page::High => self.read_high(addr),
+        };
+
+        log!(Memory, Trace, "Read {:02X} from {:04X}", data, addr);
+
+        data
+    }
+
+    #[inline]
+    pub fn write(&mut self, addr: u16, data: u8) {
+        log!(Memory, Trace, "Write {:02X} to {:04X}", data, addr);
+
+        let offset = addr as usize % PAGE_SIZE;
+
+        match self.pages[addr as usize / PAGE_SIZE] {
+            Page::Rom(_) => {
+                // Bank controller registers live in the ROM area and may remap it
+                log!(Mbc, Debug, "Write {:02X} to {:04X}", data, addr);
+                self.rom.write(addr, data, self.cycles);
+                self.map_pages();
             }
+            Page::Vram(base) => self.vram.write(base + offset, data),
+            Page::Ram(base) => self.rom.write_ram(base + offset, data),
+            Page::Wram(base) => self.wram.write(base + offset, data),
+            Page::Mbc => self.rom.write_register(addr, data, self.cycles),
+            Page::OpenBus => log!(Memory, Debug, "Write {:02X} to open bus {:04X}", data, addr),
+            Page::High => self.write_high(addr, data),
+        }
+    }
+
+    // Little endian, the high byte wraps around to 0x0000
+    pub fn read_word(&self, addr: u16) -> u16 {
+        (self.read(addr) as u16) | ((self.read(addr.wrapping_add(1)) as u16) << 8)
+    }
+
+    pub fn write_word(&mut self, addr: u16, data: u16) {
+        self.write(addr, (data & 0xff) as u8);
+        self.write(addr.wrapping_add(1), (data >> 8) as u8);
+    }
+
+    fn read_high(&self, addr: u16) -> u8 {
+        match addr {
+            OAM..=OAM_END => self.oam.read((addr - OAM) as usize),
+            // Reads as zero on DMG as long as the PPU is not blocking OAM
+            UNUSABLE..=UNUSABLE_END => 0x00,
+            IO_PORTS..=IO_PORTS_END => self.read_io(addr),
+            HRAM..=HRAM_END => self.hram.read((addr - HRAM) as usize),
+            IE => self.interrupts.read_enable(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn write_high(&mut self, addr: u16, data: u8) {
+        match addr {
+            OAM..=OAM_END => self.oam.write((addr - OAM) as usize, data),
+            UNUSABLE..=UNUSABLE_END => {}
+            IO_PORTS..=IO_PORTS_END => self.write_io(addr, data),
+            HRAM..=HRAM_END => self.hram.write((addr - HRAM) as usize, data),
+            IE => self.interrupts.write_enable(data),
+            _ => unreachable!(),
+        }
+    }
+
+    // Unmapped registers read as 0xFF and ignore writes
+    fn read_io(&self, addr: u16) -> u8 {
+        let reg = match io::lookup(addr) {
+            Some(reg) if reg.device != Device::Cgb || self.cgb => reg,
+            _ => return 0xFF,
+        };
+
+        let value = match reg.device {
+            Device::Interrupts => self.interrupts.read_flag(),
+            Device::Joypad => self.joypad.read(),
+            Device::Serial => self.serial.read(addr, self.cgb),
+            Device::Timer => self.timer.read(addr, self.cycles),
+            _ => self.io.read(addr),
+        };
+
+        reg.read(value)
+    }
+
+    fn write_io(&mut self, addr: u16, data: u8) {
+        let reg = match io::lookup(addr) {
+            Some(reg) if reg.device != Device::Cgb || self.cgb => reg,
             _ => {
-                println!("Ignored read from address: {:04X}", addr);
-                0
+                log!(
+                    Io,
+                    Debug,
+                    "Write {:02X} to unmapped register {:04X}",
+                    data,
+                    addr
+                );
+                return;
             }
+        };
+
+        // PPU and APU registers are traced under their own category
+        match reg.device {
+            Device::Ppu => log!(Ppu, Trace, "Write {:02X} to {}", data, reg),
+            Device::Apu => log!(Apu, Trace, "Write {:02X} to {}", data, reg),
+            _ => log!(Io, Trace, "Write {:02X} to {}", data, reg),
+        }
+
+        match reg.device {
+            Device::Interrupts => self
+                .interrupts
+                .write_flag(reg.write(self.interrupts.flag, data)),
+            Device::Joypad => self
+                .joypad
+                .write(data & reg.write_mask, &mut self.interrupts),
+            Device::Serial => {
+                self.serial.write(
+                    addr,
+                    data & reg.write_mask,
+                    self.cgb,
+                    self.cycles,
+                    &mut self.interrupts,
+                );
+                self.schedule_serial();
+            }
+            Device::Timer => {
+                self.timer.write(
+                    addr,
+                    data & reg.write_mask,
+                    self.cycles,
+                    &mut self.interrupts,
+                );
+                self.schedule_timer();
+            }
+            _ => self.io.write(addr, reg.write(self.io.read(addr), data)),
         }
     }
 
-    pub fn write(&mut self, size: Size, addr: usize, data: usize) {
-        println!("Write to address: {:04X}", addr);
-        match addr {
-            WRAM_00..=WRAM_00_END => self.mem.write(size, addr - WRAM_00, data),
-            WRAM_01..=WRAM_01_END => self.rom.ram.write(size, addr - WRAM_01, data),
-            IO_PORTS..=IO_PORTS_END => println!("Write to IO port: {:04X}", addr),
-            _ => println!("Ignored write to address: {:04X}", addr),
+    // Components are not stepped cycle by cycle, time jumps ahead and every event
+    // whose deadline was passed is handled in order
+    #[inline]
+    pub fn tick(&mut self, cycles: usize) {
+        self.cycles += cycles;
+
+        while let Some((event, at)) = self.scheduler.pop_due(self.cycles) {
+            self.handle_event(event, at);
         }
     }
 
-    pub fn tick(&mut self, times: usize) {
-        if times <= 0 {
-            return;
+    pub fn cycles_to_next_event(&self) -> Option<usize> {
+        self.scheduler
+            .next_deadline()
+            .map(|at| at.saturating_sub(self.cycles))
+    }
+
+    // Presses or releases a button right away
+    pub fn set_button(&mut self, button: Button, pressed: bool) {
+        self.joypad.set(button, pressed, &mut self.interrupts);
+    }
+
+    // Presses or releases a button once the bus reaches the given cycle
+    pub fn set_button_at(&mut self, at: usize, button: Button, pressed: bool) {
+        self.joypad.set_at(at, button, pressed);
+        self.schedule_joypad();
+    }
+
+    // Clocks a byte in from the other side of the link if a transfer is waiting on
+    // the external clock, and returns the byte that was sent
+    pub fn clock_serial(&mut self, data: u8) -> Option<u8> {
+        self.serial.clock_external(data, &mut self.interrupts)
+    }
+
+    fn schedule_serial(&mut self) {
+        match self.serial.next_event() {
+            Some(at) => self.scheduler.schedule(Event::Serial, at),
+            None => self.scheduler.cancel(Event::Serial),
+        }
+    }
+
+    fn schedule_joypad(&mut self) {
+        match self.joypad.next_event() {
+            Some(at) => self.scheduler.schedule(Event::Joypad, at),
+            None => self.scheduler.cancel(Event::Joypad),
         }
+    }
 
-        for _ in 0..times {
-            self.cycles += 1;
-            println!("Tick! {}", self.cycles);
-            // All components who need to be ticked
+    fn schedule_timer(&mut self) {
+        match self.timer.next_event() {
+            Some(at) => self.scheduler.schedule(Event::Timer, at),
+            None => self.scheduler.cancel(Event::Timer),
         }
     }
+
+    // Handlers run with the time the event was due at, which may be slightly in the past
+    fn handle_event(&mut self, event: Event, at: usize) {
+        log!(
+            Io,
+            Trace,
+            "{:?} event due at {} handled at {}",
+            event,
+            at,
+            self.cycles
+        );
+
+        match event {
+            Event::Timer => {
+                self.timer.sync(self.cycles, &mut self.interrupts);
+                self.schedule_timer();
+            }
+            Event::Joypad => {
+                self.joypad.sync(self.cycles, &mut self.interrupts);
+                self.schedule_joypad();
+            }
+            Event::Serial => {
+                self.serial.sync(self.cycles, &mut self.interrupts);
+                self.schedule_serial();
+            }
+            // Devices hook in here as they get emulated
+            Event::Ppu | Event::Apu | Event::Dma | Event::Rtc => {}
+        }
+    }
+}
+
+// The interrupt lines are wired straight to the controller instead of going through IE and IF
+impl MemoryInterface for Bus {
+    #[inline]
+    fn read(&mut self, addr: u16) -> u8 {
+        Bus::read(self, addr)
+    }
+
+    #[inline]
+    fn write(&mut self, addr: u16, data: u8) {
+        Bus::write(self, addr, data)
+    }
+
+    #[inline]
+    fn tick(&mut self, cycles: usize) {
+        Bus::tick(self, cycles)
+    }
+
+    fn pending_interrupts(&mut self) -> u8 {
+        self.interrupts.pending()
+    }
+
+    fn highest_pending(&mut self) -> Option<Interrupt> {
+        self.interrupts.highest_pending()
+    }
+
+    fn acknowledge(&mut self, interrupt: Interrupt) {
+        self.interrupts.acknowledge(interrupt);
+    }
+
+    fn cycles_to_next_event(&self) -> Option<usize> {
+        Bus::cycles_to_next_event(self)
+    }
+
+    fn joypad_asserted(&mut self) -> bool {
+        self.joypad.lines() != 0x0F
+    }
 }
 
 impl Default for Bus {
@@ -1,93 +1,127 @@
-use crate::{memory::Memory, types::Size};
-use std::string;
+pub mod header;
+pub mod mbc;
+pub mod rtc;
 
-pub struct Cartridge {
-    pub title: String,
-    pub cgb_flag: u8,
-    pub sgb_flag: u8,
-    pub cartridge_type: u8,
-    pub rom_size: u8,
-    pub ram_size: u8,
-    pub destination_code: u8,
-    pub old_licensee_code: u8,
-    pub new_licensee_code: u16,
-    pub mask_rom_version_number: u8,
-    pub header_checksum: u8,
-    pub global_checksum: u16,
+use crate::cartridge::header::{Header, HeaderError, HEADER_END};
+use crate::cartridge::mbc::{Mbc, RumbleCallback};
+use crate::cartridge::rtc::Rtc;
+use crate::memory::Memory;
 
+pub struct Cartridge {
+    pub header: Header,
     pub rom: Vec<u8>,
     pub ram: Memory,
+    mbc: Box<dyn Mbc>,
+    ram_bits: u8,
 }
 
 impl Cartridge {
-    pub fn new(rom: &[u8]) -> Result<Self, String> {
-        let mut cart = Self {
-            title: String::new(),
-            cgb_flag: 0,
-            sgb_flag: 0,
-            cartridge_type: 0,
-            rom_size: 0,
-            ram_size: 0,
-            destination_code: 0,
-            old_licensee_code: 0,
-            new_licensee_code: 0,
-            mask_rom_version_number: 0,
-            header_checksum: 0,
-            global_checksum: 0,
-
-            rom: Vec::new(),
-            ram: Memory::new(0),
-        };
-
-        cart.title = string::String::from_utf8(rom[0x134..0x143].to_vec())
-            .unwrap()
-            .trim_matches(char::from(0))
-            .to_string();
-
-        cart.cgb_flag = rom[0x143];
-        cart.sgb_flag = rom[0x146];
-        cart.cartridge_type = rom[0x147];
-        cart.rom_size = rom[0x148];
-        cart.ram_size = rom[0x149];
-        cart.destination_code = rom[0x14A];
-        cart.old_licensee_code = rom[0x14B];
-        cart.new_licensee_code = (rom[0x144] as u16) << 8 | rom[0x145] as u16;
-        cart.mask_rom_version_number = rom[0x14C];
-        cart.header_checksum = rom[0x14D];
-        cart.global_checksum = (rom[0x14E] as u16) << 8 | rom[0x14F] as u16;
-
-        cart.rom = rom.to_owned();
-
-        match cart.ram_size {
-            0x00 => cart.ram = Memory::new(0),
-            0x01 => cart.ram = Memory::new(2 * 1024),
-            0x02 => cart.ram = Memory::new(8 * 1024),
-            0x03 => cart.ram = Memory::new(32 * 1024),
-            0x04 => cart.ram = Memory::new(128 * 1024),
-            0x05 => cart.ram = Memory::new(64 * 1024),
-            _ => return Err(format!("Invalid RAM size: {:#X}", cart.ram_size)),
-        }
+    pub fn new(rom: &[u8]) -> Result<Self, HeaderError> {
+        let header = Header::parse(rom)?;
+        let ram = Memory::new(mbc::ram_size(&header));
+        let mbc = mbc::new(&header, rom);
+        let ram_bits = mbc.ram_bits();
+
+        Ok(Self {
+            header,
+            rom: rom.to_owned(),
+            ram,
+            mbc,
+            ram_bits,
+        })
+    }
+
+    // Like new, but also refuses a ROM whose logo or checksums are wrong
+    pub fn new_verified(rom: &[u8]) -> Result<Self, HeaderError> {
+        let cart = Self::new(rom)?;
+        cart.verify()?;
 
         Ok(cart)
     }
 
-    pub fn read(&self, size: Size, addr: usize) -> usize {
-        match size {
-            Size::Byte => self.rom[addr] as usize,
-            Size::Word => (self.rom[addr + 1] as usize) << 8 | self.rom[addr] as usize,
+    pub fn verify(&self) -> Result<(), HeaderError> {
+        self.header.verify(&self.rom)
+    }
+
+    // Anything past the end of the ROM image reads as open bus
+    #[inline]
+    pub fn read_rom(&self, offset: usize) -> u8 {
+        match self.rom.get(offset) {
+            Some(data) => *data,
+            None => 0xff,
+        }
+    }
+
+    // Bits the RAM chip does not have always read back as 1
+    #[inline]
+    pub fn read_ram(&self, offset: usize) -> u8 {
+        self.ram.read(offset) | !self.ram_bits
+    }
+
+    #[inline]
+    pub fn write_ram(&mut self, offset: usize, data: u8) {
+        self.ram.write(offset, data & self.ram_bits)
+    }
+
+    // RAM contents worth writing to a save file, only if a battery keeps them
+    pub fn battery_ram(&self) -> Option<&[u8]> {
+        if self.header.cartridge_type.has_battery() && self.ram.size() > 0 {
+            Some(self.ram.data())
+        } else {
+            None
+        }
+    }
+
+    // Restores a save file, anything past the end of the RAM is ignored
+    pub fn load_battery_ram(&mut self, data: &[u8]) {
+        for (offset, byte) in data.iter().enumerate() {
+            self.write_ram(offset, *byte);
         }
     }
 
-    pub fn load_new_rom(&mut self, rom: &[u8]) -> Result<(), String> {
-        *self = Self::new(rom)?;
-        Ok(())
+    // Where in the ROM image a CPU address between 0x0000 and 0x7FFF points to
+    pub fn rom_offset(&self, addr: u16) -> usize {
+        self.mbc.rom_offset(addr)
+    }
+
+    // Where in the RAM a CPU address between 0xA000 and 0xBFFF points to, or None
+    // when no RAM is mapped there
+    pub fn ram_offset(&self, addr: u16) -> Option<usize> {
+        self.mbc.ram_offset(addr)
+    }
+
+    // Writes to the ROM area go to the memory bank controller
+    pub fn write(&mut self, addr: u16, data: u8, now: usize) {
+        self.mbc.write(addr, data, now)
+    }
+
+    // Whether 0xA000-0xBFFF currently shows controller registers instead of RAM
+    pub fn registers_mapped(&self) -> bool {
+        self.mbc.registers_mapped()
+    }
+
+    pub fn read_register(&self, addr: u16) -> u8 {
+        self.mbc.read_register(addr)
+    }
+
+    pub fn write_register(&mut self, addr: u16, data: u8, now: usize) {
+        self.mbc.write_register(addr, data, now)
+    }
+
+    // The real time clock of MBC3 cartridges that have one
+    pub fn rtc(&mut self) -> Option<&mut Rtc> {
+        self.mbc.rtc()
+    }
+
+    // Frontends get told when a rumble cartridge switches its motor
+    pub fn on_rumble(&mut self, callback: RumbleCallback) {
+        self.mbc.on_rumble(callback)
     }
 }
 
 impl Default for Cartridge {
     fn default() -> Self {
-        let mut rom = vec![0; 0x150];
-        rom[0x149] = 0x00; // rom only
-        Self::new(&rom).unwrap() // empty header cartridge
+        let rom = vec![0; HEADER_END];
+        Self::new(&rom).unwrap() // empty header, rom only cartridge
     }
 }
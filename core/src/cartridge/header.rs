@@ -0,0 +1,523 @@
+use std::error::Error;
+use std::fmt;
+
+pub const HEADER_END: usize = 0x150;
+
+const LOGO: usize = 0x104;
+const TITLE: usize = 0x134;
+const MANUFACTURER: usize = 0x13F;
+const CGB_FLAG: usize = 0x143;
+const NEW_LICENSEE: usize = 0x144;
+const SGB_FLAG: usize = 0x146;
+const CARTRIDGE_TYPE: usize = 0x147;
+const ROM_SIZE: usize = 0x148;
+const RAM_SIZE: usize = 0x149;
+const DESTINATION: usize = 0x14A;
+const OLD_LICENSEE: usize = 0x14B;
+const VERSION: usize = 0x14C;
+const HEADER_CHECKSUM: usize = 0x14D;
+const GLOBAL_CHECKSUM: usize = 0x14E;
+
+// Old licensee code telling that the new two character code is used instead
+const USE_NEW_LICENSEE: u8 = 0x33;
+
+#[rustfmt::skip]
+pub const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83,
+    0x00, 0x0C, 0x00, 0x0D, 0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E,
+    0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99, 0xBB, 0xBB, 0x67, 0x63,
+    0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HeaderError {
+    TooShort { len: usize },
+    UnknownCartridgeType(u8),
+    UnknownRomSize(u8),
+    UnknownRamSize(u8),
+    BadLogo,
+    HeaderChecksum { computed: u8, stored: u8 },
+    GlobalChecksum { computed: u16, stored: u16 },
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HeaderError::TooShort { len } => write!(
+                f,
+                "ROM is {} bytes, too short to hold a header ({} bytes)",
+                len, HEADER_END
+            ),
+            HeaderError::UnknownCartridgeType(code) => {
+                write!(f, "Unknown cartridge type: {:#04X}", code)
+            }
+            HeaderError::UnknownRomSize(code) => write!(f, "Unknown ROM size: {:#04X}", code),
+            HeaderError::UnknownRamSize(code) => write!(f, "Unknown RAM size: {:#04X}", code),
+            HeaderError::BadLogo => write!(f, "Nintendo logo does not match"),
+            HeaderError::HeaderChecksum { computed, stored } => write!(
+                f,
+                "Header checksum mismatch: computed {:#04X}, stored {:#04X}",
+                computed, stored
+            ),
+            HeaderError::GlobalChecksum { computed, stored } => write!(
+                f,
+                "Global checksum mismatch: computed {:#06X}, stored {:#06X}",
+                computed, stored
+            ),
+        }
+    }
+}
+
+impl Error for HeaderError {}
+
+// The chip on the cartridge that handles bank switching, if any
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Controller {
+    None,
+    Mbc1,
+    Mbc2,
+    Mbc3,
+    Mbc5,
+    Mbc6,
+    Mbc7,
+    Mmm01,
+    PocketCamera,
+    Tama5,
+    HuC1,
+    HuC3,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CartridgeType {
+    RomOnly,
+    RomRam,
+    RomRamBattery,
+    Mbc1,
+    Mbc1Ram,
+    Mbc1RamBattery,
+    Mbc2,
+    Mbc2Battery,
+    Mmm01,
+    Mmm01Ram,
+    Mmm01RamBattery,
+    Mbc3TimerBattery,
+    Mbc3TimerRamBattery,
+    Mbc3,
+    Mbc3Ram,
+    Mbc3RamBattery,
+    Mbc5,
+    Mbc5Ram,
+    Mbc5RamBattery,
+    Mbc5Rumble,
+    Mbc5RumbleRam,
+    Mbc5RumbleRamBattery,
+    Mbc6,
+    Mbc7SensorRumbleRamBattery,
+    PocketCamera,
+    Tama5,
+    HuC3,
+    HuC1RamBattery,
+}
+
+impl CartridgeType {
+    pub fn from_code(code: u8) -> Option<Self> {
+        let cartridge_type = match code {
+            0x00 => CartridgeType::RomOnly,
+            0x01 => CartridgeType::Mbc1,
+            0x02 => CartridgeType::Mbc1Ram,
+            0x03 => CartridgeType::Mbc1RamBattery,
+            0x05 => CartridgeType::Mbc2,
+            0x06 => CartridgeType::Mbc2Battery,
+            0x08 => CartridgeType::RomRam,
+            0x09 => CartridgeType::RomRamBattery,
+            0x0B => CartridgeType::Mmm01,
+            0x0C => CartridgeType::Mmm01Ram,
+            0x0D => CartridgeType::Mmm01RamBattery,
+            0x0F => CartridgeType::Mbc3TimerBattery,
+            0x10 => CartridgeType::Mbc3TimerRamBattery,
+            0x11 => CartridgeType::Mbc3,
+            0x12 => CartridgeType::Mbc3Ram,
+            0x13 => CartridgeType::Mbc3RamBattery,
+            0x19 => CartridgeType::Mbc5,
+            0x1A => CartridgeType::Mbc5Ram,
+            0x1B => CartridgeType::Mbc5RamBattery,
+            0x1C => CartridgeType::Mbc5Rumble,
+            0x1D => CartridgeType::Mbc5RumbleRam,
+            0x1E => CartridgeType::Mbc5RumbleRamBattery,
+            0x20 => CartridgeType::Mbc6,
+            0x22 => CartridgeType::Mbc7SensorRumbleRamBattery,
+            0xFC => CartridgeType::PocketCamera,
+            0xFD => CartridgeType::Tama5,
+            0xFE => CartridgeType::HuC3,
+            0xFF => CartridgeType::HuC1RamBattery,
+            _ => return None,
+        };
+
+        Some(cartridge_type)
+    }
+
+    pub fn controller(&self) -> Controller {
+        match self {
+            CartridgeType::RomOnly | CartridgeType::RomRam | CartridgeType::RomRamBattery => {
+                Controller::None
+            }
+            CartridgeType::Mbc1 | CartridgeType::Mbc1Ram | CartridgeType::Mbc1RamBattery => {
+                Controller::Mbc1
+            }
+            CartridgeType::Mbc2 | CartridgeType::Mbc2Battery => Controller::Mbc2,
+            CartridgeType::Mmm01 | CartridgeType::Mmm01Ram | CartridgeType::Mmm01RamBattery => {
+                Controller::Mmm01
+            }
+            CartridgeType::Mbc3TimerBattery
+            | CartridgeType::Mbc3TimerRamBattery
+            | CartridgeType::Mbc3
+            | CartridgeType::Mbc3Ram
+            | CartridgeType::Mbc3RamBattery => Controller::Mbc3,
+            CartridgeType::Mbc5
+            | CartridgeType::Mbc5Ram
+            | CartridgeType::Mbc5RamBattery
+            | CartridgeType::Mbc5Rumble
+            | CartridgeType::Mbc5RumbleRam
+            | CartridgeType::Mbc5RumbleRamBattery => Controller::Mbc5,
+            CartridgeType::Mbc6 => Controller::Mbc6,
+            CartridgeType::Mbc7SensorRumbleRamBattery => Controller::Mbc7,
+            CartridgeType::PocketCamera => Controller::PocketCamera,
+            CartridgeType::Tama5 => Controller::Tama5,
+            CartridgeType::HuC3 => Controller::HuC3,
+            CartridgeType::HuC1RamBattery => Controller::HuC1,
+        }
+    }
+
+    // MBC2 has its RAM built in, so it counts even though the type does not say so
+    pub fn has_ram(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::RomRam
+                | CartridgeType::RomRamBattery
+                | CartridgeType::Mbc1Ram
+                | CartridgeType::Mbc1RamBattery
+                | CartridgeType::Mbc2
+                | CartridgeType::Mbc2Battery
+                | CartridgeType::Mmm01Ram
+                | CartridgeType::Mmm01RamBattery
+                | CartridgeType::Mbc3TimerRamBattery
+                | CartridgeType::Mbc3Ram
+                | CartridgeType::Mbc3RamBattery
+                | CartridgeType::Mbc5Ram
+                | CartridgeType::Mbc5RamBattery
+                | CartridgeType::Mbc5RumbleRam
+                | CartridgeType::Mbc5RumbleRamBattery
+                | CartridgeType::Mbc7SensorRumbleRamBattery
+                | CartridgeType::PocketCamera
+                | CartridgeType::HuC3
+                | CartridgeType::HuC1RamBattery
+        )
+    }
+
+    pub fn has_battery(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::RomRamBattery
+                | CartridgeType::Mbc1RamBattery
+                | CartridgeType::Mbc2Battery
+                | CartridgeType::Mmm01RamBattery
+                | CartridgeType::Mbc3TimerBattery
+                | CartridgeType::Mbc3TimerRamBattery
+                | CartridgeType::Mbc3RamBattery
+                | CartridgeType::Mbc5RamBattery
+                | CartridgeType::Mbc5RumbleRamBattery
+                | CartridgeType::Mbc7SensorRumbleRamBattery
+                | CartridgeType::HuC3
+                | CartridgeType::HuC1RamBattery
+        )
+    }
+
+    pub fn has_timer(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::Mbc3TimerBattery | CartridgeType::Mbc3TimerRamBattery
+        )
+    }
+
+    pub fn has_rumble(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::Mbc5Rumble
+                | CartridgeType::Mbc5RumbleRam
+                | CartridgeType::Mbc5RumbleRamBattery
+                | CartridgeType::Mbc7SensorRumbleRamBattery
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RomSize {
+    Kib32,
+    Kib64,
+    Kib128,
+    Kib256,
+    Kib512,
+    Mib1,
+    Mib2,
+    Mib4,
+    Mib8,
+    // Only seen in a few unofficial headers
+    Mib1_1,
+    Mib1_2,
+    Mib1_5,
+}
+
+impl RomSize {
+    pub fn from_code(code: u8) -> Option<Self> {
+        let size = match code {
+            0x00 => RomSize::Kib32,
+            0x01 => RomSize::Kib64,
+            0x02 => RomSize::Kib128,
+            0x03 => RomSize::Kib256,
+            0x04 => RomSize::Kib512,
+            0x05 => RomSize::Mib1,
+            0x06 => RomSize::Mib2,
+            0x07 => RomSize::Mib4,
+            0x08 => RomSize::Mib8,
+            0x52 => RomSize::Mib1_1,
+            0x53 => RomSize::Mib1_2,
+            0x54 => RomSize::Mib1_5,
+            _ => return None,
+        };
+
+        Some(size)
+    }
+
+    // Number of 16 KiB banks
+    pub fn banks(&self) -> usize {
+        match self {
+            RomSize::Kib32 => 2,
+            RomSize::Kib64 => 4,
+            RomSize::Kib128 => 8,
+            RomSize::Kib256 => 16,
+            RomSize::Kib512 => 32,
+            RomSize::Mib1 => 64,
+            RomSize::Mib2 => 128,
+            RomSize::Mib4 => 256,
+            RomSize::Mib8 => 512,
+            RomSize::Mib1_1 => 72,
+            RomSize::Mib1_2 => 80,
+            RomSize::Mib1_5 => 96,
+        }
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.banks() * 0x4000
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RamSize {
+    None,
+    // Listed by some homebrew, no licensed cartridge uses it
+    Kib2,
+    Kib8,
+    Kib32,
+    Kib64,
+    Kib128,
+}
+
+impl RamSize {
+    pub fn from_code(code: u8) -> Option<Self> {
+        let size = match code {
+            0x00 => RamSize::None,
+            0x01 => RamSize::Kib2,
+            0x02 => RamSize::Kib8,
+            0x03 => RamSize::Kib32,
+            0x04 => RamSize::Kib128,
+            0x05 => RamSize::Kib64,
+            _ => return None,
+        };
+
+        Some(size)
+    }
+
+    pub fn bytes(&self) -> usize {
+        match self {
+            RamSize::None => 0,
+            RamSize::Kib2 => 2 * 1024,
+            RamSize::Kib8 => 8 * 1024,
+            RamSize::Kib32 => 32 * 1024,
+            RamSize::Kib64 => 64 * 1024,
+            RamSize::Kib128 => 128 * 1024,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CgbSupport {
+    None,
+    // Runs on both, with CGB enhancements
+    Enhanced,
+    Only,
+}
+
+impl CgbSupport {
+    pub fn from_flag(flag: u8) -> Self {
+        match flag {
+            0xC0 => CgbSupport::Only,
+            flag if flag & 0x80 != 0 => CgbSupport::Enhanced,
+            _ => CgbSupport::None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Destination {
+    Japan,
+    Overseas,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Licensee {
+    Old(u8),
+    New([u8; 2]),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub title: String,
+    // Four character code that CGB era cartridges squeeze into the end of the title
+    pub manufacturer: Option<String>,
+    pub cgb: CgbSupport,
+    pub sgb: bool,
+    pub licensee: Licensee,
+    pub cartridge_type: CartridgeType,
+    pub rom_size: RomSize,
+    pub ram_size: RamSize,
+    pub destination: Destination,
+    pub version: u8,
+    pub header_checksum: u8,
+    pub global_checksum: u16,
+}
+
+impl Header {
+    // Only fails when the header is missing or uses codes we do not know, the logo
+    // and checksums are left to verify
+    pub fn parse(rom: &[u8]) -> Result<Self, HeaderError> {
+        if rom.len() < HEADER_END {
+            return Err(HeaderError::TooShort { len: rom.len() });
+        }
+
+        let cgb = CgbSupport::from_flag(rom[CGB_FLAG]);
+        let (title, manufacturer) = parse_title(rom, cgb);
+
+        let cartridge_type = CartridgeType::from_code(rom[CARTRIDGE_TYPE])
+            .ok_or(HeaderError::UnknownCartridgeType(rom[CARTRIDGE_TYPE]))?;
+        let rom_size =
+            RomSize::from_code(rom[ROM_SIZE]).ok_or(HeaderError::UnknownRomSize(rom[ROM_SIZE]))?;
+        let ram_size =
+            RamSize::from_code(rom[RAM_SIZE]).ok_or(HeaderError::UnknownRamSize(rom[RAM_SIZE]))?;
+
+        let licensee = match rom[OLD_LICENSEE] {
+            USE_NEW_LICENSEE => Licensee::New([rom[NEW_LICENSEE], rom[NEW_LICENSEE + 1]]),
+            code => Licensee::Old(code),
+        };
+
+        let destination = match rom[DESTINATION] {
+            0x00 => Destination::Japan,
+            _ => Destination::Overseas,
+        };
+
+        Ok(Self {
+            title,
+            manufacturer,
+            cgb,
+            // The SGB functions are only enabled with the old licensee code set to 0x33
+            sgb: rom[SGB_FLAG] == 0x03 && rom[OLD_LICENSEE] == USE_NEW_LICENSEE,
+            licensee,
+            cartridge_type,
+            rom_size,
+            ram_size,
+            destination,
+            version: rom[VERSION],
+            header_checksum: rom[HEADER_CHECKSUM],
+            global_checksum: (rom[GLOBAL_CHECKSUM] as u16) << 8 | rom[GLOBAL_CHECKSUM + 1] as u16,
+        })
+    }
+
+    // Checks the logo and header checksum the boot ROM would refuse to run without,
+    // then the global checksum that nothing on the console ever looks at
+    pub fn verify(&self, rom: &[u8]) -> Result<(), HeaderError> {
+        if rom.len() < HEADER_END {
+            return Err(HeaderError::TooShort { len: rom.len() });
+        }
+
+        if rom[LOGO..LOGO + NINTENDO_LOGO.len()] != NINTENDO_LOGO {
+            return Err(HeaderError::BadLogo);
+        }
+
+        let computed = header_checksum(rom);
+        if computed != self.header_checksum {
+            return Err(HeaderError::HeaderChecksum {
+                computed,
+                stored: self.header_checksum,
+            });
+        }
+
+        let computed = global_checksum(rom);
+        if computed != self.global_checksum {
+            return Err(HeaderError::GlobalChecksum {
+                computed,
+                stored: self.global_checksum,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+// The boot ROM's checksum over the title through the version byte
+pub fn header_checksum(rom: &[u8]) -> u8 {
+    rom[TITLE..HEADER_CHECKSUM]
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_sub(*byte).wrapping_sub(1))
+}
+
+// Sum of every byte in the ROM except the two holding the checksum itself
+pub fn global_checksum(rom: &[u8]) -> u16 {
+    rom.iter()
+        .enumerate()
+        .filter(|(i, _)| *i != GLOBAL_CHECKSUM && *i != GLOBAL_CHECKSUM + 1)
+        .fold(0u16, |sum, (_, byte)| sum.wrapping_add(*byte as u16))
+}
+
+// Titles are padded with zeros and may hold anything, bytes that are not valid UTF-8
+// are replaced rather than rejected. The CGB flag used to be the last title byte,
+// and later cartridges also gave four bytes to a manufacturer code
+fn parse_title(rom: &[u8], cgb: CgbSupport) -> (String, Option<String>) {
+    let (end, manufacturer) = match cgb {
+        CgbSupport::None => (CGB_FLAG + 1, None),
+        _ => {
+            let code = &rom[MANUFACTURER..CGB_FLAG];
+
+            if code
+                .iter()
+                .all(|byte| byte.is_ascii_uppercase() || byte.is_ascii_digit())
+            {
+                (
+                    MANUFACTURER,
+                    Some(String::from_utf8_lossy(code).into_owned()),
+                )
+            } else {
+                (CGB_FLAG, None)
+            }
+        }
+    };
+
+    let title = &rom[TITLE..end];
+    let title = match title.iter().position(|byte| *byte == 0) {
+        Some(len) => &title[..len],
+        None => title,
+    };
+
+    (
+        String::from_utf8_lossy(title).trim_end().to_string(),
+        manufacturer,
+    )
+}
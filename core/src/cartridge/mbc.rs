@@ -0,0 +1,112 @@
+pub mod mbc1;
+pub mod mbc2;
+pub mod mbc3;
+pub mod mbc5;
+
+use crate::cartridge::header::{Controller, Header};
+use crate::cartridge::rtc::Rtc;
+use crate::log;
+
+pub const ROM_BANK_SIZE: usize = 0x4000;
+pub const RAM_BANK_SIZE: usize = 0x2000;
+
+// Called with the new motor state and the cycle it changed at whenever a rumble
+// cartridge turns its motor on or off
+pub type RumbleCallback = Box<dyn FnMut(bool, usize) + Send>;
+
+// A memory bank controller decides which part of the ROM and RAM the CPU sees. The
+// bus asks for the mapping one 256 byte page at a time and asks again after every
+// write to the controller's registers
+pub trait Mbc: Send {
+    // Offset into the ROM image for a CPU address between 0x0000 and 0x7FFF
+    fn rom_offset(&self, addr: u16) -> usize;
+
+    // Offset into the RAM for a CPU address between 0xA000 and 0xBFFF, or None when
+    // the RAM is disabled or missing
+    fn ram_offset(&self, addr: u16) -> Option<usize>;
+
+    // Writes to 0x0000-0x7FFF land in the controller's registers
+    fn write(&mut self, addr: u16, data: u8, now: usize);
+
+    // Some controllers can map their own registers over 0xA000-0xBFFF instead of RAM,
+    // the bus sends accesses there to read_register and write_register meanwhile
+    fn registers_mapped(&self) -> bool {
+        false
+    }
+
+    fn read_register(&self, _addr: u16) -> u8 {
+        0xFF
+    }
+
+    fn write_register(&mut self, _addr: u16, _data: u8, _now: usize) {}
+
+    fn rtc(&mut self) -> Option<&mut Rtc> {
+        None
+    }
+
+    // Data lines the RAM is wired to, the others read back as 1
+    fn ram_bits(&self) -> u8 {
+        0xFF
+    }
+
+    // Controllers without a motor never call it
+    fn on_rumble(&mut self, _callback: RumbleCallback) {}
+}
+
+// Plain 32 KiB ROM with at most 8 KiB of RAM wired straight to the bus
+pub struct NoMbc {
+    ram_size: usize,
+}
+
+impl NoMbc {
+    pub fn new(ram_size: usize) -> Self {
+        Self { ram_size }
+    }
+}
+
+impl Mbc for NoMbc {
+    fn rom_offset(&self, addr: u16) -> usize {
+        addr as usize
+    }
+
+    fn ram_offset(&self, addr: u16) -> Option<usize> {
+        let offset = (addr - 0xA000) as usize;
+
+        if offset < self.ram_size {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
+    fn write(&mut self, _addr: u16, _data: u8, _now: usize) {}
+}
+
+// RAM that comes with the controller is not listed in the header
+pub fn ram_size(header: &Header) -> usize {
+    match header.cartridge_type.controller() {
+        Controller::Mbc2 => mbc2::MBC2_RAM_SIZE,
+        _ => header.ram_size.bytes(),
+    }
+}
+
+pub fn new(header: &Header, rom: &[u8]) -> Box<dyn Mbc> {
+    let ram_size = ram_size(header);
+
+    match header.cartridge_type.controller() {
+        Controller::None => Box::new(NoMbc::new(ram_size)),
+        Controller::Mbc1 => Box::new(mbc1::Mbc1::new(header, rom)),
+        Controller::Mbc2 => Box::new(mbc2::Mbc2::new(header)),
+        Controller::Mbc3 => Box::new(mbc3::Mbc3::new(header)),
+        Controller::Mbc5 => Box::new(mbc5::Mbc5::new(header)),
+        controller => {
+            log!(
+                Mbc,
+                Warn,
+                "{:?} is not supported, banking is disabled",
+                controller
+            );
+            Box::new(NoMbc::new(ram_size))
+        }
+    }
+}
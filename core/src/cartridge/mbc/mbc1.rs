@@ -0,0 +1,125 @@
+use crate::cartridge::header::{Header, NINTENDO_LOGO};
+use crate::cartridge::mbc::{Mbc, RAM_BANK_SIZE, ROM_BANK_SIZE};
+
+// MBC1M multicarts are 1 MiB and carry a second game with its own header every 16 banks
+const MULTICART_SIZE: usize = 64 * ROM_BANK_SIZE;
+const MULTICART_GAMES: usize = 4;
+const LOGO: usize = 0x104;
+
+pub struct Mbc1 {
+    ram_enabled: bool,
+    // 5 bit register selecting the 0x4000-0x7FFF bank, written as 0 it selects 1
+    bank1: u8,
+    // 2 bit register that provides the upper ROM bank bits or the RAM bank
+    bank2: u8,
+    // In mode 1 bank2 also applies to 0x0000-0x3FFF and to RAM
+    mode: bool,
+    rom_banks: usize,
+    ram_size: usize,
+    // The multicart wires bank2 one bit lower and drops the top bit of bank1
+    multicart: bool,
+}
+
+impl Mbc1 {
+    pub fn new(header: &Header, rom: &[u8]) -> Self {
+        Self {
+            ram_enabled: false,
+            bank1: 1,
+            bank2: 0,
+            mode: false,
+            rom_banks: header.rom_size.banks(),
+            ram_size: header.ram_size.bytes(),
+            multicart: is_multicart(rom),
+        }
+    }
+
+    fn bank2_shift(&self) -> u8 {
+        if self.multicart {
+            4
+        } else {
+            5
+        }
+    }
+
+    fn low_bank(&self) -> usize {
+        if self.mode {
+            (self.bank2 << self.bank2_shift()) as usize
+        } else {
+            0
+        }
+    }
+
+    fn high_bank(&self) -> usize {
+        let bank1 = if self.multicart {
+            self.bank1 & 0x0F
+        } else {
+            self.bank1
+        };
+
+        ((self.bank2 << self.bank2_shift()) | bank1) as usize
+    }
+
+    fn ram_bank(&self) -> usize {
+        if self.mode {
+            self.bank2 as usize
+        } else {
+            0
+        }
+    }
+}
+
+impl Mbc for Mbc1 {
+    fn rom_offset(&self, addr: u16) -> usize {
+        let bank = match addr {
+            0x0000..=0x3FFF => self.low_bank(),
+            _ => self.high_bank(),
+        };
+
+        // Bank bits past the size of the ROM are not connected
+        let bank = bank & (self.rom_banks.next_power_of_two() - 1);
+
+        bank * ROM_BANK_SIZE + (addr as usize & (ROM_BANK_SIZE - 1))
+    }
+
+    fn ram_offset(&self, addr: u16) -> Option<usize> {
+        if !self.ram_enabled || self.ram_size == 0 {
+            return None;
+        }
+
+        let offset = self.ram_bank() * RAM_BANK_SIZE + (addr as usize & (RAM_BANK_SIZE - 1));
+
+        // Smaller chips are mirrored over the whole window
+        Some(offset & (self.ram_size - 1))
+    }
+
+    fn write(&mut self, addr: u16, data: u8, _now: usize) {
+        match addr {
+            0x0000..=0x1FFF => self.ram_enabled = data & 0x0F == 0x0A,
+            0x2000..=0x3FFF => {
+                // The zero check looks at all five bits, even if fewer are wired up
+                self.bank1 = match data & 0x1F {
+                    0 => 1,
+                    bank => bank,
+                };
+            }
+            0x4000..=0x5FFF => self.bank2 = data & 0x03,
+            _ => self.mode = data & 0x01 != 0,
+        }
+    }
+}
+
+// Multicarts are detected by the logo of a game header in bank 0x10, 0x20 or 0x30
+fn is_multicart(rom: &[u8]) -> bool {
+    if rom.len() != MULTICART_SIZE {
+        return false;
+    }
+
+    let games = (0..MULTICART_GAMES)
+        .filter(|game| {
+            let start = game * 0x10 * ROM_BANK_SIZE + LOGO;
+            rom[start..start + NINTENDO_LOGO.len()] == NINTENDO_LOGO
+        })
+        .count();
+
+    games > 1
+}
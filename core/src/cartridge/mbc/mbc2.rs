@@ -0,0 +1,63 @@
+use crate::cartridge::header::Header;
+use crate::cartridge::mbc::{Mbc, ROM_BANK_SIZE};
+
+// 512 half bytes inside the controller, echoed all over 0xA000-0xBFFF
+pub const MBC2_RAM_SIZE: usize = 0x200;
+
+// Address bit 8 picks the register for writes to 0x0000-0x3FFF
+const REGISTER_SELECT: u16 = 0x0100;
+
+pub struct Mbc2 {
+    ram_enabled: bool,
+    // 4 bit register, written as 0 it selects 1
+    rom_bank: u8,
+    rom_banks: usize,
+}
+
+impl Mbc2 {
+    pub fn new(header: &Header) -> Self {
+        Self {
+            ram_enabled: false,
+            rom_bank: 1,
+            rom_banks: header.rom_size.banks(),
+        }
+    }
+}
+
+impl Mbc for Mbc2 {
+    fn rom_offset(&self, addr: u16) -> usize {
+        let bank = match addr {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank as usize & (self.rom_banks.next_power_of_two() - 1),
+        };
+
+        bank * ROM_BANK_SIZE + (addr as usize & (ROM_BANK_SIZE - 1))
+    }
+
+    fn ram_offset(&self, addr: u16) -> Option<usize> {
+        if self.ram_enabled {
+            Some(addr as usize & (MBC2_RAM_SIZE - 1))
+        } else {
+            None
+        }
+    }
+
+    fn write(&mut self, addr: u16, data: u8, _now: usize) {
+        match addr {
+            0x0000..=0x3FFF if addr & REGISTER_SELECT == 0 => {
+                self.ram_enabled = data & 0x0F == 0x0A
+            }
+            0x0000..=0x3FFF => {
+                self.rom_bank = match data & 0x0F {
+                    0 => 1,
+                    bank => bank,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    fn ram_bits(&self) -> u8 {
+        0x0F
+    }
+}
@@ -0,0 +1,113 @@
+use crate::cartridge::header::Header;
+use crate::cartridge::mbc::{Mbc, RAM_BANK_SIZE, ROM_BANK_SIZE};
+use crate::cartridge::rtc::{Rtc, RTC_DH, RTC_S};
+
+// Anything past 2 MiB of ROM or 32 KiB of RAM needs the MBC30
+const MBC3_ROM_BANKS: usize = 128;
+const MBC3_RAM_SIZE: usize = 4 * RAM_BANK_SIZE;
+
+pub struct Mbc3 {
+    // Enables both the RAM and the clock registers
+    ram_enabled: bool,
+    rom_bank: u8,
+    // 0x00-0x07 select a RAM bank, 0x08-0x0C a clock register
+    ram_bank: u8,
+    // The clock is latched by writing 0x00 and then 0x01
+    latch: u8,
+    rom_banks: usize,
+    ram_size: usize,
+    // The MBC30 has one more ROM bank bit and twice the RAM banks
+    mbc30: bool,
+    rtc: Option<Rtc>,
+}
+
+impl Mbc3 {
+    pub fn new(header: &Header) -> Self {
+        let rom_banks = header.rom_size.banks();
+        let ram_size = header.ram_size.bytes();
+
+        Self {
+            ram_enabled: false,
+            rom_bank: 1,
+            ram_bank: 0,
+            latch: 0xFF,
+            rom_banks,
+            ram_size,
+            mbc30: rom_banks > MBC3_ROM_BANKS || ram_size > MBC3_RAM_SIZE,
+            rtc: header.cartridge_type.has_timer().then(Rtc::new),
+        }
+    }
+
+    fn rtc_selected(&self) -> bool {
+        (RTC_S..=RTC_DH).contains(&self.ram_bank)
+    }
+}
+
+impl Mbc for Mbc3 {
+    fn rom_offset(&self, addr: u16) -> usize {
+        let bank = match addr {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank as usize & (self.rom_banks.next_power_of_two() - 1),
+        };
+
+        bank * ROM_BANK_SIZE + (addr as usize & (ROM_BANK_SIZE - 1))
+    }
+
+    fn ram_offset(&self, addr: u16) -> Option<usize> {
+        if !self.ram_enabled || self.ram_size == 0 || self.rtc_selected() {
+            return None;
+        }
+
+        let bank = if self.mbc30 {
+            self.ram_bank & 0x07
+        } else {
+            self.ram_bank & 0x03
+        };
+
+        let offset = bank as usize * RAM_BANK_SIZE + (addr as usize & (RAM_BANK_SIZE - 1));
+
+        Some(offset & (self.ram_size - 1))
+    }
+
+    fn write(&mut self, addr: u16, data: u8, now: usize) {
+        match addr {
+            0x0000..=0x1FFF => self.ram_enabled = data & 0x0F == 0x0A,
+            0x2000..=0x3FFF => {
+                let bank = if self.mbc30 { data } else { data & 0x7F };
+
+                self.rom_bank = bank.max(1);
+            }
+            0x4000..=0x5FFF => self.ram_bank = data & 0x0F,
+            _ => {
+                if self.latch == 0x00 && data == 0x01 {
+                    if let Some(rtc) = &mut self.rtc {
+                        rtc.latch(now);
+                    }
+                }
+
+                self.latch = data;
+            }
+        }
+    }
+
+    fn registers_mapped(&self) -> bool {
+        self.ram_enabled && self.rtc.is_some() && self.rtc_selected()
+    }
+
+    fn read_register(&self, _addr: u16) -> u8 {
+        match &self.rtc {
+            Some(rtc) => rtc.read(self.ram_bank),
+            None => 0xFF,
+        }
+    }
+
+    fn write_register(&mut self, _addr: u16, data: u8, now: usize) {
+        if let Some(rtc) = &mut self.rtc {
+            rtc.write(self.ram_bank, data, now);
+        }
+    }
+
+    fn rtc(&mut self) -> Option<&mut Rtc> {
+        self.rtc.as_mut()
+    }
+}
@@ -0,0 +1,84 @@
+use crate::cartridge::header::Header;
+use crate::cartridge::mbc::{Mbc, RumbleCallback, RAM_BANK_SIZE, ROM_BANK_SIZE};
+
+// On rumble cartridges bit 3 of the RAM bank register drives the motor instead
+const RUMBLE_MOTOR: u8 = 0x08;
+
+pub struct Mbc5 {
+    // Unlike the older controllers only exactly 0x0A enables the RAM
+    ram_enabled: bool,
+    // 9 bit register, bank 0 can be mapped to 0x4000-0x7FFF as well
+    rom_bank: u16,
+    ram_bank: u8,
+    rom_banks: usize,
+    ram_size: usize,
+    rumble: bool,
+    motor: bool,
+    on_rumble: Option<RumbleCallback>,
+}
+
+impl Mbc5 {
+    pub fn new(header: &Header) -> Self {
+        Self {
+            ram_enabled: false,
+            rom_bank: 1,
+            ram_bank: 0,
+            rom_banks: header.rom_size.banks(),
+            ram_size: header.ram_size.bytes(),
+            rumble: header.cartridge_type.has_rumble(),
+            motor: false,
+            on_rumble: None,
+        }
+    }
+
+    fn set_motor(&mut self, motor: bool, now: usize) {
+        if motor == self.motor {
+            return;
+        }
+
+        self.motor = motor;
+
+        if let Some(callback) = &mut self.on_rumble {
+            callback(motor, now);
+        }
+    }
+}
+
+impl Mbc for Mbc5 {
+    fn rom_offset(&self, addr: u16) -> usize {
+        let bank = match addr {
+            0x0000..=0x3FFF => 0,
+            _ => self.rom_bank as usize & (self.rom_banks.next_power_of_two() - 1),
+        };
+
+        bank * ROM_BANK_SIZE + (addr as usize & (ROM_BANK_SIZE - 1))
+    }
+
+    fn ram_offset(&self, addr: u16) -> Option<usize> {
+        if !self.ram_enabled || self.ram_size == 0 {
+            return None;
+        }
+
+        let offset = self.ram_bank as usize * RAM_BANK_SIZE + (addr as usize & (RAM_BANK_SIZE - 1));
+
+        Some(offset & (self.ram_size - 1))
+    }
+
+    fn write(&mut self, addr: u16, data: u8, now: usize) {
+        match addr {
+            0x0000..=0x1FFF => self.ram_enabled = data == 0x0A,
+            0x2000..=0x2FFF => self.rom_bank = (self.rom_bank & 0x100) | data as u16,
+            0x3000..=0x3FFF => self.rom_bank = (self.rom_bank & 0xFF) | ((data as u16 & 0x01) << 8),
+            0x4000..=0x5FFF if self.rumble => {
+                self.ram_bank = data & 0x07;
+                self.set_motor(data & RUMBLE_MOTOR != 0, now);
+            }
+            0x4000..=0x5FFF => self.ram_bank = data & 0x0F,
+            _ => {}
+        }
+    }
+
+    fn on_rumble(&mut self, callback: RumbleCallback) {
+        self.on_rumble = Some(callback);
+    }
+}
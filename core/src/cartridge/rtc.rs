@@ -0,0 +1,222 @@
+use std::time::SystemTime;
+
+const CYCLES_PER_SECOND: u64 = 4_194_304;
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+// Register numbers as selected through the MBC3 RAM bank register
+pub const RTC_S: u8 = 0x08;
+pub const RTC_M: u8 = 0x09;
+pub const RTC_H: u8 = 0x0A;
+pub const RTC_DL: u8 = 0x0B;
+pub const RTC_DH: u8 = 0x0C;
+
+const DH_DAY: u8 = 0x01;
+const DH_HALT: u8 = 0x40;
+const DH_CARRY: u8 = 0x80;
+
+const DAYS: u16 = 512;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockSource {
+    // One second every 4194304 emulated cycles, runs exactly as fast as the game
+    Cycles,
+    // Follows the host's wall clock, keeps running while the emulator is paused
+    Host,
+}
+
+// The MBC3 real time clock. The counters advance lazily whenever the clock is
+// synced, the CPU only ever sees the copy made by the latest latch
+pub struct Rtc {
+    pub seconds: u8,
+    pub minutes: u8,
+    pub hours: u8,
+    pub days: u16,
+    pub halt: bool,
+    // Set when the day counter overflows, stays set until software clears it
+    pub carry: bool,
+    latched: [u8; 5],
+    source: ClockSource,
+    last_cycles: usize,
+    last_host: SystemTime,
+    // Progress towards the next second in cycles or nanoseconds, depending on source
+    subsecond: u64,
+}
+
+impl Rtc {
+    pub fn new() -> Self {
+        Self {
+            seconds: 0,
+            minutes: 0,
+            hours: 0,
+            days: 0,
+            halt: false,
+            carry: false,
+            latched: [0; 5],
+            source: ClockSource::Cycles,
+            last_cycles: 0,
+            last_host: SystemTime::now(),
+            subsecond: 0,
+        }
+    }
+
+    pub fn source(&self) -> ClockSource {
+        self.source
+    }
+
+    pub fn set_source(&mut self, source: ClockSource, now: usize) {
+        self.sync(now);
+        self.source = source;
+        self.subsecond = 0;
+    }
+
+    // Brings the counters up to date, both time bases are followed so switching the
+    // source never counts the same stretch twice
+    pub fn sync(&mut self, now: usize) {
+        let cycles = now.saturating_sub(self.last_cycles) as u64;
+        self.last_cycles = now;
+
+        let host = SystemTime::now();
+        let nanos = host
+            .duration_since(self.last_host)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        self.last_host = host;
+
+        if self.halt {
+            return;
+        }
+
+        let (elapsed, unit) = match self.source {
+            ClockSource::Cycles => (cycles, CYCLES_PER_SECOND),
+            ClockSource::Host => (nanos, NANOS_PER_SECOND),
+        };
+
+        self.subsecond += elapsed;
+        self.advance(self.subsecond / unit);
+        self.subsecond %= unit;
+    }
+
+    pub fn latch(&mut self, now: usize) {
+        self.sync(now);
+
+        self.latched = [
+            self.seconds,
+            self.minutes,
+            self.hours,
+            self.days as u8,
+            self.day_high(),
+        ];
+    }
+
+    pub fn read(&self, reg: u8) -> u8 {
+        match reg {
+            RTC_S..=RTC_DH => self.latched[(reg - RTC_S) as usize],
+            _ => 0xFF,
+        }
+    }
+
+    // Writes go to the running counters, the latched copy follows so a game reading
+    // back what it wrote does not need to latch again
+    pub fn write(&mut self, reg: u8, data: u8, now: usize) {
+        self.sync(now);
+
+        match reg {
+            RTC_S => {
+                // Writing the seconds restarts the divider that counts them
+                self.seconds = data & 0x3F;
+                self.subsecond = 0;
+            }
+            RTC_M => self.minutes = data & 0x3F,
+            RTC_H => self.hours = data & 0x1F,
+            RTC_DL => self.days = (self.days & 0x100) | data as u16,
+            RTC_DH => {
+                self.days = (self.days & 0xFF) | (((data & DH_DAY) as u16) << 8);
+                self.halt = data & DH_HALT != 0;
+                self.carry = data & DH_CARRY != 0;
+            }
+            _ => return,
+        }
+
+        let value = match reg {
+            RTC_S => self.seconds,
+            RTC_M => self.minutes,
+            RTC_H => self.hours,
+            RTC_DL => self.days as u8,
+            _ => self.day_high(),
+        };
+
+        self.latched[(reg - RTC_S) as usize] = value;
+    }
+
+    fn day_high(&self) -> u8 {
+        let mut value = (self.days >> 8) as u8 & DH_DAY;
+
+        if self.halt {
+            value |= DH_HALT;
+        }
+
+        if self.carry {
+            value |= DH_CARRY;
+        }
+
+        value
+    }
+
+    fn advance(&mut self, mut seconds: u64) {
+        while seconds > 0 {
+            // Counters set out of range by software count up to their bit limit and
+            // wrap to 0 without a carry, so those have to be stepped one by one
+            if self.seconds >= 60 || self.minutes >= 60 || self.hours >= 24 {
+                self.tick();
+                seconds -= 1;
+                continue;
+            }
+
+            let total = ((self.days as u64 * 24 + self.hours as u64) * 60 + self.minutes as u64)
+                * 60
+                + self.seconds as u64
+                + seconds;
+
+            let days = total / 86400;
+
+            self.seconds = (total % 60) as u8;
+            self.minutes = (total / 60 % 60) as u8;
+            self.hours = (total / 3600 % 24) as u8;
+            self.days = (days % DAYS as u64) as u16;
+            self.carry |= days >= DAYS as u64;
+
+            return;
+        }
+    }
+
+    fn tick(&mut self) {
+        self.seconds = (self.seconds + 1) & 0x3F;
+        if self.seconds != 60 {
+            return;
+        }
+        self.seconds = 0;
+
+        self.minutes = (self.minutes + 1) & 0x3F;
+        if self.minutes != 60 {
+            return;
+        }
+        self.minutes = 0;
+
+        self.hours = (self.hours + 1) & 0x1F;
+        if self.hours != 24 {
+            return;
+        }
+        self.hours = 0;
+
+        self.days += 1;
+        if self.days == DAYS {
+            self.days = 0;
+            self.carry = true;
+        }
+    }
+}
+
+impl Default for Rtc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,2 +1,5 @@
+pub mod alu;
+pub mod interface;
+pub mod opcodes;
 pub mod registers;
 pub mod sm83;
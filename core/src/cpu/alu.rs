@@ -0,0 +1,183 @@
+use crate::cpu::registers::{CARRY, HALF_CARRY, SUBTRACT, ZERO};
+
+// Every operation returns the result together with the new value of F. Operations
+// that leave some flags untouched take the current F as input.
+
+fn zero(result: u8) -> u8 {
+    if result == 0 {
+        ZERO
+    } else {
+        0
+    }
+}
+
+fn flag(cond: bool, flag: u8) -> u8 {
+    if cond {
+        flag
+    } else {
+        0
+    }
+}
+
+pub fn add(a: u8, b: u8, carry: bool) -> (u8, u8) {
+    let c = carry as u8;
+    let result = a.wrapping_add(b).wrapping_add(c);
+
+    let flags = zero(result)
+        | flag((a & 0x0f) + (b & 0x0f) + c > 0x0f, HALF_CARRY)
+        | flag(a as u16 + b as u16 + c as u16 > 0xff, CARRY);
+
+    (result, flags)
+}
+
+pub fn sub(a: u8, b: u8, carry: bool) -> (u8, u8) {
+    let c = carry as u8;
+    let result = a.wrapping_sub(b).wrapping_sub(c);
+
+    let flags = zero(result)
+        | SUBTRACT
+        | flag((a & 0x0f) < (b & 0x0f) + c, HALF_CARRY)
+        | flag((a as u16) < b as u16 + c as u16, CARRY);
+
+    (result, flags)
+}
+
+pub fn and(a: u8, b: u8) -> (u8, u8) {
+    let result = a & b;
+
+    (result, zero(result) | HALF_CARRY)
+}
+
+pub fn or(a: u8, b: u8) -> (u8, u8) {
+    let result = a | b;
+
+    (result, zero(result))
+}
+
+pub fn xor(a: u8, b: u8) -> (u8, u8) {
+    let result = a ^ b;
+
+    (result, zero(result))
+}
+
+pub fn inc(data: u8, f: u8) -> (u8, u8) {
+    let result = data.wrapping_add(1);
+
+    let flags = zero(result) | flag(data & 0x0f == 0x0f, HALF_CARRY) | (f & CARRY);
+
+    (result, flags)
+}
+
+pub fn dec(data: u8, f: u8) -> (u8, u8) {
+    let result = data.wrapping_sub(1);
+
+    let flags = zero(result) | SUBTRACT | flag(data & 0x0f == 0x00, HALF_CARRY) | (f & CARRY);
+
+    (result, flags)
+}
+
+// ADD HL, rr: H and C come from bits 11 and 15, Z is left untouched
+pub fn add_word(a: u16, b: u16, f: u8) -> (u16, u8) {
+    let result = a.wrapping_add(b);
+
+    let flags = (f & ZERO)
+        | flag((a & 0x0fff) + (b & 0x0fff) > 0x0fff, HALF_CARRY)
+        | flag(a as u32 + b as u32 > 0xffff, CARRY);
+
+    (result, flags)
+}
+
+// ADD SP, e and LD HL, SP+e: H and C come from the unsigned low byte addition
+pub fn add_word_signed(a: u16, e: i8) -> (u16, u8) {
+    let b = e as u8;
+    let result = a.wrapping_add(e as u16);
+
+    let flags = flag((a & 0x0f) + (b as u16 & 0x0f) > 0x0f, HALF_CARRY)
+        | flag((a & 0xff) + b as u16 > 0xff, CARRY);
+
+    (result, flags)
+}
+
+pub fn daa(a: u8, f: u8) -> (u8, u8) {
+    let mut adjust = 0;
+    let mut carry = f & CARRY != 0;
+
+    let result = if f & SUBTRACT == 0 {
+        if f & HALF_CARRY != 0 || a & 0x0f > 0x09 {
+            adjust |= 0x06;
+        }
+
+        if carry || a > 0x99 {
+            adjust |= 0x60;
+            carry = true;
+        }
+
+        a.wrapping_add(adjust)
+    } else {
+        if f & HALF_CARRY != 0 {
+            adjust |= 0x06;
+        }
+
+        if carry {
+            adjust |= 0x60;
+        }
+
+        a.wrapping_sub(adjust)
+    };
+
+    let flags = zero(result) | (f & SUBTRACT) | flag(carry, CARRY);
+
+    (result, flags)
+}
+
+pub fn rlc(data: u8) -> (u8, u8) {
+    let result = data.rotate_left(1);
+
+    (result, zero(result) | flag(data & 0x80 != 0, CARRY))
+}
+
+pub fn rrc(data: u8) -> (u8, u8) {
+    let result = data.rotate_right(1);
+
+    (result, zero(result) | flag(data & 0x01 != 0, CARRY))
+}
+
+pub fn rl(data: u8, carry: bool) -> (u8, u8) {
+    let result = (data << 1) | carry as u8;
+
+    (result, zero(result) | flag(data & 0x80 != 0, CARRY))
+}
+
+pub fn rr(data: u8, carry: bool) -> (u8, u8) {
+    let result = (data >> 1) | (carry as u8) << 7;
+
+    (result, zero(result) | flag(data & 0x01 != 0, CARRY))
+}
+
+pub fn sla(data: u8) -> (u8, u8) {
+    let result = data << 1;
+
+    (result, zero(result) | flag(data & 0x80 != 0, CARRY))
+}
+
+pub fn sra(data: u8) -> (u8, u8) {
+    let result = (data >> 1) | (data & 0x80);
+
+    (result, zero(result) | flag(data & 0x01 != 0, CARRY))
+}
+
+pub fn srl(data: u8) -> (u8, u8) {
+    let result = data >> 1;
+
+    (result, zero(result) | flag(data & 0x01 != 0, CARRY))
+}
+
+pub fn swap(data: u8) -> (u8, u8) {
+    let result = data.rotate_right(4);
+
+    (result, zero(result))
+}
+
+pub fn bit(bit: u8, data: u8, f: u8) -> u8 {
+    zero(data & (1 << bit)) | HALF_CARRY | (f & CARRY)
+}
@@ -0,0 +1,88 @@
+use crate::interrupts::Interrupt;
+use crate::memory::Memory;
+
+const P1: u16 = 0xFF00;
+const IF: u16 = 0xFF0F;
+const IE: u16 = 0xFFFF;
+
+// Everything the CPU needs from the rest of the machine. The CPU ticks once per
+// M-cycle before the access that goes with it, so an implementation sees the same
+// interleaving of time and memory traffic as the real bus
+pub trait MemoryInterface {
+    fn read(&mut self, addr: u16) -> u8;
+
+    fn write(&mut self, addr: u16, data: u8);
+
+    fn tick(&mut self, cycles: usize);
+
+    // IE & IF, by default read through the memory map like the CPU would see them
+    fn pending_interrupts(&mut self) -> u8 {
+        self.read(IE) & self.read(IF) & 0x1f
+    }
+
+    fn highest_pending(&mut self) -> Option<Interrupt> {
+        let pending = self.pending_interrupts();
+
+        Interrupt::ALL
+            .into_iter()
+            .find(|interrupt| pending & interrupt.bit() != 0)
+    }
+
+    fn acknowledge(&mut self, interrupt: Interrupt) {
+        let flag = self.read(IF);
+        self.write(IF, flag & !interrupt.bit());
+    }
+
+    // How long until something may change the interrupt lines, a halted CPU sleeps
+    // straight through to it. None means nothing is scheduled
+    fn cycles_to_next_event(&self) -> Option<usize> {
+        None
+    }
+
+    // Whether any selected joypad input line is low, which wakes the CPU from STOP
+    fn joypad_asserted(&mut self) -> bool {
+        self.read(P1) & 0x0F != 0x0F
+    }
+}
+
+// A plain 64 KiB of RAM with no devices behind it, IE and IF are ordinary bytes
+pub struct FlatMemory {
+    pub ram: Memory,
+    pub cycles: usize,
+}
+
+impl FlatMemory {
+    pub fn new() -> Self {
+        Self {
+            ram: Memory::new(0x10000),
+            cycles: 0,
+        }
+    }
+
+    // Copies the program to the given address, wrapping at the end of the address space
+    pub fn load(&mut self, addr: u16, program: &[u8]) {
+        for (i, byte) in program.iter().enumerate() {
+            self.ram.write(addr.wrapping_add(i as u16) as usize, *byte);
+        }
+    }
+}
+
+impl Default for FlatMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryInterface for FlatMemory {
+    fn read(&mut self, addr: u16) -> u8 {
+        self.ram.read(addr as usize)
+    }
+
+    fn write(&mut self, addr: u16, data: u8) {
+        self.ram.write(addr as usize, data);
+    }
+
+    fn tick(&mut self, cycles: usize) {
+        self.cycles += cycles;
+    }
+}
@@ -0,0 +1,646 @@
+use crate::cpu::registers::{ByteReg, Condition, WordReg};
+use std::fmt::Display;
+
+use Operand::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Byte(ByteReg),
+    Word(WordReg),
+    // (rr)
+    Indirect(WordReg),
+    // (HL+) and (HL-)
+    IndirectInc,
+    IndirectDec,
+    // (C), high page access at 0xFF00 + C
+    IndirectC,
+    Imm8,
+    Imm16,
+    // (nn)
+    IndirectImm16,
+    // (n), high page access at 0xFF00 + n
+    HighImm8,
+    // e, signed 8 bit offset
+    Signed8,
+    // SP+e
+    SPOffset,
+    Cond(Condition),
+    Bit(u8),
+    Vector(u8),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Opcode {
+    pub mnemonic: &'static str,
+    pub operands: &'static [Operand],
+    // Total length in bytes, including the opcode (and the $cb prefix)
+    pub length: u8,
+    // T-cycles when a conditional branch is not taken, or the only timing otherwise
+    pub cycles: usize,
+    // T-cycles when a conditional branch is taken
+    pub branch_cycles: Option<usize>,
+}
+
+impl Opcode {
+    const fn new(
+        mnemonic: &'static str,
+        operands: &'static [Operand],
+        length: u8,
+        cycles: usize,
+    ) -> Self {
+        Self {
+            mnemonic,
+            operands,
+            length,
+            cycles,
+            branch_cycles: None,
+        }
+    }
+
+    const fn branch(
+        mnemonic: &'static str,
+        operands: &'static [Operand],
+        length: u8,
+        cycles: usize,
+        branch_cycles: usize,
+    ) -> Self {
+        Self {
+            mnemonic,
+            operands,
+            length,
+            cycles,
+            branch_cycles: Some(branch_cycles),
+        }
+    }
+
+    pub fn condition(&self) -> Option<Condition> {
+        self.operands.iter().find_map(|operand| match operand {
+            Cond(cond) => Some(*cond),
+            _ => None,
+        })
+    }
+}
+
+// Illegal opcodes have no entry
+pub fn lookup(op: u8) -> Option<&'static Opcode> {
+    OPCODES[op as usize].as_ref()
+}
+
+pub fn lookup_cb(op: u8) -> &'static Opcode {
+    &CB_OPCODES[op as usize]
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Byte(reg) => write!(f, "{}", reg),
+            Word(reg) => write!(f, "{}", reg),
+            Indirect(reg) => write!(f, "({})", reg),
+            IndirectInc => write!(f, "(HL+)"),
+            IndirectDec => write!(f, "(HL-)"),
+            IndirectC => write!(f, "(C)"),
+            Imm8 => write!(f, "n"),
+            Imm16 => write!(f, "nn"),
+            IndirectImm16 => write!(f, "(nn)"),
+            HighImm8 => write!(f, "(n)"),
+            Signed8 => write!(f, "e"),
+            SPOffset => write!(f, "SP+e"),
+            Cond(cond) => write!(f, "{}", cond),
+            Bit(bit) => write!(f, "{}", bit),
+            Vector(vector) => write!(f, "{:02X}h", vector),
+        }
+    }
+}
+
+impl Display for Opcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.mnemonic)?;
+
+        for (i, operand) in self.operands.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{}{}", separator, operand)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[rustfmt::skip]
+pub static OPCODES: [Option<Opcode>; 256] = [
+    Some(Opcode::new("NOP", &[], 1, 4)), // 0x00
+    Some(Opcode::new("LD", &[Word(WordReg::BC), Imm16], 3, 12)), // 0x01
+    Some(Opcode::new("LD", &[Indirect(WordReg::BC), Byte(ByteReg::A)], 1, 8)), // 0x02
+    Some(Opcode::new("INC", &[Word(WordReg::BC)], 1, 8)), // 0x03
+    Some(Opcode::new("INC", &[Byte(ByteReg::B)], 1, 4)), // 0x04
+    Some(Opcode::new("DEC", &[Byte(ByteReg::B)], 1, 4)), // 0x05
+    Some(Opcode::new("LD", &[Byte(ByteReg::B), Imm8], 2, 8)), // 0x06
+    Some(Opcode::new("RLCA", &[], 1, 4)), // 0x07
+    Some(Opcode::new("LD", &[IndirectImm16, Word(WordReg::SP)], 3, 20)), // 0x08
+    Some(Opcode::new("ADD", &[Word(WordReg::HL), Word(WordReg::BC)], 1, 8)), // 0x09
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Indirect(WordReg::BC)], 1, 8)), // 0x0a
+    Some(Opcode::new("DEC", &[Word(WordReg::BC)], 1, 8)), // 0x0b
+    Some(Opcode::new("INC", &[Byte(ByteReg::C)], 1, 4)), // 0x0c
+    Some(Opcode::new("DEC", &[Byte(ByteReg::C)], 1, 4)), // 0x0d
+    Some(Opcode::new("LD", &[Byte(ByteReg::C), Imm8], 2, 8)), // 0x0e
+    Some(Opcode::new("RRCA", &[], 1, 4)), // 0x0f
+    Some(Opcode::new("STOP", &[], 2, 4)), // 0x10
+    Some(Opcode::new("LD", &[Word(WordReg::DE), Imm16], 3, 12)), // 0x11
+    Some(Opcode::new("LD", &[Indirect(WordReg::DE), Byte(ByteReg::A)], 1, 8)), // 0x12
+    Some(Opcode::new("INC", &[Word(WordReg::DE)], 1, 8)), // 0x13
+    Some(Opcode::new("INC", &[Byte(ByteReg::D)], 1, 4)), // 0x14
+    Some(Opcode::new("DEC", &[Byte(ByteReg::D)], 1, 4)), // 0x15
+    Some(Opcode::new("LD", &[Byte(ByteReg::D), Imm8], 2, 8)), // 0x16
+    Some(Opcode::new("RLA", &[], 1, 4)), // 0x17
+    Some(Opcode::new("JR", &[Signed8], 2, 12)), // 0x18
+    Some(Opcode::new("ADD", &[Word(WordReg::HL), Word(WordReg::DE)], 1, 8)), // 0x19
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Indirect(WordReg::DE)], 1, 8)), // 0x1a
+    Some(Opcode::new("DEC", &[Word(WordReg::DE)], 1, 8)), // 0x1b
+    Some(Opcode::new("INC", &[Byte(ByteReg::E)], 1, 4)), // 0x1c
+    Some(Opcode::new("DEC", &[Byte(ByteReg::E)], 1, 4)), // 0x1d
+    Some(Opcode::new("LD", &[Byte(ByteReg::E), Imm8], 2, 8)), // 0x1e
+    Some(Opcode::new("RRA", &[], 1, 4)), // 0x1f
+    Some(Opcode::branch("JR", &[Cond(Condition::NZ), Signed8], 2, 8, 12)), // 0x20
+    Some(Opcode::new("LD", &[Word(WordReg::HL), Imm16], 3, 12)), // 0x21
+    Some(Opcode::new("LD", &[IndirectInc, Byte(ByteReg::A)], 1, 8)), // 0x22
+    Some(Opcode::new("INC", &[Word(WordReg::HL)], 1, 8)), // 0x23
+    Some(Opcode::new("INC", &[Byte(ByteReg::H)], 1, 4)), // 0x24
+    Some(Opcode::new("DEC", &[Byte(ByteReg::H)], 1, 4)), // 0x25
+    Some(Opcode::new("LD", &[Byte(ByteReg::H), Imm8], 2, 8)), // 0x26
+    Some(Opcode::new("DAA", &[], 1, 4)), // 0x27
+    Some(Opcode::branch("JR", &[Cond(Condition::Z), Signed8], 2, 8, 12)), // 0x28
+    Some(Opcode::new("ADD", &[Word(WordReg::HL), Word(WordReg::HL)], 1, 8)), // 0x29
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), IndirectInc], 1, 8)), // 0x2a
+    Some(Opcode::new("DEC", &[Word(WordReg::HL)], 1, 8)), // 0x2b
+    Some(Opcode::new("INC", &[Byte(ByteReg::L)], 1, 4)), // 0x2c
+    Some(Opcode::new("DEC", &[Byte(ByteReg::L)], 1, 4)), // 0x2d
+    Some(Opcode::new("LD", &[Byte(ByteReg::L), Imm8], 2, 8)), // 0x2e
+    Some(Opcode::new("CPL", &[], 1, 4)), // 0x2f
+    Some(Opcode::branch("JR", &[Cond(Condition::NC), Signed8], 2, 8, 12)), // 0x30
+    Some(Opcode::new("LD", &[Word(WordReg::SP), Imm16], 3, 12)), // 0x31
+    Some(Opcode::new("LD", &[IndirectDec, Byte(ByteReg::A)], 1, 8)), // 0x32
+    Some(Opcode::new("INC", &[Word(WordReg::SP)], 1, 8)), // 0x33
+    Some(Opcode::new("INC", &[Indirect(WordReg::HL)], 1, 12)), // 0x34
+    Some(Opcode::new("DEC", &[Indirect(WordReg::HL)], 1, 12)), // 0x35
+    Some(Opcode::new("LD", &[Indirect(WordReg::HL), Imm8], 2, 12)), // 0x36
+    Some(Opcode::new("SCF", &[], 1, 4)), // 0x37
+    Some(Opcode::branch("JR", &[Cond(Condition::C), Signed8], 2, 8, 12)), // 0x38
+    Some(Opcode::new("ADD", &[Word(WordReg::HL), Word(WordReg::SP)], 1, 8)), // 0x39
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), IndirectDec], 1, 8)), // 0x3a
+    Some(Opcode::new("DEC", &[Word(WordReg::SP)], 1, 8)), // 0x3b
+    Some(Opcode::new("INC", &[Byte(ByteReg::A)], 1, 4)), // 0x3c
+    Some(Opcode::new("DEC", &[Byte(ByteReg::A)], 1, 4)), // 0x3d
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Imm8], 2, 8)), // 0x3e
+    Some(Opcode::new("CCF", &[], 1, 4)), // 0x3f
+    Some(Opcode::new("LD", &[Byte(ByteReg::B), Byte(ByteReg::B)], 1, 4)), // 0x40
+    Some(Opcode::new("LD", &[Byte(ByteReg::B), Byte(ByteReg::C)], 1, 4)), // 0x41
+    Some(Opcode::new("LD", &[Byte(ByteReg::B), Byte(ByteReg::D)], 1, 4)), // 0x42
+    Some(Opcode::new("LD", &[Byte(ByteReg::B), Byte(ByteReg::E)], 1, 4)), // 0x43
+    Some(Opcode::new("LD", &[Byte(ByteReg::B), Byte(ByteReg::H)], 1, 4)), // 0x44
+    Some(Opcode::new("LD", &[Byte(ByteReg::B), Byte(ByteReg::L)], 1, 4)), // 0x45
+    Some(Opcode::new("LD", &[Byte(ByteReg::B), Indirect(WordReg::HL)], 1, 8)), // 0x46
+    Some(Opcode::new("LD", &[Byte(ByteReg::B), Byte(ByteReg::A)], 1, 4)), // 0x47
+    Some(Opcode::new("LD", &[Byte(ByteReg::C), Byte(ByteReg::B)], 1, 4)), // 0x48
+    Some(Opcode::new("LD", &[Byte(ByteReg::C), Byte(ByteReg::C)], 1, 4)), // 0x49
+    Some(Opcode::new("LD", &[Byte(ByteReg::C), Byte(ByteReg::D)], 1, 4)), // 0x4a
+    Some(Opcode::new("LD", &[Byte(ByteReg::C), Byte(ByteReg::E)], 1, 4)), // 0x4b
+    Some(Opcode::new("LD", &[Byte(ByteReg::C), Byte(ByteReg::H)], 1, 4)), // 0x4c
+    Some(Opcode::new("LD", &[Byte(ByteReg::C), Byte(ByteReg::L)], 1, 4)), // 0x4d
+    Some(Opcode::new("LD", &[Byte(ByteReg::C), Indirect(WordReg::HL)], 1, 8)), // 0x4e
+    Some(Opcode::new("LD", &[Byte(ByteReg::C), Byte(ByteReg::A)], 1, 4)), // 0x4f
+    Some(Opcode::new("LD", &[Byte(ByteReg::D), Byte(ByteReg::B)], 1, 4)), // 0x50
+    Some(Opcode::new("LD", &[Byte(ByteReg::D), Byte(ByteReg::C)], 1, 4)), // 0x51
+    Some(Opcode::new("LD", &[Byte(ByteReg::D), Byte(ByteReg::D)], 1, 4)), // 0x52
+    Some(Opcode::new("LD", &[Byte(ByteReg::D), Byte(ByteReg::E)], 1, 4)), // 0x53
+    Some(Opcode::new("LD", &[Byte(ByteReg::D), Byte(ByteReg::H)], 1, 4)), // 0x54
+    Some(Opcode::new("LD", &[Byte(ByteReg::D), Byte(ByteReg::L)], 1, 4)), // 0x55
+    Some(Opcode::new("LD", &[Byte(ByteReg::D), Indirect(WordReg::HL)], 1, 8)), // 0x56
+    Some(Opcode::new("LD", &[Byte(ByteReg::D), Byte(ByteReg::A)], 1, 4)), // 0x57
+    Some(Opcode::new("LD", &[Byte(ByteReg::E), Byte(ByteReg::B)], 1, 4)), // 0x58
+    Some(Opcode::new("LD", &[Byte(ByteReg::E), Byte(ByteReg::C)], 1, 4)), // 0x59
+    Some(Opcode::new("LD", &[Byte(ByteReg::E), Byte(ByteReg::D)], 1, 4)), // 0x5a
+    Some(Opcode::new("LD", &[Byte(ByteReg::E), Byte(ByteReg::E)], 1, 4)), // 0x5b
+    Some(Opcode::new("LD", &[Byte(ByteReg::E), Byte(ByteReg::H)], 1, 4)), // 0x5c
+    Some(Opcode::new("LD", &[Byte(ByteReg::E), Byte(ByteReg::L)], 1, 4)), // 0x5d
+    Some(Opcode::new("LD", &[Byte(ByteReg::E), Indirect(WordReg::HL)], 1, 8)), // 0x5e
+    Some(Opcode::new("LD", &[Byte(ByteReg::E), Byte(ByteReg::A)], 1, 4)), // 0x5f
+    Some(Opcode::new("LD", &[Byte(ByteReg::H), Byte(ByteReg::B)], 1, 4)), // 0x60
+    Some(Opcode::new("LD", &[Byte(ByteReg::H), Byte(ByteReg::C)], 1, 4)), // 0x61
+    Some(Opcode::new("LD", &[Byte(ByteReg::H), Byte(ByteReg::D)], 1, 4)), // 0x62
+    Some(Opcode::new("LD", &[Byte(ByteReg::H), Byte(ByteReg::E)], 1, 4)), // 0x63
+    Some(Opcode::new("LD", &[Byte(ByteReg::H), Byte(ByteReg::H)], 1, 4)), // 0x64
+    Some(Opcode::new("LD", &[Byte(ByteReg::H), Byte(ByteReg::L)], 1, 4)), // 0x65
+    Some(Opcode::new("LD", &[Byte(ByteReg::H), Indirect(WordReg::HL)], 1, 8)), // 0x66
+    Some(Opcode::new("LD", &[Byte(ByteReg::H), Byte(ByteReg::A)], 1, 4)), // 0x67
+    Some(Opcode::new("LD", &[Byte(ByteReg::L), Byte(ByteReg::B)], 1, 4)), // 0x68
+    Some(Opcode::new("LD", &[Byte(ByteReg::L), Byte(ByteReg::C)], 1, 4)), // 0x69
+    Some(Opcode::new("LD", &[Byte(ByteReg::L), Byte(ByteReg::D)], 1, 4)), // 0x6a
+    Some(Opcode::new("LD", &[Byte(ByteReg::L), Byte(ByteReg::E)], 1, 4)), // 0x6b
+    Some(Opcode::new("LD", &[Byte(ByteReg::L), Byte(ByteReg::H)], 1, 4)), // 0x6c
+    Some(Opcode::new("LD", &[Byte(ByteReg::L), Byte(ByteReg::L)], 1, 4)), // 0x6d
+    Some(Opcode::new("LD", &[Byte(ByteReg::L), Indirect(WordReg::HL)], 1, 8)), // 0x6e
+    Some(Opcode::new("LD", &[Byte(ByteReg::L), Byte(ByteReg::A)], 1, 4)), // 0x6f
+    Some(Opcode::new("LD", &[Indirect(WordReg::HL), Byte(ByteReg::B)], 1, 8)), // 0x70
+    Some(Opcode::new("LD", &[Indirect(WordReg::HL), Byte(ByteReg::C)], 1, 8)), // 0x71
+    Some(Opcode::new("LD", &[Indirect(WordReg::HL), Byte(ByteReg::D)], 1, 8)), // 0x72
+    Some(Opcode::new("LD", &[Indirect(WordReg::HL), Byte(ByteReg::E)], 1, 8)), // 0x73
+    Some(Opcode::new("LD", &[Indirect(WordReg::HL), Byte(ByteReg::H)], 1, 8)), // 0x74
+    Some(Opcode::new("LD", &[Indirect(WordReg::HL), Byte(ByteReg::L)], 1, 8)), // 0x75
+    Some(Opcode::new("HALT", &[], 1, 4)), // 0x76
+    Some(Opcode::new("LD", &[Indirect(WordReg::HL), Byte(ByteReg::A)], 1, 8)), // 0x77
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Byte(ByteReg::B)], 1, 4)), // 0x78
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Byte(ByteReg::C)], 1, 4)), // 0x79
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Byte(ByteReg::D)], 1, 4)), // 0x7a
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Byte(ByteReg::E)], 1, 4)), // 0x7b
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Byte(ByteReg::H)], 1, 4)), // 0x7c
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Byte(ByteReg::L)], 1, 4)), // 0x7d
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Indirect(WordReg::HL)], 1, 8)), // 0x7e
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), Byte(ByteReg::A)], 1, 4)), // 0x7f
+    Some(Opcode::new("ADD", &[Byte(ByteReg::A), Byte(ByteReg::B)], 1, 4)), // 0x80
+    Some(Opcode::new("ADD", &[Byte(ByteReg::A), Byte(ByteReg::C)], 1, 4)), // 0x81
+    Some(Opcode::new("ADD", &[Byte(ByteReg::A), Byte(ByteReg::D)], 1, 4)), // 0x82
+    Some(Opcode::new("ADD", &[Byte(ByteReg::A), Byte(ByteReg::E)], 1, 4)), // 0x83
+    Some(Opcode::new("ADD", &[Byte(ByteReg::A), Byte(ByteReg::H)], 1, 4)), // 0x84
+    Some(Opcode::new("ADD", &[Byte(ByteReg::A), Byte(ByteReg::L)], 1, 4)), // 0x85
+    Some(Opcode::new("ADD", &[Byte(ByteReg::A), Indirect(WordReg::HL)], 1, 8)), // 0x86
+    Some(Opcode::new("ADD", &[Byte(ByteReg::A), Byte(ByteReg::A)], 1, 4)), // 0x87
+    Some(Opcode::new("ADC", &[Byte(ByteReg::A), Byte(ByteReg::B)], 1, 4)), // 0x88
+    Some(Opcode::new("ADC", &[Byte(ByteReg::A), Byte(ByteReg::C)], 1, 4)), // 0x89
+    Some(Opcode::new("ADC", &[Byte(ByteReg::A), Byte(ByteReg::D)], 1, 4)), // 0x8a
+    Some(Opcode::new("ADC", &[Byte(ByteReg::A), Byte(ByteReg::E)], 1, 4)), // 0x8b
+    Some(Opcode::new("ADC", &[Byte(ByteReg::A), Byte(ByteReg::H)], 1, 4)), // 0x8c
+    Some(Opcode::new("ADC", &[Byte(ByteReg::A), Byte(ByteReg::L)], 1, 4)), // 0x8d
+    Some(Opcode::new("ADC", &[Byte(ByteReg::A), Indirect(WordReg::HL)], 1, 8)), // 0x8e
+    Some(Opcode::new("ADC", &[Byte(ByteReg::A), Byte(ByteReg::A)], 1, 4)), // 0x8f
+    Some(Opcode::new("SUB", &[Byte(ByteReg::A), Byte(ByteReg::B)], 1, 4)), // 0x90
+    Some(Opcode::new("SUB", &[Byte(ByteReg::A), Byte(ByteReg::C)], 1, 4)), // 0x91
+    Some(Opcode::new("SUB", &[Byte(ByteReg::A), Byte(ByteReg::D)], 1, 4)), // 0x92
+    Some(Opcode::new("SUB", &[Byte(ByteReg::A), Byte(ByteReg::E)], 1, 4)), // 0x93
+    Some(Opcode::new("SUB", &[Byte(ByteReg::A), Byte(ByteReg::H)], 1, 4)), // 0x94
+    Some(Opcode::new("SUB", &[Byte(ByteReg::A), Byte(ByteReg::L)], 1, 4)), // 0x95
+    Some(Opcode::new("SUB", &[Byte(ByteReg::A), Indirect(WordReg::HL)], 1, 8)), // 0x96
+    Some(Opcode::new("SUB", &[Byte(ByteReg::A), Byte(ByteReg::A)], 1, 4)), // 0x97
+    Some(Opcode::new("SBC", &[Byte(ByteReg::A), Byte(ByteReg::B)], 1, 4)), // 0x98
+    Some(Opcode::new("SBC", &[Byte(ByteReg::A), Byte(ByteReg::C)], 1, 4)), // 0x99
+    Some(Opcode::new("SBC", &[Byte(ByteReg::A), Byte(ByteReg::D)], 1, 4)), // 0x9a
+    Some(Opcode::new("SBC", &[Byte(ByteReg::A), Byte(ByteReg::E)], 1, 4)), // 0x9b
+    Some(Opcode::new("SBC", &[Byte(ByteReg::A), Byte(ByteReg::H)], 1, 4)), // 0x9c
+    Some(Opcode::new("SBC", &[Byte(ByteReg::A), Byte(ByteReg::L)], 1, 4)), // 0x9d
+    Some(Opcode::new("SBC", &[Byte(ByteReg::A), Indirect(WordReg::HL)], 1, 8)), // 0x9e
+    Some(Opcode::new("SBC", &[Byte(ByteReg::A), Byte(ByteReg::A)], 1, 4)), // 0x9f
+    Some(Opcode::new("AND", &[Byte(ByteReg::A), Byte(ByteReg::B)], 1, 4)), // 0xa0
+    Some(Opcode::new("AND", &[Byte(ByteReg::A), Byte(ByteReg::C)], 1, 4)), // 0xa1
+    Some(Opcode::new("AND", &[Byte(ByteReg::A), Byte(ByteReg::D)], 1, 4)), // 0xa2
+    Some(Opcode::new("AND", &[Byte(ByteReg::A), Byte(ByteReg::E)], 1, 4)), // 0xa3
+    Some(Opcode::new("AND", &[Byte(ByteReg::A), Byte(ByteReg::H)], 1, 4)), // 0xa4
+    Some(Opcode::new("AND", &[Byte(ByteReg::A), Byte(ByteReg::L)], 1, 4)), // 0xa5
+    Some(Opcode::new("AND", &[Byte(ByteReg::A), Indirect(WordReg::HL)], 1, 8)), // 0xa6
+    Some(Opcode::new("AND", &[Byte(ByteReg::A), Byte(ByteReg::A)], 1, 4)), // 0xa7
+    Some(Opcode::new("XOR", &[Byte(ByteReg::A), Byte(ByteReg::B)], 1, 4)), // 0xa8
+    Some(Opcode::new("XOR", &[Byte(ByteReg::A), Byte(ByteReg::C)], 1, 4)), // 0xa9
+    Some(Opcode::new("XOR", &[Byte(ByteReg::A), Byte(ByteReg::D)], 1, 4)), // 0xaa
+    Some(Opcode::new("XOR", &[Byte(ByteReg::A), Byte(ByteReg::E)], 1, 4)), // 0xab
+    Some(Opcode::new("XOR", &[Byte(ByteReg::A), Byte(ByteReg::H)], 1, 4)), // 0xac
+    Some(Opcode::new("XOR", &[Byte(ByteReg::A), Byte(ByteReg::L)], 1, 4)), // 0xad
+    Some(Opcode::new("XOR", &[Byte(ByteReg::A), Indirect(WordReg::HL)], 1, 8)), // 0xae
+    Some(Opcode::new("XOR", &[Byte(ByteReg::A), Byte(ByteReg::A)], 1, 4)), // 0xaf
+    Some(Opcode::new("OR", &[Byte(ByteReg::A), Byte(ByteReg::B)], 1, 4)), // 0xb0
+    Some(Opcode::new("OR", &[Byte(ByteReg::A), Byte(ByteReg::C)], 1, 4)), // 0xb1
+    Some(Opcode::new("OR", &[Byte(ByteReg::A), Byte(ByteReg::D)], 1, 4)), // 0xb2
+    Some(Opcode::new("OR", &[Byte(ByteReg::A), Byte(ByteReg::E)], 1, 4)), // 0xb3
+    Some(Opcode::new("OR", &[Byte(ByteReg::A), Byte(ByteReg::H)], 1, 4)), // 0xb4
+    Some(Opcode::new("OR", &[Byte(ByteReg::A), Byte(ByteReg::L)], 1, 4)), // 0xb5
+    Some(Opcode::new("OR", &[Byte(ByteReg::A), Indirect(WordReg::HL)], 1, 8)), // 0xb6
+    Some(Opcode::new("OR", &[Byte(ByteReg::A), Byte(ByteReg::A)], 1, 4)), // 0xb7
+    Some(Opcode::new("CP", &[Byte(ByteReg::A), Byte(ByteReg::B)], 1, 4)), // 0xb8
+    Some(Opcode::new("CP", &[Byte(ByteReg::A), Byte(ByteReg::C)], 1, 4)), // 0xb9
+    Some(Opcode::new("CP", &[Byte(ByteReg::A), Byte(ByteReg::D)], 1, 4)), // 0xba
+    Some(Opcode::new("CP", &[Byte(ByteReg::A), Byte(ByteReg::E)], 1, 4)), // 0xbb
+    Some(Opcode::new("CP", &[Byte(ByteReg::A), Byte(ByteReg::H)], 1, 4)), // 0xbc
+    Some(Opcode::new("CP", &[Byte(ByteReg::A), Byte(ByteReg::L)], 1, 4)), // 0xbd
+    Some(Opcode::new("CP", &[Byte(ByteReg::A), Indirect(WordReg::HL)], 1, 8)), // 0xbe
+    Some(Opcode::new("CP", &[Byte(ByteReg::A), Byte(ByteReg::A)], 1, 4)), // 0xbf
+    Some(Opcode::branch("RET", &[Cond(Condition::NZ)], 1, 8, 20)), // 0xc0
+    Some(Opcode::new("POP", &[Word(WordReg::BC)], 1, 12)), // 0xc1
+    Some(Opcode::branch("JP", &[Cond(Condition::NZ), Imm16], 3, 12, 16)), // 0xc2
+    Some(Opcode::new("JP", &[Imm16], 3, 16)), // 0xc3
+    Some(Opcode::branch("CALL", &[Cond(Condition::NZ), Imm16], 3, 12, 24)), // 0xc4
+    Some(Opcode::new("PUSH", &[Word(WordReg::BC)], 1, 16)), // 0xc5
+    Some(Opcode::new("ADD", &[Byte(ByteReg::A), Imm8], 2, 8)), // 0xc6
+    Some(Opcode::new("RST", &[Vector(0x00)], 1, 16)), // 0xc7
+    Some(Opcode::branch("RET", &[Cond(Condition::Z)], 1, 8, 20)), // 0xc8
+    Some(Opcode::new("RET", &[], 1, 16)), // 0xc9
+    Some(Opcode::branch("JP", &[Cond(Condition::Z), Imm16], 3, 12, 16)), // 0xca
+    Some(Opcode::new("PREFIX", &[], 1, 4)), // 0xcb
+    Some(Opcode::branch("CALL", &[Cond(Condition::Z), Imm16], 3, 12, 24)), // 0xcc
+    Some(Opcode::new("CALL", &[Imm16], 3, 24)), // 0xcd
+    Some(Opcode::new("ADC", &[Byte(ByteReg::A), Imm8], 2, 8)), // 0xce
+    Some(Opcode::new("RST", &[Vector(0x08)], 1, 16)), // 0xcf
+    Some(Opcode::branch("RET", &[Cond(Condition::NC)], 1, 8, 20)), // 0xd0
+    Some(Opcode::new("POP", &[Word(WordReg::DE)], 1, 12)), // 0xd1
+    Some(Opcode::branch("JP", &[Cond(Condition::NC), Imm16], 3, 12, 16)), // 0xd2
+    None, // 0xd3 (illegal)
+    Some(Opcode::branch("CALL", &[Cond(Condition::NC), Imm16], 3, 12, 24)), // 0xd4
+    Some(Opcode::new("PUSH", &[Word(WordReg::DE)], 1, 16)), // 0xd5
+    Some(Opcode::new("SUB", &[Byte(ByteReg::A), Imm8], 2, 8)), // 0xd6
+    Some(Opcode::new("RST", &[Vector(0x10)], 1, 16)), // 0xd7
+    Some(Opcode::branch("RET", &[Cond(Condition::C)], 1, 8, 20)), // 0xd8
+    Some(Opcode::new("RETI", &[], 1, 16)), // 0xd9
+    Some(Opcode::branch("JP", &[Cond(Condition::C), Imm16], 3, 12, 16)), // 0xda
+    None, // 0xdb (illegal)
+    Some(Opcode::branch("CALL", &[Cond(Condition::C), Imm16], 3, 12, 24)), // 0xdc
+    None, // 0xdd (illegal)
+    Some(Opcode::new("SBC", &[Byte(ByteReg::A), Imm8], 2, 8)), // 0xde
+    Some(Opcode::new("RST", &[Vector(0x18)], 1, 16)), // 0xdf
+    Some(Opcode::new("LDH", &[HighImm8, Byte(ByteReg::A)], 2, 12)), // 0xe0
+    Some(Opcode::new("POP", &[Word(WordReg::HL)], 1, 12)), // 0xe1
+    Some(Opcode::new("LD", &[IndirectC, Byte(ByteReg::A)], 1, 8)), // 0xe2
+    None, // 0xe3 (illegal)
+    None, // 0xe4 (illegal)
+    Some(Opcode::new("PUSH", &[Word(WordReg::HL)], 1, 16)), // 0xe5
+    Some(Opcode::new("AND", &[Byte(ByteReg::A), Imm8], 2, 8)), // 0xe6
+    Some(Opcode::new("RST", &[Vector(0x20)], 1, 16)), // 0xe7
+    Some(Opcode::new("ADD", &[Word(WordReg::SP), Signed8], 2, 16)), // 0xe8
+    Some(Opcode::new("JP", &[Word(WordReg::HL)], 1, 4)), // 0xe9
+    Some(Opcode::new("LD", &[IndirectImm16, Byte(ByteReg::A)], 3, 16)), // 0xea
+    None, // 0xeb (illegal)
+    None, // 0xec (illegal)
+    None, // 0xed (illegal)
+    Some(Opcode::new("XOR", &[Byte(ByteReg::A), Imm8], 2, 8)), // 0xee
+    Some(Opcode::new("RST", &[Vector(0x28)], 1, 16)), // 0xef
+    Some(Opcode::new("LDH", &[Byte(ByteReg::A), HighImm8], 2, 12)), // 0xf0
+    Some(Opcode::new("POP", &[Word(WordReg::AF)], 1, 12)), // 0xf1
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), IndirectC], 1, 8)), // 0xf2
+    Some(Opcode::new("DI", &[], 1, 4)), // 0xf3
+    None, // 0xf4 (illegal)
+    Some(Opcode::new("PUSH", &[Word(WordReg::AF)], 1, 16)), // 0xf5
+    Some(Opcode::new("OR", &[Byte(ByteReg::A), Imm8], 2, 8)), // 0xf6
+    Some(Opcode::new("RST", &[Vector(0x30)], 1, 16)), // 0xf7
+    Some(Opcode::new("LD", &[Word(WordReg::HL), SPOffset], 2, 12)), // 0xf8
+    Some(Opcode::new("LD", &[Word(WordReg::SP), Word(WordReg::HL)], 1, 8)), // 0xf9
+    Some(Opcode::new("LD", &[Byte(ByteReg::A), IndirectImm16], 3, 16)), // 0xfa
+    Some(Opcode::new("EI", &[], 1, 4)), // 0xfb
+    None, // 0xfc (illegal)
+    None, // 0xfd (illegal)
+    Some(Opcode::new("CP", &[Byte(ByteReg::A), Imm8], 2, 8)), // 0xfe
+    Some(Opcode::new("RST", &[Vector(0x38)], 1, 16)), // 0xff
+];
+
+#[rustfmt::skip]
+pub static CB_OPCODES: [Opcode; 256] = [
+    Opcode::new("RLC", &[Byte(ByteReg::B)], 2, 8), // 0x00
+    Opcode::new("RLC", &[Byte(ByteReg::C)], 2, 8), // 0x01
+    Opcode::new("RLC", &[Byte(ByteReg::D)], 2, 8), // 0x02
+    Opcode::new("RLC", &[Byte(ByteReg::E)], 2, 8), // 0x03
+    Opcode::new("RLC", &[Byte(ByteReg::H)], 2, 8), // 0x04
+    Opcode::new("RLC", &[Byte(ByteReg::L)], 2, 8), // 0x05
+    Opcode::new("RLC", &[Indirect(WordReg::HL)], 2, 16), // 0x06
+    Opcode::new("RLC", &[Byte(ByteReg::A)], 2, 8), // 0x07
+    Opcode::new("RRC", &[Byte(ByteReg::B)], 2, 8), // 0x08
+    Opcode::new("RRC", &[Byte(ByteReg::C)], 2, 8), // 0x09
+    Opcode::new("RRC", &[Byte(ByteReg::D)], 2, 8), // 0x0a
+    Opcode::new("RRC", &[Byte(ByteReg::E)], 2, 8), // 0x0b
+    Opcode::new("RRC", &[Byte(ByteReg::H)], 2, 8), // 0x0c
+    Opcode::new("RRC", &[Byte(ByteReg::L)], 2, 8), // 0x0d
+    Opcode::new("RRC", &[Indirect(WordReg::HL)], 2, 16), // 0x0e
+    Opcode::new("RRC", &[Byte(ByteReg::A)], 2, 8), // 0x0f
+    Opcode::new("RL", &[Byte(ByteReg::B)], 2, 8), // 0x10
+    Opcode::new("RL", &[Byte(ByteReg::C)], 2, 8), // 0x11
+    Opcode::new("RL", &[Byte(ByteReg::D)], 2, 8), // 0x12
+    Opcode::new("RL", &[Byte(ByteReg::E)], 2, 8), // 0x13
+    Opcode::new("RL", &[Byte(ByteReg::H)], 2, 8), // 0x14
+    Opcode::new("RL", &[Byte(ByteReg::L)], 2, 8), // 0x15
+    Opcode::new("RL", &[Indirect(WordReg::HL)], 2, 16), // 0x16
+    Opcode::new("RL", &[Byte(ByteReg::A)], 2, 8), // 0x17
+    Opcode::new("RR", &[Byte(ByteReg::B)], 2, 8), // 0x18
+    Opcode::new("RR", &[Byte(ByteReg::C)], 2, 8), // 0x19
+    Opcode::new("RR", &[Byte(ByteReg::D)], 2, 8), // 0x1a
+    Opcode::new("RR", &[Byte(ByteReg::E)], 2, 8), // 0x1b
+    Opcode::new("RR", &[Byte(ByteReg::H)], 2, 8), // 0x1c
+    Opcode::new("RR", &[Byte(ByteReg::L)], 2, 8), // 0x1d
+    Opcode::new("RR", &[Indirect(WordReg::HL)], 2, 16), // 0x1e
+    Opcode::new("RR", &[Byte(ByteReg::A)], 2, 8), // 0x1f
+    Opcode::new("SLA", &[Byte(ByteReg::B)], 2, 8), // 0x20
+    Opcode::new("SLA", &[Byte(ByteReg::C)], 2, 8), // 0x21
+    Opcode::new("SLA", &[Byte(ByteReg::D)], 2, 8), // 0x22
+    Opcode::new("SLA", &[Byte(ByteReg::E)], 2, 8), // 0x23
+    Opcode::new("SLA", &[Byte(ByteReg::H)], 2, 8), // 0x24
+    Opcode::new("SLA", &[Byte(ByteReg::L)], 2, 8), // 0x25
+    Opcode::new("SLA", &[Indirect(WordReg::HL)], 2, 16), // 0x26
+    Opcode::new("SLA", &[Byte(ByteReg::A)], 2, 8), // 0x27
+    Opcode::new("SRA", &[Byte(ByteReg::B)], 2, 8), // 0x28
+    Opcode::new("SRA", &[Byte(ByteReg::C)], 2, 8), // 0x29
+    Opcode::new("SRA", &[Byte(ByteReg::D)], 2, 8), // 0x2a
+    Opcode::new("SRA", &[Byte(ByteReg::E)], 2, 8), // 0x2b
+    Opcode::new("SRA", &[Byte(ByteReg::H)], 2, 8), // 0x2c
+    Opcode::new("SRA", &[Byte(ByteReg::L)], 2, 8), // 0x2d
+    Opcode::new("SRA", &[Indirect(WordReg::HL)], 2, 16), // 0x2e
+    Opcode::new("SRA", &[Byte(ByteReg::A)], 2, 8), // 0x2f
+    Opcode::new("SWAP", &[Byte(ByteReg::B)], 2, 8), // 0x30
+    Opcode::new("SWAP", &[Byte(ByteReg::C)], 2, 8), // 0x31
+    Opcode::new("SWAP", &[Byte(ByteReg::D)], 2, 8), // 0x32
+    Opcode::new("SWAP", &[Byte(ByteReg::E)], 2, 8), // 0x33
+    Opcode::new("SWAP", &[Byte(ByteReg::H)], 2, 8), // 0x34
+    Opcode::new("SWAP", &[Byte(ByteReg::L)], 2, 8), // 0x35
+    Opcode::new("SWAP", &[Indirect(WordReg::HL)], 2, 16), // 0x36
+    Opcode::new("SWAP", &[Byte(ByteReg::A)], 2, 8), // 0x37
+    Opcode::new("SRL", &[Byte(ByteReg::B)], 2, 8), // 0x38
+    Opcode::new("SRL", &[Byte(ByteReg::C)], 2, 8), // 0x39
+    Opcode::new("SRL", &[Byte(ByteReg::D)], 2, 8), // 0x3a
+    Opcode::new("SRL", &[Byte(ByteReg::E)], 2, 8), // 0x3b
+    Opcode::new("SRL", &[Byte(ByteReg::H)], 2, 8), // 0x3c
+    Opcode::new("SRL", &[Byte(ByteReg::L)], 2, 8), // 0x3d
+    Opcode::new("SRL", &[Indirect(WordReg::HL)], 2, 16), // 0x3e
+    Opcode::new("SRL", &[Byte(ByteReg::A)], 2, 8), // 0x3f
+    Opcode::new("BIT", &[Bit(0), Byte(ByteReg::B)], 2, 8), // 0x40
+    Opcode::new("BIT", &[Bit(0), Byte(ByteReg::C)], 2, 8), // 0x41
+    Opcode::new("BIT", &[Bit(0), Byte(ByteReg::D)], 2, 8), // 0x42
+    Opcode::new("BIT", &[Bit(0), Byte(ByteReg::E)], 2, 8), // 0x43
+    Opcode::new("BIT", &[Bit(0), Byte(ByteReg::H)], 2, 8), // 0x44
+    Opcode::new("BIT", &[Bit(0), Byte(ByteReg::L)], 2, 8), // 0x45
+    Opcode::new("BIT", &[Bit(0), Indirect(WordReg::HL)], 2, 12), // 0x46
+    Opcode::new("BIT", &[Bit(0), Byte(ByteReg::A)], 2, 8), // 0x47
+    Opcode::new("BIT", &[Bit(1), Byte(ByteReg::B)], 2, 8), // 0x48
+    Opcode::new("BIT", &[Bit(1), Byte(ByteReg::C)], 2, 8), // 0x49
+    Opcode::new("BIT", &[Bit(1), Byte(ByteReg::D)], 2, 8), // 0x4a
+    Opcode::new("BIT", &[Bit(1), Byte(ByteReg::E)], 2, 8), // 0x4b
+    Opcode::new("BIT", &[Bit(1), Byte(ByteReg::H)], 2, 8), // 0x4c
+    Opcode::new("BIT", &[Bit(1), Byte(ByteReg::L)], 2, 8), // 0x4d
+    Opcode::new("BIT", &[Bit(1), Indirect(WordReg::HL)], 2, 12), // 0x4e
+    Opcode::new("BIT", &[Bit(1), Byte(ByteReg::A)], 2, 8), // 0x4f
+    Opcode::new("BIT", &[Bit(2), Byte(ByteReg::B)], 2, 8), // 0x50
+    Opcode::new("BIT", &[Bit(2), Byte(ByteReg::C)], 2, 8), // 0x51
+    Opcode::new("BIT", &[Bit(2), Byte(ByteReg::D)], 2, 8), // 0x52
+    Opcode::new("BIT", &[Bit(2), Byte(ByteReg::E)], 2, 8), // 0x53
+    Opcode::new("BIT", &[Bit(2), Byte(ByteReg::H)], 2, 8), // 0x54
+    Opcode::new("BIT", &[Bit(2), Byte(ByteReg::L)], 2, 8), // 0x55
+    Opcode::new("BIT", &[Bit(2), Indirect(WordReg::HL)], 2, 12), // 0x56
+    Opcode::new("BIT", &[Bit(2), Byte(ByteReg::A)], 2, 8), // 0x57
+    Opcode::new("BIT", &[Bit(3), Byte(ByteReg::B)], 2, 8), // 0x58
+    Opcode::new("BIT", &[Bit(3), Byte(ByteReg::C)], 2, 8), // 0x59
+    Opcode::new("BIT", &[Bit(3), Byte(ByteReg::D)], 2, 8), // 0x5a
+    Opcode::new("BIT", &[Bit(3), Byte(ByteReg::E)], 2, 8), // 0x5b
+    Opcode::new("BIT", &[Bit(3), Byte(ByteReg::H)], 2, 8), // 0x5c
+    Opcode::new("BIT", &[Bit(3), Byte(ByteReg::L)], 2, 8), // 0x5d
+    Opcode::new("BIT", &[Bit(3), Indirect(WordReg::HL)], 2, 12), // 0x5e
+    Opcode::new("BIT", &[Bit(3), Byte(ByteReg::A)], 2, 8), // 0x5f
+    Opcode::new("BIT", &[Bit(4), Byte(ByteReg::B)], 2, 8), // 0x60
+    Opcode::new("BIT", &[Bit(4), Byte(ByteReg::C)], 2, 8), // 0x61
+    Opcode::new("BIT", &[Bit(4), Byte(ByteReg::D)], 2, 8), // 0x62
+    Opcode::new("BIT", &[Bit(4), Byte(ByteReg::E)], 2, 8), // 0x63
+    Opcode::new("BIT", &[Bit(4), Byte(ByteReg::H)], 2, 8), // 0x64
+    Opcode::new("BIT", &[Bit(4), Byte(ByteReg::L)], 2, 8), // 0x65
+    Opcode::new("BIT", &[Bit(4), Indirect(WordReg::HL)], 2, 12), // 0x66
+    Opcode::new("BIT", &[Bit(4), Byte(ByteReg::A)], 2, 8), // 0x67
+    Opcode::new("BIT", &[Bit(5), Byte(ByteReg::B)], 2, 8), // 0x68
+    Opcode::new("BIT", &[Bit(5), Byte(ByteReg::C)], 2, 8), // 0x69
+    Opcode::new("BIT", &[Bit(5), Byte(ByteReg::D)], 2, 8), // 0x6a
+    Opcode::new("BIT", &[Bit(5), Byte(ByteReg::E)], 2, 8), // 0x6b
+    Opcode::new("BIT", &[Bit(5), Byte(ByteReg::H)], 2, 8), // 0x6c
+    Opcode::new("BIT", &[Bit(5), Byte(ByteReg::L)], 2, 8), // 0x6d
+    Opcode::new("BIT", &[Bit(5), Indirect(WordReg::HL)], 2, 12), // 0x6e
+    Opcode::new("BIT", &[Bit(5), Byte(ByteReg::A)], 2, 8), // 0x6f
+    Opcode::new("BIT", &[Bit(6), Byte(ByteReg::B)], 2, 8), // 0x70
+    Opcode::new("BIT", &[Bit(6), Byte(ByteReg::C)], 2, 8), // 0x71
+    Opcode::new("BIT", &[Bit(6), Byte(ByteReg::D)], 2, 8), // 0x72
+    Opcode::new("BIT", &[Bit(6), Byte(ByteReg::E)], 2, 8), // 0x73
+    Opcode::new("BIT", &[Bit(6), Byte(ByteReg::H)], 2, 8), // 0x74
+    Opcode::new("BIT", &[Bit(6), Byte(ByteReg::L)], 2, 8), // 0x75
+    Opcode::new("BIT", &[Bit(6), Indirect(WordReg::HL)], 2, 12), // 0x76
+    Opcode::new("BIT", &[Bit(6), Byte(ByteReg::A)], 2, 8), // 0x77
+    Opcode::new("BIT", &[Bit(7), Byte(ByteReg::B)], 2, 8), // 0x78
+    Opcode::new("BIT", &[Bit(7), Byte(ByteReg::C)], 2, 8), // 0x79
+    Opcode::new("BIT", &[Bit(7), Byte(ByteReg::D)], 2, 8), // 0x7a
+    Opcode::new("BIT", &[Bit(7), Byte(ByteReg::E)], 2, 8), // 0x7b
+    Opcode::new("BIT", &[Bit(7), Byte(ByteReg::H)], 2, 8), // 0x7c
+    Opcode::new("BIT", &[Bit(7), Byte(ByteReg::L)], 2, 8), // 0x7d
+    Opcode::new("BIT", &[Bit(7), Indirect(WordReg::HL)], 2, 12), // 0x7e
+    Opcode::new("BIT", &[Bit(7), Byte(ByteReg::A)], 2, 8), // 0x7f
+    Opcode::new("RES", &[Bit(0), Byte(ByteReg::B)], 2, 8), // 0x80
+    Opcode::new("RES", &[Bit(0), Byte(ByteReg::C)], 2, 8), // 0x81
+    Opcode::new("RES", &[Bit(0), Byte(ByteReg::D)], 2, 8), // 0x82
+    Opcode::new("RES", &[Bit(0), Byte(ByteReg::E)], 2, 8), // 0x83
+    Opcode::new("RES", &[Bit(0), Byte(ByteReg::H)], 2, 8), // 0x84
+    Opcode::new("RES", &[Bit(0), Byte(ByteReg::L)], 2, 8), // 0x85
+    Opcode::new("RES", &[Bit(0), Indirect(WordReg::HL)], 2, 16), // 0x86
+    Opcode::new("RES", &[Bit(0), Byte(ByteReg::A)], 2, 8), // 0x87
+    Opcode::new("RES", &[Bit(1), Byte(ByteReg::B)], 2, 8), // 0x88
+    Opcode::new("RES", &[Bit(1), Byte(ByteReg::C)], 2, 8), // 0x89
+    Opcode::new("RES", &[Bit(1), Byte(ByteReg::D)], 2, 8), // 0x8a
+    Opcode::new("RES", &[Bit(1), Byte(ByteReg::E)], 2, 8), // 0x8b
+    Opcode::new("RES", &[Bit(1), Byte(ByteReg::H)], 2, 8), // 0x8c
+    Opcode::new("RES", &[Bit(1), Byte(ByteReg::L)], 2, 8), // 0x8d
+    Opcode::new("RES", &[Bit(1), Indirect(WordReg::HL)], 2, 16), // 0x8e
+    Opcode::new("RES", &[Bit(1), Byte(ByteReg::A)], 2, 8), // 0x8f
+    Opcode::new("RES", &[Bit(2), Byte(ByteReg::B)], 2, 8), // 0x90
+    Opcode::new("RES", &[Bit(2), Byte(ByteReg::C)], 2, 8), // 0x91
+    Opcode::new("RES", &[Bit(2), Byte(ByteReg::D)], 2, 8), // 0x92
+    Opcode::new("RES", &[Bit(2), Byte(ByteReg::E)], 2, 8), // 0x93
+    Opcode::new("RES", &[Bit(2), Byte(ByteReg::H)], 2, 8), // 0x94
+    Opcode::new("RES", &[Bit(2), Byte(ByteReg::L)], 2, 8), // 0x95
+    Opcode::new("RES", &[Bit(2), Indirect(WordReg::HL)], 2, 16), // 0x96
+    Opcode::new("RES", &[Bit(2), Byte(ByteReg::A)], 2, 8), // 0x97
+    Opcode::new("RES", &[Bit(3), Byte(ByteReg::B)], 2, 8), // 0x98
+    Opcode::new("RES", &[Bit(3), Byte(ByteReg::C)], 2, 8), // 0x99
+    Opcode::new("RES", &[Bit(3), Byte(ByteReg::D)], 2, 8), // 0x9a
+    Opcode::new("RES", &[Bit(3), Byte(ByteReg::E)], 2, 8), // 0x9b
+    Opcode::new("RES", &[Bit(3), Byte(ByteReg::H)], 2, 8), // 0x9c
+    Opcode::new("RES", &[Bit(3), Byte(ByteReg::L)], 2, 8), // 0x9d
+    Opcode::new("RES", &[Bit(3), Indirect(WordReg::HL)], 2, 16), // 0x9e
+    Opcode::new("RES", &[Bit(3), Byte(ByteReg::A)], 2, 8), // 0x9f
+    Opcode::new("RES", &[Bit(4), Byte(ByteReg::B)], 2, 8), // 0xa0
+    Opcode::new("RES", &[Bit(4), Byte(ByteReg::C)], 2, 8), // 0xa1
+    Opcode::new("RES", &[Bit(4), Byte(ByteReg::D)], 2, 8), // 0xa2
+    Opcode::new("RES", &[Bit(4), Byte(ByteReg::E)], 2, 8), // 0xa3
+    Opcode::new("RES", &[Bit(4), Byte(ByteReg::H)], 2, 8), // 0xa4
+    Opcode::new("RES", &[Bit(4), Byte(ByteReg::L)], 2, 8), // 0xa5
+    Opcode::new("RES", &[Bit(4), Indirect(WordReg::HL)], 2, 16), // 0xa6
+    Opcode::new("RES", &[Bit(4), Byte(ByteReg::A)], 2, 8), // 0xa7
+    Opcode::new("RES", &[Bit(5), Byte(ByteReg::B)], 2, 8), // 0xa8
+    Opcode::new("RES", &[Bit(5), Byte(ByteReg::C)], 2, 8), // 0xa9
+    Opcode::new("RES", &[Bit(5), Byte(ByteReg::D)], 2, 8), // 0xaa
+    Opcode::new("RES", &[Bit(5), Byte(ByteReg::E)], 2, 8), // 0xab
+    Opcode::new("RES", &[Bit(5), Byte(ByteReg::H)], 2, 8), // 0xac
+    Opcode::new("RES", &[Bit(5), Byte(ByteReg::L)], 2, 8), // 0xad
+    Opcode::new("RES", &[Bit(5), Indirect(WordReg::HL)], 2, 16), // 0xae
+    Opcode::new("RES", &[Bit(5), Byte(ByteReg::A)], 2, 8), // 0xaf
+    Opcode::new("RES", &[Bit(6), Byte(ByteReg::B)], 2, 8), // 0xb0
+    Opcode::new("RES", &[Bit(6), Byte(ByteReg::C)], 2, 8), // 0xb1
+    Opcode::new("RES", &[Bit(6), Byte(ByteReg::D)], 2, 8), // 0xb2
+    Opcode::new("RES", &[Bit(6), Byte(ByteReg::E)], 2, 8), // 0xb3
+    Opcode::new("RES", &[Bit(6), Byte(ByteReg::H)], 2, 8), // 0xb4
+    Opcode::new("RES", &[Bit(6), Byte(ByteReg::L)], 2, 8), // 0xb5
+    Opcode::new("RES", &[Bit(6), Indirect(WordReg::HL)], 2, 16), // 0xb6
+    Opcode::new("RES", &[Bit(6), Byte(ByteReg::A)], 2, 8), // 0xb7
+    Opcode::new("RES", &[Bit(7), Byte(ByteReg::B)], 2, 8), // 0xb8
+    Opcode::new("RES", &[Bit(7), Byte(ByteReg::C)], 2, 8), // 0xb9
+    Opcode::new("RES", &[Bit(7), Byte(ByteReg::D)], 2, 8), // 0xba
+    Opcode::new("RES", &[Bit(7), Byte(ByteReg::E)], 2, 8), // 0xbb
+    Opcode::new("RES", &[Bit(7), Byte(ByteReg::H)], 2, 8), // 0xbc
+    Opcode::new("RES", &[Bit(7), Byte(ByteReg::L)], 2, 8), // 0xbd
+    Opcode::new("RES", &[Bit(7), Indirect(WordReg::HL)], 2, 16), // 0xbe
+    Opcode::new("RES", &[Bit(7), Byte(ByteReg::A)], 2, 8), // 0xbf
+    Opcode::new("SET", &[Bit(0), Byte(ByteReg::B)], 2, 8), // 0xc0
+    Opcode::new("SET", &[Bit(0), Byte(ByteReg::C)], 2, 8), // 0xc1
+    Opcode::new("SET", &[Bit(0), Byte(ByteReg::D)], 2, 8), // 0xc2
+    Opcode::new("SET", &[Bit(0), Byte(ByteReg::E)], 2, 8), // 0xc3
+    Opcode::new("SET", &[Bit(0), Byte(ByteReg::H)], 2, 8), // 0xc4
+    Opcode::new("SET", &[Bit(0), Byte(ByteReg::L)], 2, 8), // 0xc5
+    Opcode::new("SET", &[Bit(0), Indirect(WordReg::HL)], 2, 16), // 0xc6
+    Opcode::new("SET", &[Bit(0), Byte(ByteReg::A)], 2, 8), // 0xc7
+    Opcode::new("SET", &[Bit(1), Byte(ByteReg::B)], 2, 8), // 0xc8
+    Opcode::new("SET", &[Bit(1), Byte(ByteReg::C)], 2, 8), // 0xc9
+    Opcode::new("SET", &[Bit(1), Byte(ByteReg::D)], 2, 8), // 0xca
+    Opcode::new("SET", &[Bit(1), Byte(ByteReg::E)], 2, 8), // 0xcb
+    Opcode::new("SET", &[Bit(1), Byte(ByteReg::H)], 2, 8), // 0xcc
+    Opcode::new("SET", &[Bit(1), Byte(ByteReg::L)], 2, 8), // 0xcd
+    Opcode::new("SET", &[Bit(1), Indirect(WordReg::HL)], 2, 16), // 0xce
+    Opcode::new("SET", &[Bit(1), Byte(ByteReg::A)], 2, 8), // 0xcf
+    Opcode::new("SET", &[Bit(2), Byte(ByteReg::B)], 2, 8), // 0xd0
+    Opcode::new("SET", &[Bit(2), Byte(ByteReg::C)], 2, 8), // 0xd1
+    Opcode::new("SET", &[Bit(2), Byte(ByteReg::D)], 2, 8), // 0xd2
+    Opcode::new("SET", &[Bit(2), Byte(ByteReg::E)], 2, 8), // 0xd3
+    Opcode::new("SET", &[Bit(2), Byte(ByteReg::H)], 2, 8), // 0xd4
+    Opcode::new("SET", &[Bit(2), Byte(ByteReg::L)], 2, 8), // 0xd5
+    Opcode::new("SET", &[Bit(2), Indirect(WordReg::HL)], 2, 16), // 0xd6
+    Opcode::new("SET", &[Bit(2), Byte(ByteReg::A)], 2, 8), // 0xd7
+    Opcode::new("SET", &[Bit(3), Byte(ByteReg::B)], 2, 8), // 0xd8
+    Opcode::new("SET", &[Bit(3), Byte(ByteReg::C)], 2, 8), // 0xd9
+    Opcode::new("SET", &[Bit(3), Byte(ByteReg::D)], 2, 8), // 0xda
+    Opcode::new("SET", &[Bit(3), Byte(ByteReg::E)], 2, 8), // 0xdb
+    Opcode::new("SET", &[Bit(3), Byte(ByteReg::H)], 2, 8), // 0xdc
+    Opcode::new("SET", &[Bit(3), Byte(ByteReg::L)], 2, 8), // 0xdd
+    Opcode::new("SET", &[Bit(3), Indirect(WordReg::HL)], 2, 16), // 0xde
+    Opcode::new("SET", &[Bit(3), Byte(ByteReg::A)], 2, 8), // 0xdf
+    Opcode::new("SET", &[Bit(4), Byte(ByteReg::B)], 2, 8), // 0xe0
+    Opcode::new("SET", &[Bit(4), Byte(ByteReg::C)], 2, 8), // 0xe1
+    Opcode::new("SET", &[Bit(4), Byte(ByteReg::D)], 2, 8), // 0xe2
+    Opcode::new("SET", &[Bit(4), Byte(ByteReg::E)], 2, 8), // 0xe3
+    Opcode::new("SET", &[Bit(4), Byte(ByteReg::H)], 2, 8), // 0xe4
+    Opcode::new("SET", &[Bit(4), Byte(ByteReg::L)], 2, 8), // 0xe5
+    Opcode::new("SET", &[Bit(4), Indirect(WordReg::HL)], 2, 16), // 0xe6
+    Opcode::new("SET", &[Bit(4), Byte(ByteReg::A)], 2, 8), // 0xe7
+    Opcode::new("SET", &[Bit(5), Byte(ByteReg::B)], 2, 8), // 0xe8
+    Opcode::new("SET", &[Bit(5), Byte(ByteReg::C)], 2, 8), // 0xe9
+    Opcode::new("SET", &[Bit(5), Byte(ByteReg::D)], 2, 8), // 0xea
+    Opcode::new("SET", &[Bit(5), Byte(ByteReg::E)], 2, 8), // 0xeb
+    Opcode::new("SET", &[Bit(5), Byte(ByteReg::H)], 2, 8), // 0xec
+    Opcode::new("SET", &[Bit(5), Byte(ByteReg::L)], 2, 8), // 0xed
+    Opcode::new("SET", &[Bit(5), Indirect(WordReg::HL)], 2, 16), // 0xee
+    Opcode::new("SET", &[Bit(5), Byte(ByteReg::A)], 2, 8), // 0xef
+    Opcode::new("SET", &[Bit(6), Byte(ByteReg::B)], 2, 8), // 0xf0
+    Opcode::new("SET", &[Bit(6), Byte(ByteReg::C)], 2, 8), // 0xf1
+    Opcode::new("SET", &[Bit(6), Byte(ByteReg::D)], 2, 8), // 0xf2
+    Opcode::new("SET", &[Bit(6), Byte(ByteReg::E)], 2, 8), // 0xf3
+    Opcode::new("SET", &[Bit(6), Byte(ByteReg::H)], 2, 8), // 0xf4
+    Opcode::new("SET", &[Bit(6), Byte(ByteReg::L)], 2, 8), // 0xf5
+    Opcode::new("SET", &[Bit(6), Indirect(WordReg::HL)], 2, 16), // 0xf6
+    Opcode::new("SET", &[Bit(6), Byte(ByteReg::A)], 2, 8), // 0xf7
+    Opcode::new("SET", &[Bit(7), Byte(ByteReg::B)], 2, 8), // 0xf8
+    Opcode::new("SET", &[Bit(7), Byte(ByteReg::C)], 2, 8), // 0xf9
+    Opcode::new("SET", &[Bit(7), Byte(ByteReg::D)], 2, 8), // 0xfa
+    Opcode::new("SET", &[Bit(7), Byte(ByteReg::E)], 2, 8), // 0xfb
+    Opcode::new("SET", &[Bit(7), Byte(ByteReg::H)], 2, 8), // 0xfc
+    Opcode::new("SET", &[Bit(7), Byte(ByteReg::L)], 2, 8), // 0xfd
+    Opcode::new("SET", &[Bit(7), Indirect(WordReg::HL)], 2, 16), // 0xfe
+    Opcode::new("SET", &[Bit(7), Byte(ByteReg::A)], 2, 8), // 0xff
+];
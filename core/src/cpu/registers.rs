@@ -1,6 +1,11 @@
 use std::fmt::Display;
 
-#[derive(Clone, Copy)]
+pub const ZERO: u8 = 0b1000_0000;
+pub const SUBTRACT: u8 = 0b0100_0000;
+pub const HALF_CARRY: u8 = 0b0010_0000;
+pub const CARRY: u8 = 0b0001_0000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WordReg {
     AF,
     BC,
@@ -9,7 +14,7 @@ pub enum WordReg {
     SP,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ByteReg {
     A,
     B,
@@ -21,6 +26,25 @@ pub enum ByteReg {
     L,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Condition {
+    NZ,
+    Z,
+    NC,
+    C,
+}
+
+impl Condition {
+    pub fn check(&self, flags: u8) -> bool {
+        match self {
+            Condition::NZ => flags & ZERO == 0,
+            Condition::Z => flags & ZERO != 0,
+            Condition::NC => flags & CARRY == 0,
+            Condition::C => flags & CARRY != 0,
+        }
+    }
+}
+
 pub struct Registers {
     pub a: u8,
     pub b: u8,
@@ -106,49 +130,13 @@ impl Registers {
         }
     }
 
-    pub fn check_zero(&mut self, data: u8) {
-        if data == 0 {
-            self.f |= 0x80;
-        } else {
-            self.f &= !0x80;
-        }
-    }
-
-    pub fn subtract(&mut self, data: bool) {
-        if data {
-            self.f |= 0x40;
-        } else {
-            self.f &= !0x40;
-        }
-    }
-
-    pub fn check_half_carry(&mut self, data: u16) {
-        if data > 0xf {
-            self.f |= 0x20;
-        } else {
-            self.f &= !0x20;
-        }
-    }
-
-    pub fn check_carry(&mut self, data: u16) {
-        if data > 0xff {
-            self.f |= 0x10;
-        } else {
-            self.f &= !0x10;
-        }
-    }
-
     pub fn get_carry(&self) -> bool {
-        self.f & 0x10 != 0
+        self.f & CARRY != 0
     }
 
     pub fn set_flags(&mut self, data: u8) {
         self.f = data;
     }
-
-    pub fn push_word(&mut self, data: u16) {
-        self.set_word(WordReg::SP, data);
-    }
 }
 
 impl Default for Registers {
@@ -193,3 +181,14 @@ impl Display for ByteReg {
         }
     }
 }
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::NZ => write!(f, "NZ"),
+            Condition::Z => write!(f, "Z"),
+            Condition::NC => write!(f, "NC"),
+            Condition::C => write!(f, "C"),
+        }
+    }
+}
@@ -1,1614 +1,1815 @@
 use crate::bus::Bus;
-use crate::cpu::registers::{ByteReg, Registers, WordReg};
-use crate::types::Size;
-
-macro_rules! check_all {
-    ($self:ident, $reg:ident, $val:expr, $subtract:expr) => {
-        $self.reg.check_zero($val);
-        $self.reg.subtract($subtract);
-        $self.reg.check_half_carry($val as u16);
-        $self.reg.check_carry($val as u16);
-    };
+use crate::cpu::alu;
+use crate::cpu::interface::MemoryInterface;
+use crate::cpu::registers::{
+    ByteReg, Condition, Registers, WordReg, CARRY, HALF_CARRY, SUBTRACT, ZERO,
+};
+use crate::log;
+use std::error::Error;
+use std::fmt;
+
+const M_CYCLE: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpuState {
+    Running,
+    Halted,
+    Stopped,
+    // Entered by executing an illegal opcode, only a reset gets the CPU out of it
+    Locked,
 }
 
-macro_rules! check_all_carrys {
-    ($self:ident, $reg:ident, $val:expr) => {
-        $self.reg.check_half_carry($val as u16);
-        $self.reg.check_carry($val as u16);
-    };
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpuError {
+    IllegalOpcode { opcode: u8, addr: u16 },
 }
 
-macro_rules! clear_flag_and_check_zero_carry {
-    ($self:ident, $reg:ident, $val:expr) => {
-        $self.reg.set_flags(0);
-        $self.reg.check_zero($val);
-        $self.reg.check_carry($val as u16);
-    };
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CpuError::IllegalOpcode { opcode, addr } => {
+                write!(f, "Illegal opcode {:02X} at {:04X}", opcode, addr)
+            }
+        }
+    }
 }
 
-const ZERO: u8 = 0b1000_0000;
-const SUBTRACT: u8 = 0b0100_0000;
-const HALF_CARRY: u8 = 0b0010_0000;
-const CARRY: u8 = 0b0001_0000;
+impl Error for CpuError {}
 
-pub struct SM83 {
+pub struct SM83<M: MemoryInterface = Bus> {
     pub reg: Registers,
-    pub bus: Bus,
+    pub bus: M,
     pub pc: u16,
+    pub ime: bool,
+    pub state: CpuState,
+    // T-cycles elapsed since the CPU was created
+    pub cycles: usize,
+    ei_pending: bool,
+    halt_bug: bool,
 }
 
-// TODO: remove self.reg.substract, set with self.reg.set_flags
 impl SM83 {
     pub fn new() -> Self {
+        Self::with_bus(Bus::new(None))
+    }
+}
+
+impl<M: MemoryInterface> SM83<M> {
+    pub fn with_bus(bus: M) -> Self {
         Self {
             reg: Registers::new(),
-            bus: Bus::new(None),
+            bus,
             pc: 0x100,
+            ime: false,
+            state: CpuState::Running,
+            cycles: 0,
+            ei_pending: false,
+            halt_bug: false,
+        }
+    }
+
+    // Runs a single instruction (or interrupt dispatch, or sleep while halted) and
+    // returns the number of T-cycles it took. A halted CPU sleeps until the bus' next
+    // event in one step. Hitting an illegal opcode locks up the CPU and is reported
+    // once, later steps keep the rest of the machine running
+    pub fn step(&mut self) -> Result<usize, CpuError> {
+        self.step_within(usize::MAX)
+    }
+
+    // Runs for at least the given number of T-cycles and returns how many were
+    // actually consumed, which may overshoot by the length of the last instruction.
+    // Sleeping in HALT never runs past the budget
+    pub fn run_for(&mut self, cycles: usize) -> Result<usize, CpuError> {
+        let mut elapsed = 0;
+
+        while elapsed < cycles {
+            elapsed += self.step_within(cycles - elapsed)?;
+        }
+
+        Ok(elapsed)
+    }
+
+    // Runs until the predicate holds before the next instruction and returns the
+    // number of T-cycles consumed
+    pub fn run_until<F>(&mut self, mut predicate: F) -> Result<usize, CpuError>
+    where
+        F: FnMut(&SM83<M>) -> bool,
+    {
+        let mut elapsed = 0;
+
+        while !predicate(self) {
+            elapsed += self.step()?;
         }
+
+        Ok(elapsed)
     }
 
-    pub fn step(&mut self) {
-        let op = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
+    fn step_within(&mut self, budget: usize) -> Result<usize, CpuError> {
+        let start = self.cycles;
+
+        self.execute(budget)?;
+
+        Ok(self.cycles - start)
+    }
+
+    fn execute(&mut self, budget: usize) -> Result<(), CpuError> {
+        if self.bus.pending_interrupts() != 0 && self.state == CpuState::Halted {
+            self.state = CpuState::Running;
+
+            if self.ime {
+                self.idle();
+            }
+        }
+
+        // Only a held button gets the CPU out of STOP
+        if self.state == CpuState::Stopped && self.bus.joypad_asserted() {
+            self.state = CpuState::Running;
+        }
+
+        if self.state == CpuState::Halted {
+            self.sleep(budget);
+            return Ok(());
+        }
+
+        if self.state != CpuState::Running {
+            self.idle();
+            return Ok(());
+        }
+
+        if self.ime && self.bus.pending_interrupts() != 0 {
+            self.dispatch_interrupt();
+            return Ok(());
+        }
+
+        // EI only takes effect after the instruction that follows it
+        let ei_pending = self.ei_pending;
+
+        let addr = self.pc;
+        let op = self.read(addr);
+
+        // The HALT bug makes the CPU fail to increment PC after the fetch
+        if self.halt_bug {
+            self.halt_bug = false;
+        } else {
+            self.pc = self.pc.wrapping_add(1);
+        }
+
         self.run_instruction(op);
+
+        if self.state == CpuState::Locked {
+            log!(Cpu, Error, "Illegal opcode {:02X} at {:04X}", op, addr);
+            return Err(CpuError::IllegalOpcode { opcode: op, addr });
+        }
+
+        if ei_pending && self.ei_pending {
+            self.ime = true;
+            self.ei_pending = false;
+        }
+
+        Ok(())
     }
 
     fn run_instruction(&mut self, op: u8) {
         match op {
             // ADC A, r
-            0x88 => self.adc_r(ByteReg::B, 4),
-            0x89 => self.adc_r(ByteReg::C, 4),
-            0x8a => self.adc_r(ByteReg::D, 4),
-            0x8b => self.adc_r(ByteReg::E, 4),
-            0x8c => self.adc_r(ByteReg::H, 4),
-            0x8d => self.adc_r(ByteReg::L, 4),
-            0x8f => self.adc_r(ByteReg::A, 4),
+            0x88 => self.adc_r(ByteReg::B),
+            0x89 => self.adc_r(ByteReg::C),
+            0x8a => self.adc_r(ByteReg::D),
+            0x8b => self.adc_r(ByteReg::E),
+            0x8c => self.adc_r(ByteReg::H),
+            0x8d => self.adc_r(ByteReg::L),
+            0x8f => self.adc_r(ByteReg::A),
 
             // ADC A, (HL)
-            0x8e => self.adc_hl(8),
+            0x8e => self.adc_hl(),
 
             // ADC A, n
-            0xce => self.adc_n(8),
+            0xce => self.adc_n(),
 
             // ADD A, r
-            0x80 => self.add_r(ByteReg::B, 4),
-            0x81 => self.add_r(ByteReg::C, 4),
-            0x82 => self.add_r(ByteReg::D, 4),
-            0x83 => self.add_r(ByteReg::E, 4),
-            0x84 => self.add_r(ByteReg::H, 4),
-            0x85 => self.add_r(ByteReg::L, 4),
-            0x87 => self.add_r(ByteReg::A, 4),
+            0x80 => self.add_r(ByteReg::B),
+            0x81 => self.add_r(ByteReg::C),
+            0x82 => self.add_r(ByteReg::D),
+            0x83 => self.add_r(ByteReg::E),
+            0x84 => self.add_r(ByteReg::H),
+            0x85 => self.add_r(ByteReg::L),
+            0x87 => self.add_r(ByteReg::A),
 
             // ADD A, (HL)
-            0x86 => self.add_hl(8),
+            0x86 => self.add_hl(),
 
             // ADD A, n
-            0xc6 => self.add_n(8),
+            0xc6 => self.add_n(),
 
             // ADD HL, rr
-            0x09 => self.add_hl_rr(WordReg::BC, 8),
-            0x19 => self.add_hl_rr(WordReg::DE, 8),
-            0x29 => self.add_hl_rr(WordReg::HL, 8),
-            0x39 => self.add_hl_rr(WordReg::SP, 8),
+            0x09 => self.add_hl_rr(WordReg::BC),
+            0x19 => self.add_hl_rr(WordReg::DE),
+            0x29 => self.add_hl_rr(WordReg::HL),
+            0x39 => self.add_hl_rr(WordReg::SP),
 
-            // ADD SP, n
-            0xe8 => self.add_sp_n(16),
+            // ADD SP, e
+            0xe8 => self.add_sp_e(),
 
             // AND A, r
-            0xa0 => self.and_r(ByteReg::B, 4),
-            0xa1 => self.and_r(ByteReg::C, 4),
-            0xa2 => self.and_r(ByteReg::D, 4),
-            0xa3 => self.and_r(ByteReg::E, 4),
-            0xa4 => self.and_r(ByteReg::H, 4),
-            0xa5 => self.and_r(ByteReg::L, 4),
-            0xa7 => self.and_r(ByteReg::A, 4),
+            0xa0 => self.and_r(ByteReg::B),
+            0xa1 => self.and_r(ByteReg::C),
+            0xa2 => self.and_r(ByteReg::D),
+            0xa3 => self.and_r(ByteReg::E),
+            0xa4 => self.and_r(ByteReg::H),
+            0xa5 => self.and_r(ByteReg::L),
+            0xa7 => self.and_r(ByteReg::A),
 
             // AND A, (HL)
-            0xa6 => self.and_hl(8),
+            0xa6 => self.and_hl(),
 
             // AND A, n
-            0xe6 => self.and_n(8),
+            0xe6 => self.and_n(),
 
             // CP A, r
-            0xbf => self.cp_r_a(4),
-            0xb8 => self.cp_r(ByteReg::B, 4),
-            0xb9 => self.cp_r(ByteReg::C, 4),
-            0xba => self.cp_r(ByteReg::D, 4),
-            0xbb => self.cp_r(ByteReg::E, 4),
-            0xbc => self.cp_r(ByteReg::H, 4),
-            0xbd => self.cp_r(ByteReg::L, 4),
+            0xbf => self.cp_r(ByteReg::A),
+            0xb8 => self.cp_r(ByteReg::B),
+            0xb9 => self.cp_r(ByteReg::C),
+            0xba => self.cp_r(ByteReg::D),
+            0xbb => self.cp_r(ByteReg::E),
+            0xbc => self.cp_r(ByteReg::H),
+            0xbd => self.cp_r(ByteReg::L),
 
             // CP A, (HL)
-            0xbe => self.cp_hl(8),
+            0xbe => self.cp_hl(),
 
             // CP A, n
-            0xfe => self.cp_n(8),
+            0xfe => self.cp_n(),
 
             // DEC r
-            0x3d => self.dec_r(ByteReg::A, 4),
-            0x05 => self.dec_r(ByteReg::B, 4),
-            0x0d => self.dec_r(ByteReg::C, 4),
-            0x15 => self.dec_r(ByteReg::D, 4),
-            0x1d => self.dec_r(ByteReg::E, 4),
-            0x25 => self.dec_r(ByteReg::H, 4),
-            0x2d => self.dec_r(ByteReg::L, 4),
+            0x3d => self.dec_r(ByteReg::A),
+            0x05 => self.dec_r(ByteReg::B),
+            0x0d => self.dec_r(ByteReg::C),
+            0x15 => self.dec_r(ByteReg::D),
+            0x1d => self.dec_r(ByteReg::E),
+            0x25 => self.dec_r(ByteReg::H),
+            0x2d => self.dec_r(ByteReg::L),
 
             // DEC (HL)
-            0x35 => self.dec_hl(12),
+            0x35 => self.dec_hl(),
 
             // DEC rr
-            0x0b => self.dec_rr(WordReg::BC, 8),
-            0x1b => self.dec_rr(WordReg::DE, 8),
-            0x2b => self.dec_rr(WordReg::HL, 8),
-            0x3b => self.dec_rr(WordReg::SP, 8),
+            0x0b => self.dec_rr(WordReg::BC),
+            0x1b => self.dec_rr(WordReg::DE),
+            0x2b => self.dec_rr(WordReg::HL),
+            0x3b => self.dec_rr(WordReg::SP),
 
             // INC r
-            0x3c => self.inc_r(ByteReg::A, 4),
-            0x04 => self.inc_r(ByteReg::B, 4),
-            0x0c => self.inc_r(ByteReg::C, 4),
-            0x14 => self.inc_r(ByteReg::D, 4),
-            0x1c => self.inc_r(ByteReg::E, 4),
-            0x24 => self.inc_r(ByteReg::H, 4),
-            0x2c => self.inc_r(ByteReg::L, 4),
+            0x3c => self.inc_r(ByteReg::A),
+            0x04 => self.inc_r(ByteReg::B),
+            0x0c => self.inc_r(ByteReg::C),
+            0x14 => self.inc_r(ByteReg::D),
+            0x1c => self.inc_r(ByteReg::E),
+            0x24 => self.inc_r(ByteReg::H),
+            0x2c => self.inc_r(ByteReg::L),
 
             // INC (HL)
-            0x34 => self.inc_hl(12),
+            0x34 => self.inc_hl(),
 
             // INC rr
-            0x03 => self.inc_rr(WordReg::BC, 8),
-            0x13 => self.inc_rr(WordReg::DE, 8),
-            0x23 => self.inc_rr(WordReg::HL, 8),
-            0x33 => self.inc_rr(WordReg::SP, 8),
+            0x03 => self.inc_rr(WordReg::BC),
+            0x13 => self.inc_rr(WordReg::DE),
+            0x23 => self.inc_rr(WordReg::HL),
+            0x33 => self.inc_rr(WordReg::SP),
 
             // OR A, r
-            0xb7 => self.or_r(ByteReg::A, 4),
-            0xb0 => self.or_r(ByteReg::B, 4),
-            0xb1 => self.or_r(ByteReg::C, 4),
-            0xb2 => self.or_r(ByteReg::D, 4),
-            0xb3 => self.or_r(ByteReg::E, 4),
-            0xb4 => self.or_r(ByteReg::H, 4),
-            0xb5 => self.or_r(ByteReg::L, 4),
+            0xb7 => self.or_r(ByteReg::A),
+            0xb0 => self.or_r(ByteReg::B),
+            0xb1 => self.or_r(ByteReg::C),
+            0xb2 => self.or_r(ByteReg::D),
+            0xb3 => self.or_r(ByteReg::E),
+            0xb4 => self.or_r(ByteReg::H),
+            0xb5 => self.or_r(ByteReg::L),
 
             // OR A, (HL)
-            0xb6 => self.or_hl(8),
+            0xb6 => self.or_hl(),
 
             // OR A, n
-            0xf6 => self.or_n(8),
+            0xf6 => self.or_n(),
 
             // SBC A, r
-            0x9f => self.sbc_r_a(4),
-            0x98 => self.sbc_r(ByteReg::B, 4),
-            0x99 => self.sbc_r(ByteReg::C, 4),
-            0x9a => self.sbc_r(ByteReg::D, 4),
-            0x9b => self.sbc_r(ByteReg::E, 4),
-            0x9c => self.sbc_r(ByteReg::H, 4),
-            0x9d => self.sbc_r(ByteReg::L, 4),
+            0x9f => self.sbc_r(ByteReg::A),
+            0x98 => self.sbc_r(ByteReg::B),
+            0x99 => self.sbc_r(ByteReg::C),
+            0x9a => self.sbc_r(ByteReg::D),
+            0x9b => self.sbc_r(ByteReg::E),
+            0x9c => self.sbc_r(ByteReg::H),
+            0x9d => self.sbc_r(ByteReg::L),
 
             // SBC A, (HL)
-            0x9e => self.sbc_hl(8),
+            0x9e => self.sbc_hl(),
 
             // SBC A, n
-            0xde => self.sbc_n(8),
+            0xde => self.sbc_n(),
 
             // SUB A, r
-            0x97 => self.sub_r_a(4),
-            0x90 => self.sub_r(ByteReg::B, 4),
-            0x91 => self.sub_r(ByteReg::C, 4),
-            0x92 => self.sub_r(ByteReg::D, 4),
-            0x93 => self.sub_r(ByteReg::E, 4),
-            0x94 => self.sub_r(ByteReg::H, 4),
-            0x95 => self.sub_r(ByteReg::L, 4),
+            0x97 => self.sub_r(ByteReg::A),
+            0x90 => self.sub_r(ByteReg::B),
+            0x91 => self.sub_r(ByteReg::C),
+            0x92 => self.sub_r(ByteReg::D),
+            0x93 => self.sub_r(ByteReg::E),
+            0x94 => self.sub_r(ByteReg::H),
+            0x95 => self.sub_r(ByteReg::L),
 
             // SUB A, (HL)
-            0x96 => self.sub_hl(8),
+            0x96 => self.sub_hl(),
 
             // SUB A, n
-            0xd6 => self.sub_n(8),
+            0xd6 => self.sub_n(),
 
             // XOR A, r
-            0xaf => self.xor_r_a(4),
-            0xa8 => self.xor_r(ByteReg::B, 4),
-            0xa9 => self.xor_r(ByteReg::C, 4),
-            0xaa => self.xor_r(ByteReg::D, 4),
-            0xab => self.xor_r(ByteReg::E, 4),
-            0xac => self.xor_r(ByteReg::H, 4),
-            0xad => self.xor_r(ByteReg::L, 4),
+            0xaf => self.xor_r(ByteReg::A),
+            0xa8 => self.xor_r(ByteReg::B),
+            0xa9 => self.xor_r(ByteReg::C),
+            0xaa => self.xor_r(ByteReg::D),
+            0xab => self.xor_r(ByteReg::E),
+            0xac => self.xor_r(ByteReg::H),
+            0xad => self.xor_r(ByteReg::L),
 
             // XOR A, (HL)
-            0xae => self.xor_hl(8),
+            0xae => self.xor_hl(),
 
             // XOR A, n
-            0xee => self.xor_n(8),
+            0xee => self.xor_n(),
 
             // PREFIX CB
             0xcb => self.prefix_cb(),
 
             // RLA
-            0x17 => self.rla(4),
+            0x17 => self.rla(),
 
             // RLCA
-            0x07 => self.rlca(4),
+            0x07 => self.rlca(),
 
             // RRA
-            0x1f => self.rra(4),
+            0x1f => self.rra(),
 
             // RRCA
-            0x0f => self.rrca(4),
+            0x0f => self.rrca(),
 
             // LD r, r
-            0x7f => self.ld_r_r(ByteReg::A, ByteReg::A, 4),
-            0x78 => self.ld_r_r(ByteReg::A, ByteReg::B, 4),
-            0x79 => self.ld_r_r(ByteReg::A, ByteReg::C, 4),
-            0x7a => self.ld_r_r(ByteReg::A, ByteReg::D, 4),
-            0x7b => self.ld_r_r(ByteReg::A, ByteReg::E, 4),
-            0x7c => self.ld_r_r(ByteReg::A, ByteReg::H, 4),
-            0x7d => self.ld_r_r(ByteReg::A, ByteReg::L, 4),
-
-            0x47 => self.ld_r_r(ByteReg::B, ByteReg::A, 4),
-            0x40 => self.ld_r_r(ByteReg::B, ByteReg::B, 4),
-            0x41 => self.ld_r_r(ByteReg::B, ByteReg::C, 4),
-            0x42 => self.ld_r_r(ByteReg::B, ByteReg::D, 4),
-            0x43 => self.ld_r_r(ByteReg::B, ByteReg::E, 4),
-            0x44 => self.ld_r_r(ByteReg::B, ByteReg::H, 4),
-
-            0x4f => self.ld_r_r(ByteReg::C, ByteReg::A, 4),
-            0x48 => self.ld_r_r(ByteReg::C, ByteReg::B, 4),
-            0x49 => self.ld_r_r(ByteReg::C, ByteReg::C, 4),
-            0x4a => self.ld_r_r(ByteReg::C, ByteReg::D, 4),
-            0x4b => self.ld_r_r(ByteReg::C, ByteReg::E, 4),
-            0x4c => self.ld_r_r(ByteReg::C, ByteReg::H, 4),
-
-            0x57 => self.ld_r_r(ByteReg::D, ByteReg::A, 4),
-            0x50 => self.ld_r_r(ByteReg::D, ByteReg::B, 4),
-            0x51 => self.ld_r_r(ByteReg::D, ByteReg::C, 4),
-            0x52 => self.ld_r_r(ByteReg::D, ByteReg::D, 4),
-            0x53 => self.ld_r_r(ByteReg::D, ByteReg::E, 4),
-            0x54 => self.ld_r_r(ByteReg::D, ByteReg::H, 4),
-
-            0x5f => self.ld_r_r(ByteReg::E, ByteReg::A, 4),
-            0x58 => self.ld_r_r(ByteReg::E, ByteReg::B, 4),
-            0x59 => self.ld_r_r(ByteReg::E, ByteReg::C, 4),
-            0x5a => self.ld_r_r(ByteReg::E, ByteReg::D, 4),
-            0x5b => self.ld_r_r(ByteReg::E, ByteReg::E, 4),
-            0x5c => self.ld_r_r(ByteReg::E, ByteReg::H, 4),
-
-            0x67 => self.ld_r_r(ByteReg::H, ByteReg::A, 4),
-            0x60 => self.ld_r_r(ByteReg::H, ByteReg::B, 4),
-            0x61 => self.ld_r_r(ByteReg::H, ByteReg::C, 4),
-            0x62 => self.ld_r_r(ByteReg::H, ByteReg::D, 4),
-            0x63 => self.ld_r_r(ByteReg::H, ByteReg::E, 4),
-            0x64 => self.ld_r_r(ByteReg::H, ByteReg::H, 4),
-
-            0x6f => self.ld_r_r(ByteReg::L, ByteReg::A, 4),
-            0x68 => self.ld_r_r(ByteReg::L, ByteReg::B, 4),
-            0x69 => self.ld_r_r(ByteReg::L, ByteReg::C, 4),
-            0x6a => self.ld_r_r(ByteReg::L, ByteReg::D, 4),
-            0x6b => self.ld_r_r(ByteReg::L, ByteReg::E, 4),
-            0x6c => self.ld_r_r(ByteReg::L, ByteReg::H, 4),
+            0x7f => self.ld_r_r(ByteReg::A, ByteReg::A),
+            0x78 => self.ld_r_r(ByteReg::A, ByteReg::B),
+            0x79 => self.ld_r_r(ByteReg::A, ByteReg::C),
+            0x7a => self.ld_r_r(ByteReg::A, ByteReg::D),
+            0x7b => self.ld_r_r(ByteReg::A, ByteReg::E),
+            0x7c => self.ld_r_r(ByteReg::A, ByteReg::H),
+            0x7d => self.ld_r_r(ByteReg::A, ByteReg::L),
+
+            0x47 => self.ld_r_r(ByteReg::B, ByteReg::A),
+            0x40 => self.ld_r_r(ByteReg::B, ByteReg::B),
+            0x41 => self.ld_r_r(ByteReg::B, ByteReg::C),
+            0x42 => self.ld_r_r(ByteReg::B, ByteReg::D),
+            0x43 => self.ld_r_r(ByteReg::B, ByteReg::E),
+            0x44 => self.ld_r_r(ByteReg::B, ByteReg::H),
+            0x45 => self.ld_r_r(ByteReg::B, ByteReg::L),
+
+            0x4f => self.ld_r_r(ByteReg::C, ByteReg::A),
+            0x48 => self.ld_r_r(ByteReg::C, ByteReg::B),
+            0x49 => self.ld_r_r(ByteReg::C, ByteReg::C),
+            0x4a => self.ld_r_r(ByteReg::C, ByteReg::D),
+            0x4b => self.ld_r_r(ByteReg::C, ByteReg::E),
+            0x4c => self.ld_r_r(ByteReg::C, ByteReg::H),
+            0x4d => self.ld_r_r(ByteReg::C, ByteReg::L),
+
+            0x57 => self.ld_r_r(ByteReg::D, ByteReg::A),
+            0x50 => self.ld_r_r(ByteReg::D, ByteReg::B),
+            0x51 => self.ld_r_r(ByteReg::D, ByteReg::C),
+            0x52 => self.ld_r_r(ByteReg::D, ByteReg::D),
+            0x53 => self.ld_r_r(ByteReg::D, ByteReg::E),
+            0x54 => self.ld_r_r(ByteReg::D, ByteReg::H),
+            0x55 => self.ld_r_r(ByteReg::D, ByteReg::L),
+
+            0x5f => self.ld_r_r(ByteReg::E, ByteReg::A),
+            0x58 => self.ld_r_r(ByteReg::E, ByteReg::B),
+            0x59 => self.ld_r_r(ByteReg::E, ByteReg::C),
+            0x5a => self.ld_r_r(ByteReg::E, ByteReg::D),
+            0x5b => self.ld_r_r(ByteReg::E, ByteReg::E),
+            0x5c => self.ld_r_r(ByteReg::E, ByteReg::H),
+            0x5d => self.ld_r_r(ByteReg::E, ByteReg::L),
+
+            0x67 => self.ld_r_r(ByteReg::H, ByteReg::A),
+            0x60 => self.ld_r_r(ByteReg::H, ByteReg::B),
+            0x61 => self.ld_r_r(ByteReg::H, ByteReg::C),
+            0x62 => self.ld_r_r(ByteReg::H, ByteReg::D),
+            0x63 => self.ld_r_r(ByteReg::H, ByteReg::E),
+            0x64 => self.ld_r_r(ByteReg::H, ByteReg::H),
+            0x65 => self.ld_r_r(ByteReg::H, ByteReg::L),
+
+            0x6f => self.ld_r_r(ByteReg::L, ByteReg::A),
+            0x68 => self.ld_r_r(ByteReg::L, ByteReg::B),
+            0x69 => self.ld_r_r(ByteReg::L, ByteReg::C),
+            0x6a => self.ld_r_r(ByteReg::L, ByteReg::D),
+            0x6b => self.ld_r_r(ByteReg::L, ByteReg::E),
+            0x6c => self.ld_r_r(ByteReg::L, ByteReg::H),
+            0x6d => self.ld_r_r(ByteReg::L, ByteReg::L),
 
             // LD r, n
-            0x3e => self.ld_r_n(ByteReg::A, 8),
-            0x06 => self.ld_r_n(ByteReg::B, 8),
-            0x0e => self.ld_r_n(ByteReg::C, 8),
-            0x16 => self.ld_r_n(ByteReg::D, 8),
-            0x1e => self.ld_r_n(ByteReg::E, 8),
-            0x26 => self.ld_r_n(ByteReg::H, 8),
-            0x2e => self.ld_r_n(ByteReg::L, 8),
+            0x3e => self.ld_r_n(ByteReg::A),
+            0x06 => self.ld_r_n(ByteReg::B),
+            0x0e => self.ld_r_n(ByteReg::C),
+            0x16 => self.ld_r_n(ByteReg::D),
+            0x1e => self.ld_r_n(ByteReg::E),
+            0x26 => self.ld_r_n(ByteReg::H),
+            0x2e => self.ld_r_n(ByteReg::L),
 
             // LD r, (HL)
-            0x7e => self.ld_r_hl(ByteReg::A, 8),
-            0x46 => self.ld_r_hl(ByteReg::B, 8),
-            0x4e => self.ld_r_hl(ByteReg::C, 8),
-            0x56 => self.ld_r_hl(ByteReg::D, 8),
-            0x5e => self.ld_r_hl(ByteReg::E, 8),
-            0x66 => self.ld_r_hl(ByteReg::H, 8),
-            0x6e => self.ld_r_hl(ByteReg::L, 8),
+            0x7e => self.ld_r_hl(ByteReg::A),
+            0x46 => self.ld_r_hl(ByteReg::B),
+            0x4e => self.ld_r_hl(ByteReg::C),
+            0x56 => self.ld_r_hl(ByteReg::D),
+            0x5e => self.ld_r_hl(ByteReg::E),
+            0x66 => self.ld_r_hl(ByteReg::H),
+            0x6e => self.ld_r_hl(ByteReg::L),
 
             // LD (HL), r
-            0x77 => self.ld_hl_r(ByteReg::A, 8),
-            0x70 => self.ld_hl_r(ByteReg::B, 8),
-            0x71 => self.ld_hl_r(ByteReg::C, 8),
-            0x72 => self.ld_hl_r(ByteReg::D, 8),
-            0x73 => self.ld_hl_r(ByteReg::E, 8),
-            0x74 => self.ld_hl_r(ByteReg::H, 8),
-            0x75 => self.ld_hl_r(ByteReg::L, 8),
+            0x77 => self.ld_hl_r(ByteReg::A),
+            0x70 => self.ld_hl_r(ByteReg::B),
+            0x71 => self.ld_hl_r(ByteReg::C),
+            0x72 => self.ld_hl_r(ByteReg::D),
+            0x73 => self.ld_hl_r(ByteReg::E),
+            0x74 => self.ld_hl_r(ByteReg::H),
+            0x75 => self.ld_hl_r(ByteReg::L),
 
             // LD (HL), n
-            0x36 => self.ld_hl_n(12),
+            0x36 => self.ld_hl_n(),
 
             // LD A, rr
-            0x0a => self.ld_a_rr(WordReg::BC, 8),
-            0x1a => self.ld_a_rr(WordReg::DE, 8),
+            0x0a => self.ld_a_rr(WordReg::BC),
+            0x1a => self.ld_a_rr(WordReg::DE),
 
             // LD rr, A
-            0x02 => self.ld_rr_a(WordReg::BC, 8),
-            0x12 => self.ld_rr_a(WordReg::DE, 8),
+            0x02 => self.ld_rr_a(WordReg::BC),
+            0x12 => self.ld_rr_a(WordReg::DE),
 
             // LD A, (HL+/HL-)
-            0x2a => self.ld_a_hl(8, true),
-            0x3a => self.ld_a_hl(8, false),
+            0x2a => self.ld_a_hl(true),
+            0x3a => self.ld_a_hl(false),
 
             // LD (HL+/HL-), A
-            0x22 => self.ld_hl_a(8, true),
-            0x32 => self.ld_hl_a(8, false),
+            0x22 => self.ld_hl_a(true),
+            0x32 => self.ld_hl_a(false),
 
             // LD A, nn
-            0xfa => self.ld_a_nn(16),
+            0xfa => self.ld_a_nn(),
 
             // LD nn, A
-            0xea => self.ld_nn_a(16),
+            0xea => self.ld_nn_a(),
 
             // LD A, (C)
-            0xf2 => self.ld_a_c(8),
+            0xf2 => self.ld_a_c(),
 
             // LD (C), A
-            0xe2 => self.ld_c_a(8),
+            0xe2 => self.ld_c_a(),
+
+            // LD HL, SP+e
+            0xf8 => self.ld_hl_sp_e(),
+
+            // LD rr, nn
+            0x01 => self.ld_rr_nn(WordReg::BC),
+            0x11 => self.ld_rr_nn(WordReg::DE),
+            0x21 => self.ld_rr_nn(WordReg::HL),
+            0x31 => self.ld_rr_nn(WordReg::SP),
+
+            // LD (nn), SP
+            0x08 => self.ld_nn_sp(),
+
+            // LD SP, HL
+            0xf9 => self.ld_sp_hl(),
 
             // LDH A, n
-            0xf0 => self.ldh_a_n(12),
+            0xf0 => self.ldh_a_n(),
 
             // LDH n, A
-            0xe0 => self.ldh_n_a(12),
-
-            // TODO: Jump and subroutine instructions: JP, JR, RET, RETI, RST
+            0xe0 => self.ldh_n_a(),
+
+            // JP nn
+            0xc3 => self.jp_nn(),
+
+            // JP HL
+            0xe9 => self.jp_hl(),
+
+            // JP cc, nn
+            0xc2 => self.jp_cc_nn(Condition::NZ),
+            0xca => self.jp_cc_nn(Condition::Z),
+            0xd2 => self.jp_cc_nn(Condition::NC),
+            0xda => self.jp_cc_nn(Condition::C),
+
+            // JR e
+            0x18 => self.jr_e(),
+
+            // JR cc, e
+            0x20 => self.jr_cc_e(Condition::NZ),
+            0x28 => self.jr_cc_e(Condition::Z),
+            0x30 => self.jr_cc_e(Condition::NC),
+            0x38 => self.jr_cc_e(Condition::C),
+
+            // RET
+            0xc9 => self.ret(),
+
+            // RET cc
+            0xc0 => self.ret_cc(Condition::NZ),
+            0xc8 => self.ret_cc(Condition::Z),
+            0xd0 => self.ret_cc(Condition::NC),
+            0xd8 => self.ret_cc(Condition::C),
+
+            // RETI
+            0xd9 => self.reti(),
+
+            // RST n
+            0xc7 => self.rst(0x00),
+            0xcf => self.rst(0x08),
+            0xd7 => self.rst(0x10),
+            0xdf => self.rst(0x18),
+            0xe7 => self.rst(0x20),
+            0xef => self.rst(0x28),
+            0xf7 => self.rst(0x30),
+            0xff => self.rst(0x38),
 
             // CALL nn
-            0xcd => self.call_nn(24),
+            0xcd => self.call_nn(),
 
             // CALL cc, nn
-            0xc4 => self.call_cc_nn(ZERO | SUBTRACT, 24),
-            0xd4 => self.call_cc_nn(CARRY | SUBTRACT, 24),
+            0xc4 => self.call_cc_nn(Condition::NZ),
+            0xcc => self.call_cc_nn(Condition::Z),
+            0xd4 => self.call_cc_nn(Condition::NC),
+            0xdc => self.call_cc_nn(Condition::C),
+
+            // PUSH rr
+            0xc5 => self.push_rr(WordReg::BC),
+            0xd5 => self.push_rr(WordReg::DE),
+            0xe5 => self.push_rr(WordReg::HL),
+            0xf5 => self.push_rr(WordReg::AF),
+
+            // POP rr
+            0xc1 => self.pop_rr(WordReg::BC),
+            0xd1 => self.pop_rr(WordReg::DE),
+            0xe1 => self.pop_rr(WordReg::HL),
+            0xf1 => self.pop_rr(WordReg::AF),
 
-            // CALL C, nn
-            0xdc => self.call_cc_nn(CARRY, 24),
+            // NOP
+            0x00 => self.nop(),
 
-            // CALL Z, nn
-            0xcc => self.call_cc_nn(ZERO, 24),
+            // STOP
+            0x10 => self.stop(),
 
-            // TODO: Stack instructions: POP, PUSH
-            // TODO: Misc instructions: CCF, CPL, DAA, DI, EI, HALT, NOP, SCF, STOP
+            // HALT
+            0x76 => self.halt(),
 
-            _ => panic!("Unimplemented opcode: {:02x}", op),
+            // DAA
+            0x27 => self.daa(),
+
+            // CPL
+            0x2f => self.cpl(),
+
+            // SCF
+            0x37 => self.scf(),
+
+            // CCF
+            0x3f => self.ccf(),
+
+            // DI
+            0xf3 => self.di(),
+
+            // EI
+            0xfb => self.ei(),
+
+            // Illegal opcodes
+            0xd3 | 0xdb | 0xdd | 0xe3 | 0xe4 | 0xeb | 0xec | 0xed | 0xf4 | 0xfc | 0xfd => {
+                self.lock()
+            }
         }
     }
 
     fn prefix_cb(&mut self) {
-        let op = self.bus.read(Size::Byte, self.pc as usize);
-        self.pc += 1;
+        let op = self.fetch();
 
         match op {
             // BIT b0, r
-            0x47 => self.bit_r(0, ByteReg::A, 8),
-            0x40 => self.bit_r(0, ByteReg::B, 8),
-            0x41 => self.bit_r(0, ByteReg::C, 8),
-            0x42 => self.bit_r(0, ByteReg::D, 8),
-            0x43 => self.bit_r(0, ByteReg::E, 8),
-            0x44 => self.bit_r(0, ByteReg::H, 8),
-            0x45 => self.bit_r(0, ByteReg::L, 8),
+            0x47 => self.bit_r(0, ByteReg::A),
+            0x40 => self.bit_r(0, ByteReg::B),
+            0x41 => self.bit_r(0, ByteReg::C),
+            0x42 => self.bit_r(0, ByteReg::D),
+            0x43 => self.bit_r(0, ByteReg::E),
+            0x44 => self.bit_r(0, ByteReg::H),
+            0x45 => self.bit_r(0, ByteReg::L),
 
             // BIT b0, (HL)
-            0x46 => self.bit_hl(0, 12),
+            0x46 => self.bit_hl(0),
 
             // BIT b1, r
-            0x4f => self.bit_r(1, ByteReg::A, 8),
-            0x48 => self.bit_r(1, ByteReg::B, 8),
-            0x49 => self.bit_r(1, ByteReg::C, 8),
-            0x4a => self.bit_r(1, ByteReg::D, 8),
-            0x4b => self.bit_r(1, ByteReg::E, 8),
-            0x4c => self.bit_r(1, ByteReg::H, 8),
-            0x4d => self.bit_r(1, ByteReg::L, 8),
+            0x4f => self.bit_r(1, ByteReg::A),
+            0x48 => self.bit_r(1, ByteReg::B),
+            0x49 => self.bit_r(1, ByteReg::C),
+            0x4a => self.bit_r(1, ByteReg::D),
+            0x4b => self.bit_r(1, ByteReg::E),
+            0x4c => self.bit_r(1, ByteReg::H),
+            0x4d => self.bit_r(1, ByteReg::L),
 
             // BIT b1, (HL)
-            0x4e => self.bit_hl(1, 12),
+            0x4e => self.bit_hl(1),
 
             // BIT b2, r
-            0x57 => self.bit_r(2, ByteReg::A, 8),
-            0x50 => self.bit_r(2, ByteReg::B, 8),
-            0x51 => self.bit_r(2, ByteReg::C, 8),
-            0x52 => self.bit_r(2, ByteReg::D, 8),
-            0x53 => self.bit_r(2, ByteReg::E, 8),
-            0x54 => self.bit_r(2, ByteReg::H, 8),
-            0x55 => self.bit_r(2, ByteReg::L, 8),
+            0x57 => self.bit_r(2, ByteReg::A),
+            0x50 => self.bit_r(2, ByteReg::B),
+            0x51 => self.bit_r(2, ByteReg::C),
+            0x52 => self.bit_r(2, ByteReg::D),
+            0x53 => self.bit_r(2, ByteReg::E),
+            0x54 => self.bit_r(2, ByteReg::H),
+            0x55 => self.bit_r(2, ByteReg::L),
 
             // BIT b2, (HL)
-            0x56 => self.bit_hl(2, 12),
+            0x56 => self.bit_hl(2),
 
             // BIT b3, r
-            0x5f => self.bit_r(3, ByteReg::A, 8),
-            0x58 => self.bit_r(3, ByteReg::B, 8),
-            0x59 => self.bit_r(3, ByteReg::C, 8),
-            0x5a => self.bit_r(3, ByteReg::D, 8),
-            0x5b => self.bit_r(3, ByteReg::E, 8),
-            0x5c => self.bit_r(3, ByteReg::H, 8),
-            0x5d => self.bit_r(3, ByteReg::L, 8),
+            0x5f => self.bit_r(3, ByteReg::A),
+            0x58 => self.bit_r(3, ByteReg::B),
+            0x59 => self.bit_r(3, ByteReg::C),
+            0x5a => self.bit_r(3, ByteReg::D),
+            0x5b => self.bit_r(3, ByteReg::E),
+            0x5c => self.bit_r(3, ByteReg::H),
+            0x5d => self.bit_r(3, ByteReg::L),
 
             // BIT b3, (HL)
-            0x5e => self.bit_hl(3, 12),
+            0x5e => self.bit_hl(3),
 
             // BIT b4, r
-            0x67 => self.bit_r(4, ByteReg::A, 8),
-            0x60 => self.bit_r(4, ByteReg::B, 8),
-            0x61 => self.bit_r(4, ByteReg::C, 8),
-            0x62 => self.bit_r(4, ByteReg::D, 8),
-            0x63 => self.bit_r(4, ByteReg::E, 8),
-            0x64 => self.bit_r(4, ByteReg::H, 8),
-            0x65 => self.bit_r(4, ByteReg::L, 8),
+            0x67 => self.bit_r(4, ByteReg::A),
+            0x60 => self.bit_r(4, ByteReg::B),
+            0x61 => self.bit_r(4, ByteReg::C),
+            0x62 => self.bit_r(4, ByteReg::D),
+            0x63 => self.bit_r(4, ByteReg::E),
+            0x64 => self.bit_r(4, ByteReg::H),
+            0x65 => self.bit_r(4, ByteReg::L),
 
             // BIT b4, (HL)
-            0x66 => self.bit_hl(4, 12),
+            0x66 => self.bit_hl(4),
 
             // BIT b5, r
-            0x6f => self.bit_r(5, ByteReg::A, 8),
-            0x68 => self.bit_r(5, ByteReg::B, 8),
-            0x69 => self.bit_r(5, ByteReg::C, 8),
-            0x6a => self.bit_r(5, ByteReg::D, 8),
-            0x6b => self.bit_r(5, ByteReg::E, 8),
-            0x6c => self.bit_r(5, ByteReg::H, 8),
-            0x6d => self.bit_r(5, ByteReg::L, 8),
+            0x6f => self.bit_r(5, ByteReg::A),
+            0x68 => self.bit_r(5, ByteReg::B),
+            0x69 => self.bit_r(5, ByteReg::C),
+            0x6a => self.bit_r(5, ByteReg::D),
+            0x6b => self.bit_r(5, ByteReg::E),
+            0x6c => self.bit_r(5, ByteReg::H),
+            0x6d => self.bit_r(5, ByteReg::L),
 
             // BIT b5, (HL)
-            0x6e => self.bit_hl(5, 12),
+            0x6e => self.bit_hl(5),
 
             // BIT b6, r
-            0x77 => self.bit_r(6, ByteReg::A, 8),
-            0x70 => self.bit_r(6, ByteReg::B, 8),
-            0x71 => self.bit_r(6, ByteReg::C, 8),
-            0x72 => self.bit_r(6, ByteReg::D, 8),
-            0x73 => self.bit_r(6, ByteReg::E, 8),
-            0x74 => self.bit_r(6, ByteReg::H, 8),
-            0x75 => self.bit_r(6, ByteReg::L, 8),
+            0x77 => self.bit_r(6, ByteReg::A),
+            0x70 => self.bit_r(6, ByteReg::B),
+            0x71 => self.bit_r(6, ByteReg::C),
+            0x72 => self.bit_r(6, ByteReg::D),
+            0x73 => self.bit_r(6, ByteReg::E),
+            0x74 => self.bit_r(6, ByteReg::H),
+            0x75 => self.bit_r(6, ByteReg::L),
 
             // BIT b6, (HL)
-            0x76 => self.bit_hl(6, 12),
+            0x76 => self.bit_hl(6),
 
             // BIT b7, r
-            0x7f => self.bit_r(7, ByteReg::A, 8),
-            0x78 => self.bit_r(7, ByteReg::B, 8),
-            0x79 => self.bit_r(7, ByteReg::C, 8),
-            0x7a => self.bit_r(7, ByteReg::D, 8),
-            0x7b => self.bit_r(7, ByteReg::E, 8),
-            0x7c => self.bit_r(7, ByteReg::H, 8),
-            0x7d => self.bit_r(7, ByteReg::L, 8),
+            0x7f => self.bit_r(7, ByteReg::A),
+            0x78 => self.bit_r(7, ByteReg::B),
+            0x79 => self.bit_r(7, ByteReg::C),
+            0x7a => self.bit_r(7, ByteReg::D),
+            0x7b => self.bit_r(7, ByteReg::E),
+            0x7c => self.bit_r(7, ByteReg::H),
+            0x7d => self.bit_r(7, ByteReg::L),
 
             // BIT b7, (HL)
-            0x7e => self.bit_hl(7, 12),
+            0x7e => self.bit_hl(7),
 
             // RES b0, r
-            0x87 => self.res_r(0, ByteReg::A, 8),
-            0x80 => self.res_r(0, ByteReg::B, 8),
-            0x81 => self.res_r(0, ByteReg::C, 8),
-            0x82 => self.res_r(0, ByteReg::D, 8),
-            0x83 => self.res_r(0, ByteReg::E, 8),
-            0x84 => self.res_r(0, ByteReg::H, 8),
-            0x85 => self.res_r(0, ByteReg::L, 8),
+            0x87 => self.res_r(0, ByteReg::A),
+            0x80 => self.res_r(0, ByteReg::B),
+            0x81 => self.res_r(0, ByteReg::C),
+            0x82 => self.res_r(0, ByteReg::D),
+            0x83 => self.res_r(0, ByteReg::E),
+            0x84 => self.res_r(0, ByteReg::H),
+            0x85 => self.res_r(0, ByteReg::L),
 
             // RES b0, (HL)
-            0x86 => self.res_hl(0, 16),
+            0x86 => self.res_hl(0),
 
             // RES b1, r
-            0x8f => self.res_r(1, ByteReg::A, 8),
-            0x88 => self.res_r(1, ByteReg::B, 8),
-            0x89 => self.res_r(1, ByteReg::C, 8),
-            0x8a => self.res_r(1, ByteReg::D, 8),
-            0x8b => self.res_r(1, ByteReg::E, 8),
-            0x8c => self.res_r(1, ByteReg::H, 8),
-            0x8d => self.res_r(1, ByteReg::L, 8),
+            0x8f => self.res_r(1, ByteReg::A),
+            0x88 => self.res_r(1, ByteReg::B),
+            0x89 => self.res_r(1, ByteReg::C),
+            0x8a => self.res_r(1, ByteReg::D),
+            0x8b => self.res_r(1, ByteReg::E),
+            0x8c => self.res_r(1, ByteReg::H),
+            0x8d => self.res_r(1, ByteReg::L),
 
             // RES b1, (HL)
-            0x8e => self.res_hl(1, 16),
+            0x8e => self.res_hl(1),
 
             // RES b2, r
-            0x97 => self.res_r(2, ByteReg::A, 8),
-            0x90 => self.res_r(2, ByteReg::B, 8),
-            0x91 => self.res_r(2, ByteReg::C, 8),
-            0x92 => self.res_r(2, ByteReg::D, 8),
-            0x93 => self.res_r(2, ByteReg::E, 8),
-            0x94 => self.res_r(2, ByteReg::H, 8),
-            0x95 => self.res_r(2, ByteReg::L, 8),
+            0x97 => self.res_r(2, ByteReg::A),
+            0x90 => self.res_r(2, ByteReg::B),
+            0x91 => self.res_r(2, ByteReg::C),
+            0x92 => self.res_r(2, ByteReg::D),
+            0x93 => self.res_r(2, ByteReg::E),
+            0x94 => self.res_r(2, ByteReg::H),
+            0x95 => self.res_r(2, ByteReg::L),
 
             // RES b2, (HL)
-            0x96 => self.res_hl(2, 16),
+            0x96 => self.res_hl(2),
 
             // RES b3, r
-            0x9f => self.res_r(3, ByteReg::A, 8),
-            0x98 => self.res_r(3, ByteReg::B, 8),
-            0x99 => self.res_r(3, ByteReg::C, 8),
-            0x9a => self.res_r(3, ByteReg::D, 8),
-            0x9b => self.res_r(3, ByteReg::E, 8),
-            0x9c => self.res_r(3, ByteReg::H, 8),
-            0x9d => self.res_r(3, ByteReg::L, 8),
+            0x9f => self.res_r(3, ByteReg::A),
+            0x98 => self.res_r(3, ByteReg::B),
+            0x99 => self.res_r(3, ByteReg::C),
+            0x9a => self.res_r(3, ByteReg::D),
+            0x9b => self.res_r(3, ByteReg::E),
+            0x9c => self.res_r(3, ByteReg::H),
+            0x9d => self.res_r(3, ByteReg::L),
 
             // RES b3, (HL)
-            0x9e => self.res_hl(3, 16),
+            0x9e => self.res_hl(3),
 
             // RES b4, r
-            0xa7 => self.res_r(4, ByteReg::A, 8),
-            0xa0 => self.res_r(4, ByteReg::B, 8),
-            0xa1 => self.res_r(4, ByteReg::C, 8),
-            0xa2 => self.res_r(4, ByteReg::D, 8),
-            0xa3 => self.res_r(4, ByteReg::E, 8),
-            0xa4 => self.res_r(4, ByteReg::H, 8),
-            0xa5 => self.res_r(4, ByteReg::L, 8),
+            0xa7 => self.res_r(4, ByteReg::A),
+            0xa0 => self.res_r(4, ByteReg::B),
+            0xa1 => self.res_r(4, ByteReg::C),
+            0xa2 => self.res_r(4, ByteReg::D),
+            0xa3 => self.res_r(4, ByteReg::E),
+            0xa4 => self.res_r(4, ByteReg::H),
+            0xa5 => self.res_r(4, ByteReg::L),
 
             // RES b4, (HL)
-            0xa6 => self.res_hl(4, 16),
+            0xa6 => self.res_hl(4),
 
             // RES b5, r
-            0xaf => self.res_r(5, ByteReg::A, 8),
-            0xa8 => self.res_r(5, ByteReg::B, 8),
-            0xa9 => self.res_r(5, ByteReg::C, 8),
-            0xaa => self.res_r(5, ByteReg::D, 8),
-            0xab => self.res_r(5, ByteReg::E, 8),
-            0xac => self.res_r(5, ByteReg::H, 8),
-            0xad => self.res_r(5, ByteReg::L, 8),
+            0xaf => self.res_r(5, ByteReg::A),
+            0xa8 => self.res_r(5, ByteReg::B),
+            0xa9 => self.res_r(5, ByteReg::C),
+            0xaa => self.res_r(5, ByteReg::D),
+            0xab => self.res_r(5, ByteReg::E),
+            0xac => self.res_r(5, ByteReg::H),
+            0xad => self.res_r(5, ByteReg::L),
 
             // RES b5, (HL)
-            0xae => self.res_hl(5, 16),
+            0xae => self.res_hl(5),
 
             // RES b6, r
-            0xb7 => self.res_r(6, ByteReg::A, 8),
-            0xb0 => self.res_r(6, ByteReg::B, 8),
-            0xb1 => self.res_r(6, ByteReg::C, 8),
-            0xb2 => self.res_r(6, ByteReg::D, 8),
-            0xb3 => self.res_r(6, ByteReg::E, 8),
-            0xb4 => self.res_r(6, ByteReg::H, 8),
-            0xb5 => self.res_r(6, ByteReg::L, 8),
+            0xb7 => self.res_r(6, ByteReg::A),
+            0xb0 => self.res_r(6, ByteReg::B),
+            0xb1 => self.res_r(6, ByteReg::C),
+            0xb2 => self.res_r(6, ByteReg::D),
+            0xb3 => self.res_r(6, ByteReg::E),
+            0xb4 => self.res_r(6, ByteReg::H),
+            0xb5 => self.res_r(6, ByteReg::L),
 
             // RES b6, (HL)
-            0xb6 => self.res_hl(6, 16),
+            0xb6 => self.res_hl(6),
 
             // RES b7, r
-            0xbf => self.res_r(7, ByteReg::A, 8),
-            0xb8 => self.res_r(7, ByteReg::B, 8),
-            0xb9 => self.res_r(7, ByteReg::C, 8),
-            0xba => self.res_r(7, ByteReg::D, 8),
-            0xbb => self.res_r(7, ByteReg::E, 8),
-            0xbc => self.res_r(7, ByteReg::H, 8),
-            0xbd => self.res_r(7, ByteReg::L, 8),
+            0xbf => self.res_r(7, ByteReg::A),
+            0xb8 => self.res_r(7, ByteReg::B),
+            0xb9 => self.res_r(7, ByteReg::C),
+            0xba => self.res_r(7, ByteReg::D),
+            0xbb => self.res_r(7, ByteReg::E),
+            0xbc => self.res_r(7, ByteReg::H),
+            0xbd => self.res_r(7, ByteReg::L),
 
             // RES b7, (HL)
-            0xbe => self.res_hl(7, 16),
+            0xbe => self.res_hl(7),
 
             // SET b0, r
-            0xc7 => self.set_r(0, ByteReg::A, 8),
-            0xc0 => self.set_r(0, ByteReg::B, 8),
-            0xc1 => self.set_r(0, ByteReg::C, 8),
-            0xc2 => self.set_r(0, ByteReg::D, 8),
-            0xc3 => self.set_r(0, ByteReg::E, 8),
-            0xc4 => self.set_r(0, ByteReg::H, 8),
-            0xc5 => self.set_r(0, ByteReg::L, 8),
+            0xc7 => self.set_r(0, ByteReg::A),
+            0xc0 => self.set_r(0, ByteReg::B),
+            0xc1 => self.set_r(0, ByteReg::C),
+            0xc2 => self.set_r(0, ByteReg::D),
+            0xc3 => self.set_r(0, ByteReg::E),
+            0xc4 => self.set_r(0, ByteReg::H),
+            0xc5 => self.set_r(0, ByteReg::L),
 
             // SET b0, (HL)
-            0xc6 => self.set_hl(0, 16),
+            0xc6 => self.set_hl(0),
 
             // SET b1, r
-            0xcf => self.set_r(1, ByteReg::A, 8),
-            0xc8 => self.set_r(1, ByteReg::B, 8),
-            0xc9 => self.set_r(1, ByteReg::C, 8),
-            0xca => self.set_r(1, ByteReg::D, 8),
-            0xcb => self.set_r(1, ByteReg::E, 8),
-            0xcc => self.set_r(1, ByteReg::H, 8),
-            0xcd => self.set_r(1, ByteReg::L, 8),
+            0xcf => self.set_r(1, ByteReg::A),
+            0xc8 => self.set_r(1, ByteReg::B),
+            0xc9 => self.set_r(1, ByteReg::C),
+            0xca => self.set_r(1, ByteReg::D),
+            0xcb => self.set_r(1, ByteReg::E),
+            0xcc => self.set_r(1, ByteReg::H),
+            0xcd => self.set_r(1, ByteReg::L),
 
             // SET b1, (HL)
-            0xce => self.set_hl(1, 16),
+            0xce => self.set_hl(1),
 
             // SET b2, r
-            0xd7 => self.set_r(2, ByteReg::A, 8),
-            0xd0 => self.set_r(2, ByteReg::B, 8),
-            0xd1 => self.set_r(2, ByteReg::C, 8),
-            0xd2 => self.set_r(2, ByteReg::D, 8),
-            0xd3 => self.set_r(2, ByteReg::E, 8),
-            0xd4 => self.set_r(2, ByteReg::H, 8),
-            0xd5 => self.set_r(2, ByteReg::L, 8),
+            0xd7 => self.set_r(2, ByteReg::A),
+            0xd0 => self.set_r(2, ByteReg::B),
+            0xd1 => self.set_r(2, ByteReg::C),
+            0xd2 => self.set_r(2, ByteReg::D),
+            0xd3 => self.set_r(2, ByteReg::E),
+            0xd4 => self.set_r(2, ByteReg::H),
+            0xd5 => self.set_r(2, ByteReg::L),
 
             // SET b2, (HL)
-            0xd6 => self.set_hl(2, 16),
+            0xd6 => self.set_hl(2),
 
             // SET b3, r
-            0xdf => self.set_r(3, ByteReg::A, 8),
-            0xd8 => self.set_r(3, ByteReg::B, 8),
-            0xd9 => self.set_r(3, ByteReg::C, 8),
-            0xda => self.set_r(3, ByteReg::D, 8),
-            0xdb => self.set_r(3, ByteReg::E, 8),
-            0xdc => self.set_r(3, ByteReg::H, 8),
-            0xdd => self.set_r(3, ByteReg::L, 8),
+            0xdf => self.set_r(3, ByteReg::A),
+            0xd8 => self.set_r(3, ByteReg::B),
+            0xd9 => self.set_r(3, ByteReg::C),
+            0xda => self.set_r(3, ByteReg::D),
+            0xdb => self.set_r(3, ByteReg::E),
+            0xdc => self.set_r(3, ByteReg::H),
+            0xdd => self.set_r(3, ByteReg::L),
 
             // SET b3, (HL)
-            0xde => self.set_hl(3, 16),
+            0xde => self.set_hl(3),
 
             // SET b4, r
-            0xe7 => self.set_r(4, ByteReg::A, 8),
-            0xe0 => self.set_r(4, ByteReg::B, 8),
-            0xe1 => self.set_r(4, ByteReg::C, 8),
-            0xe2 => self.set_r(4, ByteReg::D, 8),
-            0xe3 => self.set_r(4, ByteReg::E, 8),
-            0xe4 => self.set_r(4, ByteReg::H, 8),
-            0xe5 => self.set_r(4, ByteReg::L, 8),
+            0xe7 => self.set_r(4, ByteReg::A),
+            0xe0 => self.set_r(4, ByteReg::B),
+            0xe1 => self.set_r(4, ByteReg::C),
+            0xe2 => self.set_r(4, ByteReg::D),
+            0xe3 => self.set_r(4, ByteReg::E),
+            0xe4 => self.set_r(4, ByteReg::H),
+            0xe5 => self.set_r(4, ByteReg::L),
 
             // SET b4, (HL)
-            0xe6 => self.set_hl(4, 16),
+            0xe6 => self.set_hl(4),
 
             // SET b5, r
-            0xef => self.set_r(5, ByteReg::A, 8),
-            0xe8 => self.set_r(5, ByteReg::B, 8),
-            0xe9 => self.set_r(5, ByteReg::C, 8),
-            0xea => self.set_r(5, ByteReg::D, 8),
-            0xeb => self.set_r(5, ByteReg::E, 8),
-            0xec => self.set_r(5, ByteReg::H, 8),
-            0xed => self.set_r(5, ByteReg::L, 8),
+            0xef => self.set_r(5, ByteReg::A),
+            0xe8 => self.set_r(5, ByteReg::B),
+            0xe9 => self.set_r(5, ByteReg::C),
+            0xea => self.set_r(5, ByteReg::D),
+            0xeb => self.set_r(5, ByteReg::E),
+            0xec => self.set_r(5, ByteReg::H),
+            0xed => self.set_r(5, ByteReg::L),
 
             // SET b5, (HL)
-            0xee => self.set_hl(5, 16),
+            0xee => self.set_hl(5),
 
             // SET b6, r
-            0xf7 => self.set_r(6, ByteReg::A, 8),
-            0xf0 => self.set_r(6, ByteReg::B, 8),
-            0xf1 => self.set_r(6, ByteReg::C, 8),
-            0xf2 => self.set_r(6, ByteReg::D, 8),
-            0xf3 => self.set_r(6, ByteReg::E, 8),
-            0xf4 => self.set_r(6, ByteReg::H, 8),
-            0xf5 => self.set_r(6, ByteReg::L, 8),
+            0xf7 => self.set_r(6, ByteReg::A),
+            0xf0 => self.set_r(6, ByteReg::B),
+            0xf1 => self.set_r(6, ByteReg::C),
+            0xf2 => self.set_r(6, ByteReg::D),
+            0xf3 => self.set_r(6, ByteReg::E),
+            0xf4 => self.set_r(6, ByteReg::H),
+            0xf5 => self.set_r(6, ByteReg::L),
 
             // SET b6, (HL)
-            0xf6 => self.set_hl(6, 16),
+            0xf6 => self.set_hl(6),
 
             // SET b7, r
-            0xff => self.set_r(7, ByteReg::A, 8),
-            0xf8 => self.set_r(7, ByteReg::B, 8),
-            0xf9 => self.set_r(7, ByteReg::C, 8),
-            0xfa => self.set_r(7, ByteReg::D, 8),
-            0xfb => self.set_r(7, ByteReg::E, 8),
-            0xfc => self.set_r(7, ByteReg::H, 8),
-            0xfd => self.set_r(7, ByteReg::L, 8),
+            0xff => self.set_r(7, ByteReg::A),
+            0xf8 => self.set_r(7, ByteReg::B),
+            0xf9 => self.set_r(7, ByteReg::C),
+            0xfa => self.set_r(7, ByteReg::D),
+            0xfb => self.set_r(7, ByteReg::E),
+            0xfc => self.set_r(7, ByteReg::H),
+            0xfd => self.set_r(7, ByteReg::L),
 
             // SET b7, (HL)
-            0xfe => self.set_hl(7, 16),
+            0xfe => self.set_hl(7),
 
             // SWAP r
-            0x37 => self.swap_r(ByteReg::A, 8),
-            0x30 => self.swap_r(ByteReg::B, 8),
-            0x31 => self.swap_r(ByteReg::C, 8),
-            0x32 => self.swap_r(ByteReg::D, 8),
-            0x33 => self.swap_r(ByteReg::E, 8),
-            0x34 => self.swap_r(ByteReg::H, 8),
-            0x35 => self.swap_r(ByteReg::L, 8),
+            0x37 => self.swap_r(ByteReg::A),
+            0x30 => self.swap_r(ByteReg::B),
+            0x31 => self.swap_r(ByteReg::C),
+            0x32 => self.swap_r(ByteReg::D),
+            0x33 => self.swap_r(ByteReg::E),
+            0x34 => self.swap_r(ByteReg::H),
+            0x35 => self.swap_r(ByteReg::L),
 
             // SWAP (HL)
-            0x36 => self.swap_hl(16),
+            0x36 => self.swap_hl(),
 
             // RL r
-            0x17 => self.rl_r(ByteReg::A, 8),
-            0x10 => self.rl_r(ByteReg::B, 8),
-            0x11 => self.rl_r(ByteReg::C, 8),
-            0x12 => self.rl_r(ByteReg::D, 8),
-            0x13 => self.rl_r(ByteReg::E, 8),
-            0x14 => self.rl_r(ByteReg::H, 8),
-            0x15 => self.rl_r(ByteReg::L, 8),
+            0x17 => self.rl_r(ByteReg::A),
+            0x10 => self.rl_r(ByteReg::B),
+            0x11 => self.rl_r(ByteReg::C),
+            0x12 => self.rl_r(ByteReg::D),
+            0x13 => self.rl_r(ByteReg::E),
+            0x14 => self.rl_r(ByteReg::H),
+            0x15 => self.rl_r(ByteReg::L),
 
             // RL (HL)
-            0x16 => self.rl_hl(16),
+            0x16 => self.rl_hl(),
 
             // RLC r
-            0x07 => self.rlc_r(ByteReg::A, 8),
-            0x00 => self.rlc_r(ByteReg::B, 8),
-            0x01 => self.rlc_r(ByteReg::C, 8),
-            0x02 => self.rlc_r(ByteReg::D, 8),
-            0x03 => self.rlc_r(ByteReg::E, 8),
-            0x04 => self.rlc_r(ByteReg::H, 8),
-            0x05 => self.rlc_r(ByteReg::L, 8),
+            0x07 => self.rlc_r(ByteReg::A),
+            0x00 => self.rlc_r(ByteReg::B),
+            0x01 => self.rlc_r(ByteReg::C),
+            0x02 => self.rlc_r(ByteReg::D),
+            0x03 => self.rlc_r(ByteReg::E),
+            0x04 => self.rlc_r(ByteReg::H),
+            0x05 => self.rlc_r(ByteReg::L),
 
             // RLC (HL)
-            0x06 => self.rlc_hl(16),
+            0x06 => self.rlc_hl(),
 
             // RR r
-            0x1f => self.rr_r(ByteReg::A, 8),
-            0x18 => self.rr_r(ByteReg::B, 8),
-            0x19 => self.rr_r(ByteReg::C, 8),
-            0x1a => self.rr_r(ByteReg::D, 8),
-            0x1b => self.rr_r(ByteReg::E, 8),
-            0x1c => self.rr_r(ByteReg::H, 8),
-            0x1d => self.rr_r(ByteReg::L, 8),
+            0x1f => self.rr_r(ByteReg::A),
+            0x18 => self.rr_r(ByteReg::B),
+            0x19 => self.rr_r(ByteReg::C),
+            0x1a => self.rr_r(ByteReg::D),
+            0x1b => self.rr_r(ByteReg::E),
+            0x1c => self.rr_r(ByteReg::H),
+            0x1d => self.rr_r(ByteReg::L),
 
             // RR (HL)
-            0x1e => self.rr_hl(16),
+            0x1e => self.rr_hl(),
 
             // RRC r
-            0x0f => self.rrc_r(ByteReg::A, 8),
-            0x08 => self.rrc_r(ByteReg::B, 8),
-            0x09 => self.rrc_r(ByteReg::C, 8),
-            0x0a => self.rrc_r(ByteReg::D, 8),
-            0x0b => self.rrc_r(ByteReg::E, 8),
-            0x0c => self.rrc_r(ByteReg::H, 8),
-            0x0d => self.rrc_r(ByteReg::L, 8),
+            0x0f => self.rrc_r(ByteReg::A),
+            0x08 => self.rrc_r(ByteReg::B),
+            0x09 => self.rrc_r(ByteReg::C),
+            0x0a => self.rrc_r(ByteReg::D),
+            0x0b => self.rrc_r(ByteReg::E),
+            0x0c => self.rrc_r(ByteReg::H),
+            0x0d => self.rrc_r(ByteReg::L),
 
             // RRC (HL)
-            0x0e => self.rrc_hl(16),
+            0x0e => self.rrc_hl(),
 
             // SLA r
-            0x27 => self.sla_r(ByteReg::A, 8),
-            0x20 => self.sla_r(ByteReg::B, 8),
-            0x21 => self.sla_r(ByteReg::C, 8),
-            0x22 => self.sla_r(ByteReg::D, 8),
-            0x23 => self.sla_r(ByteReg::E, 8),
-            0x24 => self.sla_r(ByteReg::H, 8),
-            0x25 => self.sla_r(ByteReg::L, 8),
+            0x27 => self.sla_r(ByteReg::A),
+            0x20 => self.sla_r(ByteReg::B),
+            0x21 => self.sla_r(ByteReg::C),
+            0x22 => self.sla_r(ByteReg::D),
+            0x23 => self.sla_r(ByteReg::E),
+            0x24 => self.sla_r(ByteReg::H),
+            0x25 => self.sla_r(ByteReg::L),
 
             // SLA (HL)
-            0x26 => self.sla_hl(16),
+            0x26 => self.sla_hl(),
 
             // SRA r
-            0x2f => self.sra_r(ByteReg::A, 8),
-            0x28 => self.sra_r(ByteReg::B, 8),
-            0x29 => self.sra_r(ByteReg::C, 8),
-            0x2a => self.sra_r(ByteReg::D, 8),
-            0x2b => self.sra_r(ByteReg::E, 8),
-            0x2c => self.sra_r(ByteReg::H, 8),
-            0x2d => self.sra_r(ByteReg::L, 8),
+            0x2f => self.sra_r(ByteReg::A),
+            0x28 => self.sra_r(ByteReg::B),
+            0x29 => self.sra_r(ByteReg::C),
+            0x2a => self.sra_r(ByteReg::D),
+            0x2b => self.sra_r(ByteReg::E),
+            0x2c => self.sra_r(ByteReg::H),
+            0x2d => self.sra_r(ByteReg::L),
 
             // SRA (HL)
-            0x2e => self.sra_hl(16),
+            0x2e => self.sra_hl(),
 
             // SRL r
-            0x3f => self.srl_r(ByteReg::A, 8),
-            0x38 => self.srl_r(ByteReg::B, 8),
-            0x39 => self.srl_r(ByteReg::C, 8),
-            0x3a => self.srl_r(ByteReg::D, 8),
-            0x3b => self.srl_r(ByteReg::E, 8),
-            0x3c => self.srl_r(ByteReg::H, 8),
-            0x3d => self.srl_r(ByteReg::L, 8),
+            0x3f => self.srl_r(ByteReg::A),
+            0x38 => self.srl_r(ByteReg::B),
+            0x39 => self.srl_r(ByteReg::C),
+            0x3a => self.srl_r(ByteReg::D),
+            0x3b => self.srl_r(ByteReg::E),
+            0x3c => self.srl_r(ByteReg::H),
+            0x3d => self.srl_r(ByteReg::L),
 
             // SRL (HL)
-            0x3e => self.srl_hl(16),
-
-            // DONE! all 0xcb prefixed instructions - thx god
-            _ => panic!("Unimplemented prefix $cb: {:02x}", op),
+            0x3e => self.srl_hl(),
         }
     }
 
-    fn adc_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.reg.set_byte(
-            ByteReg::A,
-            self.reg.a + self.reg.get_byte(reg) + self.reg.get_carry() as u8,
-        );
-
-        self.bus.tick(cycles);
-
-        check_all!(self, reg, self.reg.a, false);
+    // Every memory access and internal delay takes exactly one M-cycle, and the rest
+    // of the machine is advanced before the access happens
+    fn idle(&mut self) {
+        self.cycles += M_CYCLE;
+        self.bus.tick(M_CYCLE);
     }
 
-    fn adc_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL);
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-        self.reg
-            .set_byte(ByteReg::A, self.reg.a + data + self.reg.get_carry() as u8);
+    // Skips whole M-cycles up to the next event, which is the earliest anything could
+    // raise an interrupt, but no further than the budget allows
+    fn sleep(&mut self, budget: usize) {
+        let cycles = match self.bus.cycles_to_next_event() {
+            Some(cycles) => cycles.min(budget).div_ceil(M_CYCLE).max(1) * M_CYCLE,
+            None => M_CYCLE,
+        };
 
+        self.cycles += cycles;
         self.bus.tick(cycles);
-
-        check_all!(self, hl, self.reg.a, false);
     }
 
-    fn adc_n(&mut self, cycles: usize) {
-        let n = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
-        self.reg
-            .set_byte(ByteReg::A, self.reg.a + n + self.reg.get_carry() as u8);
-
-        self.bus.tick(cycles);
+    fn read(&mut self, addr: u16) -> u8 {
+        self.idle();
 
-        check_all!(self, n, self.reg.a, false);
+        self.bus.read(addr)
     }
 
-    fn add_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.reg
-            .set_byte(ByteReg::A, self.reg.a + self.reg.get_byte(reg));
+    fn write(&mut self, addr: u16, data: u8) {
+        self.idle();
 
-        self.bus.tick(cycles);
-
-        check_all!(self, reg, self.reg.a, false);
+        self.bus.write(addr, data);
     }
 
-    fn add_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL);
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
+    fn fetch(&mut self) -> u8 {
+        let data = self.read(self.pc);
+        self.pc = self.pc.wrapping_add(1);
 
-        self.reg.set_byte(ByteReg::A, self.reg.a + data);
-        self.bus.tick(cycles);
-
-        check_all!(self, hl, self.reg.a, false);
+        data
     }
 
-    fn add_n(&mut self, cycles: usize) {
-        let n = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
-        self.reg.set_byte(ByteReg::A, self.reg.a + n);
+    fn fetch_word(&mut self) -> u16 {
+        let lo = self.fetch() as u16;
+        let hi = self.fetch() as u16;
 
-        self.bus.tick(cycles);
-
-        check_all!(self, n, self.reg.a, false);
+        (hi << 8) | lo
     }
 
-    fn add_hl_rr(&mut self, reg: WordReg, cycles: usize) {
-        self.reg.set_word(
-            WordReg::HL,
-            self.reg.get_word(WordReg::HL) + self.reg.get_word(reg),
-        );
-
-        self.bus.tick(cycles);
+    fn adc_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::add(self.reg.a, self.reg.get_byte(reg), self.reg.get_carry());
 
-        self.reg.subtract(false);
-        self.reg
-            .check_half_carry(self.reg.get_word(WordReg::HL) as u16);
-        self.reg.check_carry(self.reg.get_word(WordReg::HL) as u16);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn add_sp_n(&mut self, cycles: usize) {
-        let n = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
-        self.reg
-            .set_word(WordReg::SP, self.reg.get_word(WordReg::SP) + n as u16);
+    fn adc_hl(&mut self) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        let (result, flags) = alu::add(self.reg.a, data, self.reg.get_carry());
 
-        self.bus.tick(cycles);
-
-        self.reg.set_flags(0);
-        check_all_carrys!(self, n, self.reg.get_word(WordReg::SP) as u8);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn and_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.reg
-            .set_byte(ByteReg::A, self.reg.a & self.reg.get_byte(reg));
-
-        self.bus.tick(cycles);
+    fn adc_n(&mut self) {
+        let n = self.fetch();
+        let (result, flags) = alu::add(self.reg.a, n, self.reg.get_carry());
 
-        self.reg.set_flags(HALF_CARRY);
-        self.reg.check_zero(self.reg.a);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn and_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL);
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-
-        self.reg.set_byte(ByteReg::A, self.reg.a & data);
-        self.bus.tick(cycles);
+    fn add_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::add(self.reg.a, self.reg.get_byte(reg), false);
 
-        self.reg.set_flags(HALF_CARRY);
-        self.reg.check_zero(self.reg.a);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn and_n(&mut self, cycles: usize) {
-        let n = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
-        self.reg.set_byte(ByteReg::A, self.reg.a & n);
+    fn add_hl(&mut self) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        let (result, flags) = alu::add(self.reg.a, data, false);
 
-        self.bus.tick(cycles);
-
-        self.reg.set_flags(HALF_CARRY);
-        self.reg.check_zero(self.reg.a);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn cp_r_a(&mut self, cycles: usize) {
-        let a = self.reg.a;
-
-        self.reg.check_zero(a.wrapping_sub(a));
+    fn add_n(&mut self) {
+        let n = self.fetch();
+        let (result, flags) = alu::add(self.reg.a, n, false);
 
-        self.reg.set_flags(ZERO | SUBTRACT);
-
-        self.bus.tick(cycles);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn cp_r(&mut self, reg: ByteReg, cycles: usize) {
-        let a = self.reg.a;
-        let b = self.reg.get_byte(reg);
+    fn add_hl_rr(&mut self, reg: WordReg) {
+        let (result, flags) = alu::add_word(
+            self.reg.get_word(WordReg::HL),
+            self.reg.get_word(reg),
+            self.reg.f,
+        );
 
-        check_all!(self, reg, a.wrapping_sub(b), true);
+        self.reg.set_word(WordReg::HL, result);
+        self.reg.set_flags(flags);
 
-        self.bus.tick(cycles);
+        // The 16 bit addition goes through the 8 bit ALU twice
+        self.idle();
     }
 
-    fn cp_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL);
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-        let a = self.reg.a;
+    fn add_sp_e(&mut self) {
+        let e = self.fetch() as i8;
+        let (result, flags) = alu::add_word_signed(self.reg.get_word(WordReg::SP), e);
 
-        check_all!(self, hl, a.wrapping_sub(data), true);
+        self.idle();
+        self.idle();
 
-        self.bus.tick(cycles);
+        self.reg.set_word(WordReg::SP, result);
+        self.reg.set_flags(flags);
     }
 
-    fn cp_n(&mut self, cycles: usize) {
-        let n = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
-        let a = self.reg.a;
+    fn ld_hl_sp_e(&mut self) {
+        let e = self.fetch() as i8;
+        let (result, flags) = alu::add_word_signed(self.reg.get_word(WordReg::SP), e);
 
-        check_all!(self, n, a.wrapping_sub(n), true);
+        self.idle();
 
-        self.bus.tick(cycles);
+        self.reg.set_word(WordReg::HL, result);
+        self.reg.set_flags(flags);
     }
 
-    fn dec_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.reg
-            .set_byte(reg, self.reg.get_byte(reg).wrapping_sub(1));
-
-        self.bus.tick(cycles);
+    fn and_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::and(self.reg.a, self.reg.get_byte(reg));
 
-        self.reg.check_zero(self.reg.get_byte(reg));
-        self.reg.subtract(true);
-        self.reg.check_half_carry(self.reg.get_byte(reg) as u16);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn dec_hl(&mut self, cycles: usize) {
-        self.reg
-            .set_word(WordReg::HL, self.reg.get_word(WordReg::HL).wrapping_sub(1));
-
-        self.bus.tick(cycles);
+    fn and_hl(&mut self) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        let (result, flags) = alu::and(self.reg.a, data);
 
-        self.reg.check_zero(self.reg.get_word(WordReg::HL) as u8);
-        self.reg.subtract(true);
-        self.reg.check_half_carry(self.reg.get_word(WordReg::HL));
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn dec_rr(&mut self, reg: WordReg, cycles: usize) {
-        let data = self.reg.get_word(reg).wrapping_sub(1);
+    fn and_n(&mut self) {
+        let n = self.fetch();
+        let (result, flags) = alu::and(self.reg.a, n);
 
-        self.reg.set_word(reg, data);
-
-        self.bus.tick(cycles);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn inc_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.reg
-            .set_byte(reg, self.reg.get_byte(reg).wrapping_add(1));
-
-        self.bus.tick(cycles);
+    fn cp_r(&mut self, reg: ByteReg) {
+        let (_, flags) = alu::sub(self.reg.a, self.reg.get_byte(reg), false);
 
-        self.reg.check_zero(self.reg.get_byte(reg));
-        self.reg.subtract(false);
-        self.reg.check_half_carry(self.reg.get_byte(reg) as u16);
+        self.reg.set_flags(flags);
     }
 
-    fn inc_hl(&mut self, cycles: usize) {
-        self.reg
-            .set_word(WordReg::HL, self.reg.get_word(WordReg::HL).wrapping_add(1));
+    fn cp_hl(&mut self) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        let (_, flags) = alu::sub(self.reg.a, data, false);
 
-        self.bus.tick(cycles);
-
-        self.reg.check_zero(self.reg.get_word(WordReg::HL) as u8);
-        self.reg.subtract(false);
-        self.reg.check_half_carry(self.reg.get_word(WordReg::HL));
+        self.reg.set_flags(flags);
     }
 
-    fn inc_rr(&mut self, reg: WordReg, cycles: usize) {
-        let data = self.reg.get_word(reg).wrapping_add(1);
-
-        self.reg.set_word(reg, data);
+    fn cp_n(&mut self) {
+        let n = self.fetch();
+        let (_, flags) = alu::sub(self.reg.a, n, false);
 
-        self.bus.tick(cycles);
+        self.reg.set_flags(flags);
     }
 
-    fn or_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.reg
-            .set_byte(ByteReg::A, self.reg.a | self.reg.get_byte(reg));
+    fn dec_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::dec(self.reg.get_byte(reg), self.reg.f);
 
-        self.bus.tick(cycles);
-
-        self.reg.set_flags(0);
-        self.reg.check_zero(self.reg.a);
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
     }
 
-    fn or_hl(&mut self, cycles: usize) {
+    fn dec_hl(&mut self) {
         let hl = self.reg.get_word(WordReg::HL);
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-
-        self.reg.set_byte(ByteReg::A, self.reg.a | data);
-        self.bus.tick(cycles);
+        let data = self.read(hl);
+        let (result, flags) = alu::dec(data, self.reg.f);
 
-        self.reg.set_flags(0);
-        self.reg.check_zero(self.reg.a);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn or_n(&mut self, cycles: usize) {
-        let n = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
-        self.reg.set_byte(ByteReg::A, self.reg.a | n);
+    fn dec_rr(&mut self, reg: WordReg) {
+        let data = self.reg.get_word(reg).wrapping_sub(1);
 
-        self.bus.tick(cycles);
+        self.reg.set_word(reg, data);
 
-        self.reg.set_flags(0);
-        self.reg.check_zero(self.reg.a);
+        self.idle();
     }
 
-    fn sbc_r_a(&mut self, cycles: usize) {
-        self.reg.set_byte(
-            ByteReg::A,
-            self.reg
-                .a
-                .wrapping_sub(self.reg.a - self.reg.get_carry() as u8),
-        );
+    fn inc_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::inc(self.reg.get_byte(reg), self.reg.f);
 
-        self.bus.tick(cycles);
-
-        self.reg.check_zero(self.reg.a);
-        self.reg.subtract(true);
-        self.reg.check_half_carry(self.reg.a as u16);
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
     }
 
-    fn sbc_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.reg.set_byte(
-            ByteReg::A,
-            self.reg
-                .a
-                .wrapping_sub(self.reg.get_byte(reg) - self.reg.get_carry() as u8),
-        );
-
-        self.bus.tick(cycles);
+    fn inc_hl(&mut self) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        let (result, flags) = alu::inc(data, self.reg.f);
 
-        check_all!(self, reg, self.reg.a, true);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn sbc_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL);
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-
-        self.reg.set_byte(
-            ByteReg::A,
-            self.reg.a.wrapping_sub(data - self.reg.get_carry() as u8),
-        );
+    fn inc_rr(&mut self, reg: WordReg) {
+        let data = self.reg.get_word(reg).wrapping_add(1);
 
-        self.bus.tick(cycles);
+        self.reg.set_word(reg, data);
 
-        check_all!(self, hl, self.reg.a, true);
+        self.idle();
     }
 
-    fn sbc_n(&mut self, cycles: usize) {
-        let n = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
+    fn or_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::or(self.reg.a, self.reg.get_byte(reg));
 
-        self.reg.set_byte(
-            ByteReg::A,
-            self.reg.a.wrapping_sub(n - self.reg.get_carry() as u8),
-        );
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.bus.tick(cycles);
+    fn or_hl(&mut self) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        let (result, flags) = alu::or(self.reg.a, data);
 
-        check_all!(self, n, self.reg.a, true);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn sub_r_a(&mut self, cycles: usize) {
-        self.reg.set_byte(
-            ByteReg::A,
-            self.reg
-                .a
-                .wrapping_sub(self.reg.a - self.reg.get_carry() as u8),
-        );
+    fn or_n(&mut self) {
+        let n = self.fetch();
+        let (result, flags) = alu::or(self.reg.a, n);
 
-        self.bus.tick(cycles);
-
-        self.reg.set_flags(ZERO | SUBTRACT);
-        self.reg.check_zero(self.reg.a);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn sub_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.reg.set_byte(
-            ByteReg::A,
-            self.reg
-                .a
-                .wrapping_sub(self.reg.get_byte(reg) - self.reg.get_carry() as u8),
-        );
+    fn sbc_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::sub(self.reg.a, self.reg.get_byte(reg), self.reg.get_carry());
 
-        self.bus.tick(cycles);
-
-        check_all!(self, reg, self.reg.a, true);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn sub_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL);
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
+    fn sbc_hl(&mut self) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        let (result, flags) = alu::sub(self.reg.a, data, self.reg.get_carry());
 
-        self.reg.set_byte(
-            ByteReg::A,
-            self.reg.a.wrapping_sub(data - self.reg.get_carry() as u8),
-        );
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.bus.tick(cycles);
+    fn sbc_n(&mut self) {
+        let n = self.fetch();
+        let (result, flags) = alu::sub(self.reg.a, n, self.reg.get_carry());
 
-        check_all!(self, hl, self.reg.a, true);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn sub_n(&mut self, cycles: usize) {
-        let n = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
+    fn sub_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::sub(self.reg.a, self.reg.get_byte(reg), false);
 
-        self.reg.set_byte(
-            ByteReg::A,
-            self.reg.a.wrapping_sub(n - self.reg.get_carry() as u8),
-        );
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.bus.tick(cycles);
+    fn sub_hl(&mut self) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        let (result, flags) = alu::sub(self.reg.a, data, false);
 
-        check_all!(self, n, self.reg.a, true);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn xor_r_a(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+    fn sub_n(&mut self) {
+        let n = self.fetch();
+        let (result, flags) = alu::sub(self.reg.a, n, false);
 
-        self.reg.set_flags(ZERO);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn xor_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn xor_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::xor(self.reg.a, self.reg.get_byte(reg));
 
-        self.reg.check_zero(self.reg.a ^ self.reg.get_byte(reg));
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn xor_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL);
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-
-        self.bus.tick(cycles);
+    fn xor_hl(&mut self) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        let (result, flags) = alu::xor(self.reg.a, data);
 
-        self.reg.check_zero(self.reg.a ^ data);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn xor_n(&mut self, cycles: usize) {
-        let n = self.bus.read(Size::Byte, self.pc as usize) as u8;
-        self.pc += 1;
+    fn xor_n(&mut self) {
+        let n = self.fetch();
+        let (result, flags) = alu::xor(self.reg.a, n);
 
-        self.bus.tick(cycles);
-
-        self.reg.check_zero(self.reg.a ^ n);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn bit_r(&mut self, bit: u8, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn bit_r(&mut self, bit: u8, reg: ByteReg) {
+        let flags = alu::bit(bit, self.reg.get_byte(reg), self.reg.f);
 
-        self.reg.set_flags(HALF_CARRY);
-        self.reg.check_zero(self.reg.get_byte(reg) & (1 << bit));
+        self.reg.set_flags(flags);
     }
 
-    fn bit_hl(&mut self, bit: u8, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL);
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-
-        self.bus.tick(cycles);
+    fn bit_hl(&mut self, bit: u8) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        let flags = alu::bit(bit, data, self.reg.f);
 
-        self.reg.set_flags(HALF_CARRY);
-        self.reg.check_zero(data & (1 << bit));
+        self.reg.set_flags(flags);
     }
 
-    fn res_r(&mut self, bit: u8, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
-
-        println!("#{:4X}", self.reg.get_byte(reg) & !(1 << bit));
+    fn res_r(&mut self, bit: u8, reg: ByteReg) {
         self.reg.set_byte(reg, self.reg.get_byte(reg) & !(1 << bit));
     }
 
-    fn res_hl(&mut self, bit: u8, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-
-        self.bus.tick(cycles);
+    fn res_hl(&mut self, bit: u8) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
 
-        self.bus
-            .write(Size::Byte, hl, (data & !(1 << bit)) as usize);
+        self.write(hl, data & !(1 << bit));
     }
 
-    fn set_r(&mut self, bit: u8, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
-
+    fn set_r(&mut self, bit: u8, reg: ByteReg) {
         self.reg.set_byte(reg, self.reg.get_byte(reg) | (1 << bit));
     }
 
-    fn set_hl(&mut self, bit: u8, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-
-        self.bus.tick(cycles);
+    fn set_hl(&mut self, bit: u8) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
 
-        self.bus.write(Size::Byte, hl, (data | (1 << bit)) as usize);
+        self.write(hl, data | (1 << bit));
     }
 
-    fn swap_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
-
-        let data = self.reg.get_byte(reg);
-        let swapped = (data << 4) | (data >> 4);
+    fn swap_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::swap(self.reg.get_byte(reg));
 
-        self.reg.set_byte(reg, swapped);
-
-        self.reg.set_flags(0);
-        self.reg.check_zero(swapped);
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
     }
 
-    fn swap_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize) as u8;
-
-        self.bus.tick(cycles);
-
-        let swapped = (data << 4) | (data >> 4);
-
-        self.bus.write(Size::Byte, hl, swapped as usize);
+    fn swap_hl(&mut self) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        let (result, flags) = alu::swap(data);
 
-        self.reg.set_flags(0);
-        self.reg.check_zero(swapped);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn rl_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn rl_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::rl(self.reg.get_byte(reg), self.reg.get_carry());
 
-        let data = self.reg.get_byte(reg) as usize;
-        let result: u16 = ((data << 1) | self.reg.get_carry() as usize) as u16;
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.reg.set_byte(reg, result as u8);
+    fn rl_hl(&mut self) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        let (result, flags) = alu::rl(data, self.reg.get_carry());
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn rl_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize);
+    fn rlc_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::rlc(self.reg.get_byte(reg));
 
-        self.bus.tick(cycles);
-
-        let result: u16 = ((data << 1) | self.reg.get_carry() as usize) as u16;
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.bus.write(Size::Byte, hl, result as usize);
+    fn rlc_hl(&mut self) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        let (result, flags) = alu::rlc(data);
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn rlc_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn rr_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::rr(self.reg.get_byte(reg), self.reg.get_carry());
 
-        let data = self.reg.get_byte(reg);
-        let result: u16 = (data << 1) as u16;
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.reg.set_byte(reg, result as u8);
+    fn rr_hl(&mut self) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        let (result, flags) = alu::rr(data, self.reg.get_carry());
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn rlc_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize);
+    fn rrc_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::rrc(self.reg.get_byte(reg));
 
-        self.bus.tick(cycles);
-
-        let result: u16 = (data << 1) as u16;
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.bus.write(Size::Byte, hl, result as usize);
+    fn rrc_hl(&mut self) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        let (result, flags) = alu::rrc(data);
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn rr_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
-
-        let data = self.reg.get_byte(reg);
+    fn sla_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::sla(self.reg.get_byte(reg));
 
-        let result: u16 = ((data >> 1) | (self.reg.get_carry() as u8) << 7) as u16;
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.reg.set_byte(reg, result as u8);
+    fn sla_hl(&mut self) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        let (result, flags) = alu::sla(data);
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn rr_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize);
+    fn sra_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::sra(self.reg.get_byte(reg));
 
-        self.bus.tick(cycles);
-        let result: u16 = ((data >> 1) | (self.reg.get_carry() as usize) << 7) as u16;
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.bus.write(Size::Byte, hl, result as usize);
+    fn sra_hl(&mut self) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        let (result, flags) = alu::sra(data);
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn rrc_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn srl_r(&mut self, reg: ByteReg) {
+        let (result, flags) = alu::srl(self.reg.get_byte(reg));
 
-        let data = self.reg.get_byte(reg);
-        let result: u16 = (data >> 1) as u16;
+        self.reg.set_byte(reg, result);
+        self.reg.set_flags(flags);
+    }
 
-        self.reg.set_byte(reg, result as u8);
+    fn srl_hl(&mut self) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        let (result, flags) = alu::srl(data);
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.write(hl, result);
+        self.reg.set_flags(flags);
     }
 
-    fn rrc_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize);
+    fn rla(&mut self) {
+        let (result, flags) = alu::rl(self.reg.a, self.reg.get_carry());
 
-        self.bus.tick(cycles);
-
-        let result: u16 = (data >> 1) as u16;
+        self.reg.set_byte(ByteReg::A, result);
+        // Unlike the CB prefixed rotates, the accumulator rotates always clear Z
+        self.reg.set_flags(flags & !ZERO);
+    }
 
-        self.bus.write(Size::Byte, hl, result as usize);
+    fn rlca(&mut self) {
+        let (result, flags) = alu::rlc(self.reg.a);
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags & !ZERO);
     }
 
-    fn sla_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn rra(&mut self) {
+        let (result, flags) = alu::rr(self.reg.a, self.reg.get_carry());
 
-        let data = self.reg.get_byte(reg);
-        let result: u16 = (data << 1) as u16;
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags & !ZERO);
+    }
 
-        self.reg.set_byte(reg, result as u8);
+    fn rrca(&mut self) {
+        let (result, flags) = alu::rrc(self.reg.a);
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags & !ZERO);
     }
 
-    fn sla_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize);
+    fn ld_r_r(&mut self, reg1: ByteReg, reg2: ByteReg) {
+        let data = self.reg.get_byte(reg2);
+        self.reg.set_byte(reg1, data);
+    }
 
-        self.bus.tick(cycles);
+    fn ld_r_n(&mut self, reg: ByteReg) {
+        let data = self.fetch();
+        self.reg.set_byte(reg, data);
+    }
 
-        let result: u16 = (data << 1) as u16;
+    fn ld_r_hl(&mut self, reg: ByteReg) {
+        let data = self.read(self.reg.get_word(WordReg::HL));
+        self.reg.set_byte(reg, data);
+    }
 
-        self.bus.write(Size::Byte, hl, result as usize);
+    fn ld_hl_r(&mut self, reg: ByteReg) {
+        let hl = self.reg.get_word(WordReg::HL);
+        self.write(hl, self.reg.get_byte(reg));
+    }
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+    fn ld_hl_n(&mut self) {
+        let data = self.fetch();
+        self.write(self.reg.get_word(WordReg::HL), data);
     }
 
-    fn sra_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn ld_a_rr(&mut self, reg: WordReg) {
+        let data = self.read(self.reg.get_word(reg));
+        self.reg.set_byte(ByteReg::A, data);
+    }
 
-        let data = self.reg.get_byte(reg);
-        let result: u16 = ((data >> 1) | (data & 0x80)) as u16;
+    fn ld_rr_a(&mut self, reg: WordReg) {
+        let addr = self.reg.get_word(reg);
+        self.write(addr, self.reg.a);
+    }
 
-        self.reg.set_byte(reg, result as u8);
+    fn ld_a_nn(&mut self) {
+        let addr = self.fetch_word();
+        let data = self.read(addr);
+        self.reg.set_byte(ByteReg::A, data);
+    }
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+    fn ld_nn_a(&mut self) {
+        let addr = self.fetch_word();
+        self.write(addr, self.reg.a);
     }
 
-    fn sra_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize);
+    fn ld_rr_nn(&mut self, reg: WordReg) {
+        let data = self.fetch_word();
+        self.reg.set_word(reg, data);
+    }
 
-        self.bus.tick(cycles);
+    fn ld_nn_sp(&mut self) {
+        let addr = self.fetch_word();
+        let sp = self.reg.get_word(WordReg::SP);
 
-        let result: u16 = ((data >> 1) | (data & 0x80)) as u16;
+        self.write(addr, (sp & 0xff) as u8);
+        self.write(addr.wrapping_add(1), (sp >> 8) as u8);
+    }
 
-        self.bus.write(Size::Byte, hl, result as usize);
+    fn ld_sp_hl(&mut self) {
+        self.idle();
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.reg
+            .set_word(WordReg::SP, self.reg.get_word(WordReg::HL));
     }
 
-    fn srl_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
-
-        let data = self.reg.get_byte(reg);
-        let result: u16 = (data >> 1) as u16;
+    fn ld_a_hl(&mut self, offset: bool) {
+        let hl = self.reg.get_word(WordReg::HL);
+        let data = self.read(hl);
+        self.reg.set_byte(ByteReg::A, data);
 
-        self.reg.set_byte(reg, result as u8);
+        let next = if offset {
+            hl.wrapping_add(1)
+        } else {
+            hl.wrapping_sub(1)
+        };
 
-        clear_flag_and_check_zero_carry!(self, reg, result as u8);
+        self.reg.set_word(WordReg::HL, next);
     }
 
-    fn srl_hl(&mut self, cycles: usize) {
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize);
-
-        self.bus.tick(cycles);
-
-        let result: u16 = (data >> 1) as u16;
+    fn ld_hl_a(&mut self, offset: bool) {
+        let hl = self.reg.get_word(WordReg::HL);
+        self.write(hl, self.reg.a);
 
-        self.bus.write(Size::Byte, hl, result as usize);
+        let next = if offset {
+            hl.wrapping_add(1)
+        } else {
+            hl.wrapping_sub(1)
+        };
 
-        clear_flag_and_check_zero_carry!(self, hl, result as u8);
+        self.reg.set_word(WordReg::HL, next);
     }
 
-    fn rla(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+    fn ld_a_c(&mut self) {
+        let addr = 0xFF00 | self.reg.c as u16;
+        let data = self.read(addr);
+        self.reg.set_byte(ByteReg::A, data);
+    }
 
-        let data = self.reg.get_byte(ByteReg::A) as u16;
-        let result: u16 = data << 1;
+    fn ld_c_a(&mut self) {
+        let addr = 0xFF00 | self.reg.c as u16;
+        self.write(addr, self.reg.a);
+    }
 
-        self.reg.set_byte(ByteReg::A, result as u8);
+    fn ldh_a_n(&mut self) {
+        let addr = 0xFF00 | self.fetch() as u16;
+        let data = self.read(addr);
+        self.reg.set_byte(ByteReg::A, data);
+    }
 
-        self.reg.set_flags(0);
-        self.reg.check_carry(result);
+    fn ldh_n_a(&mut self) {
+        let addr = 0xFF00 | self.fetch() as u16;
+        self.write(addr, self.reg.a);
     }
 
-    fn rlca(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+    fn push_byte(&mut self, data: u8) {
+        let sp = self.reg.get_word(WordReg::SP).wrapping_sub(1);
+        self.write(sp, data);
 
-        let data = self.reg.get_byte(ByteReg::A);
-        let result: u16 = (data << 1) as u16;
+        self.reg.set_word(WordReg::SP, sp);
+    }
 
-        self.reg.set_byte(ByteReg::A, result as u8);
+    // The stack pointer is decremented in an internal cycle before both bytes are written
+    fn push_word(&mut self, data: u16) {
+        self.idle();
 
-        self.reg.set_flags(0);
-        self.reg.check_carry(result);
+        self.push_byte((data >> 8) as u8);
+        self.push_byte((data & 0xff) as u8);
     }
 
-    fn rra(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+    fn pop_word(&mut self) -> u16 {
+        let sp = self.reg.get_word(WordReg::SP);
 
-        let data = self.reg.get_byte(ByteReg::A);
-        let result: u16 = ((data >> 1) | (self.reg.get_carry() as u8) << 7) as u16;
+        let lo = self.read(sp) as u16;
+        let sp = sp.wrapping_add(1);
 
-        self.reg.set_byte(ByteReg::A, result as u8);
+        let hi = self.read(sp) as u16;
+        let sp = sp.wrapping_add(1);
 
-        self.reg.set_flags(0);
-        self.reg.check_carry(result);
+        self.reg.set_word(WordReg::SP, sp);
+
+        (hi << 8) | lo
     }
 
-    fn rrca(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+    fn push_rr(&mut self, reg: WordReg) {
+        self.push_word(self.reg.get_word(reg));
+    }
 
-        let data = self.reg.get_byte(ByteReg::A);
-        let result: u16 = ((data >> 1) | (data & 0x80)) as u16;
+    fn pop_rr(&mut self, reg: WordReg) {
+        let data = self.pop_word();
 
-        println!("{} {}", data, result);
+        // The lower nibble of F is hardwired to zero
+        match reg {
+            WordReg::AF => self.reg.set_word(reg, data & 0xfff0),
+            _ => self.reg.set_word(reg, data),
+        }
+    }
 
-        self.reg.set_byte(ByteReg::A, result as u8);
+    // Taken jumps spend an extra internal cycle loading PC
+    fn jump(&mut self, addr: u16) {
+        self.idle();
 
-        self.reg.set_flags(0);
-        self.reg.check_carry(result);
+        self.pc = addr;
     }
 
-    fn ld_r_r(&mut self, reg1: ByteReg, reg2: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn jp_nn(&mut self) {
+        let addr = self.fetch_word();
+        self.jump(addr);
+    }
 
-        let data = self.reg.get_byte(reg2);
-        self.reg.set_byte(reg1, data);
+    fn jp_hl(&mut self) {
+        self.pc = self.reg.get_word(WordReg::HL);
     }
 
-    fn ld_r_n(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn jp_cc_nn(&mut self, cond: Condition) {
+        let addr = self.fetch_word();
 
-        let data = self.bus.read(Size::Byte, self.pc as usize);
-        self.reg.set_byte(reg, data as u8);
-        self.pc += 1;
+        if cond.check(self.reg.f) {
+            self.jump(addr);
+        }
     }
 
-    fn ld_r_hl(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
-
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize);
-        self.reg.set_byte(reg, data as u8);
+    fn jr_e(&mut self) {
+        let e = self.fetch() as i8;
+        self.jump(self.pc.wrapping_add(e as u16));
     }
 
-    fn ld_hl_r(&mut self, reg: ByteReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn jr_cc_e(&mut self, cond: Condition) {
+        let e = self.fetch() as i8;
 
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.reg.get_byte(reg);
-        self.bus.write(Size::Byte, hl, data as usize);
+        if cond.check(self.reg.f) {
+            self.jump(self.pc.wrapping_add(e as u16));
+        }
     }
 
-    fn ld_hl_n(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
-
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, self.pc as usize);
-        self.bus.write(Size::Byte, hl, data as usize);
-        self.pc += 1;
+    fn ret(&mut self) {
+        let addr = self.pop_word();
+        self.jump(addr);
     }
 
-    fn ld_a_rr(&mut self, reg: WordReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn ret_cc(&mut self, cond: Condition) {
+        // Checking the condition takes an internal cycle of its own
+        self.idle();
 
-        let addr = self.reg.get_word(reg) as usize;
-        self.bus
-            .write(Size::Byte, addr, self.reg.get_byte(ByteReg::A) as usize);
+        if cond.check(self.reg.f) {
+            self.ret();
+        }
     }
 
-    fn ld_rr_a(&mut self, reg: WordReg, cycles: usize) {
-        self.bus.tick(cycles);
+    fn reti(&mut self) {
+        self.ret();
+        self.ime = true;
+    }
 
-        let addr = self.reg.get_word(reg) as usize;
-        self.reg
-            .set_byte(ByteReg::A, self.bus.read(Size::Byte, addr) as u8);
+    fn rst(&mut self, vector: u16) {
+        self.push_word(self.pc);
+        self.pc = vector;
     }
 
-    fn ld_a_nn(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+    fn call_nn(&mut self) {
+        let addr = self.fetch_word();
 
-        let data = self.bus.read(Size::Word, self.pc as usize);
-        self.reg.set_byte(ByteReg::A, data as u8);
-        self.pc += 2;
+        self.push_word(self.pc);
+        self.pc = addr;
     }
 
-    fn ld_nn_a(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+    fn call_cc_nn(&mut self, cond: Condition) {
+        let addr = self.fetch_word();
 
-        let data = self.reg.get_byte(ByteReg::A);
-        self.bus.write(Size::Word, self.pc as usize, data as usize);
-        self.pc += 2;
+        if cond.check(self.reg.f) {
+            self.push_word(self.pc);
+            self.pc = addr;
+        }
     }
 
-    fn ld_a_hl(&mut self, cycles: usize, offset: bool) {
-        self.bus.tick(cycles);
+    fn nop(&mut self) {}
 
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.bus.read(Size::Byte, hl as usize);
-        self.reg.set_byte(ByteReg::A, data as u8);
+    fn stop(&mut self) {
+        // STOP is followed by a padding byte that gets skipped
+        self.pc = self.pc.wrapping_add(1);
+        self.state = CpuState::Stopped;
 
-        let next = if offset {
-            (hl + 1) as u16
-        } else {
-            (hl - 1) as u16
-        };
-
-        self.reg.set_word(WordReg::HL, next);
+        log!(Cpu, Debug, "STOP at {:04X}", self.pc.wrapping_sub(2));
     }
 
-    fn ld_hl_a(&mut self, cycles: usize, offset: bool) {
-        self.bus.tick(cycles);
-
-        let hl = self.reg.get_word(WordReg::HL) as usize;
-        let data = self.reg.get_byte(ByteReg::A);
-        self.bus.write(Size::Byte, hl, data as usize);
-
-        let next = if offset {
-            (hl + 1) as u16
+    fn halt(&mut self) {
+        if !self.ime && self.bus.pending_interrupts() != 0 {
+            log!(Cpu, Debug, "HALT bug at {:04X}", self.pc.wrapping_sub(1));
+            self.halt_bug = true;
         } else {
-            (hl - 1) as u16
-        };
-
-        self.reg.set_word(WordReg::HL, next);
+            self.state = CpuState::Halted;
+        }
     }
 
-    fn ld_a_c(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
-
-        let addr = 0xFF00 + self.reg.get_byte(ByteReg::C) as usize;
-        self.reg
-            .set_byte(ByteReg::A, self.bus.read(Size::Byte, addr) as u8);
-    }
+    fn dispatch_interrupt(&mut self) {
+        self.ime = false;
 
-    fn ld_c_a(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+        // With EI right before a bugged HALT the return address is the HALT itself,
+        // which then runs again after RETI
+        if self.halt_bug {
+            self.halt_bug = false;
+            self.pc = self.pc.wrapping_sub(1);
+        }
 
-        let addr = 0xFF00 + self.reg.get_byte(ByteReg::C) as usize;
-        self.bus
-            .write(Size::Byte, addr, self.reg.get_byte(ByteReg::A) as usize);
+        // Two wait states, then the usual stack pointer decrement of a push
+        self.idle();
+        self.idle();
+        self.idle();
+
+        self.push_byte((self.pc >> 8) as u8);
+
+        // The interrupt is only picked after the high byte is pushed, which may have
+        // overwritten IE and cancelled the dispatch altogether
+        let interrupt = self.bus.highest_pending();
+
+        self.push_byte((self.pc & 0xff) as u8);
+
+        match interrupt {
+            Some(interrupt) => {
+                log!(
+                    Cpu,
+                    Debug,
+                    "Dispatching {:?} from {:04X}",
+                    interrupt,
+                    self.pc
+                );
+                self.bus.acknowledge(interrupt);
+                self.pc = interrupt.vector();
+            }
+            None => {
+                log!(
+                    Cpu,
+                    Debug,
+                    "Interrupt dispatch cancelled from {:04X}",
+                    self.pc
+                );
+                self.pc = 0x0000;
+            }
+        }
     }
 
-    fn ldh_a_n(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
-
-        let addr = 0xFF00 + self.bus.read(Size::Byte, self.pc as usize) as usize;
-        self.reg
-            .set_byte(ByteReg::A, self.bus.read(Size::Byte, addr) as u8);
-        self.pc += 1;
+    fn lock(&mut self) {
+        self.state = CpuState::Locked;
     }
 
-    fn ldh_n_a(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+    fn daa(&mut self) {
+        let (result, flags) = alu::daa(self.reg.a, self.reg.f);
 
-        let addr = 0xFF00 + self.bus.read(Size::Byte, self.pc as usize) as usize;
-        self.bus
-            .write(Size::Byte, addr, self.reg.get_byte(ByteReg::A) as usize);
-        self.pc += 1;
+        self.reg.set_byte(ByteReg::A, result);
+        self.reg.set_flags(flags);
     }
 
-    fn call_nn(&mut self, cycles: usize) {
-        self.bus.tick(cycles);
+    fn cpl(&mut self) {
+        self.reg.set_byte(ByteReg::A, !self.reg.a);
 
-        let addr = self.bus.read(Size::Word, self.pc as usize);
-        self.pc += 2;
+        self.reg.set_flags(self.reg.f | SUBTRACT | HALF_CARRY);
+    }
 
-        self.reg.push_word(self.pc);
-        self.pc = addr as u16;
+    fn scf(&mut self) {
+        self.reg.set_flags((self.reg.f & ZERO) | CARRY);
     }
 
-    fn call_cc_nn(&mut self, cond: u8, cycles: usize) {
-        self.bus.tick(cycles);
+    fn ccf(&mut self) {
+        self.reg
+            .set_flags((self.reg.f & ZERO) | ((self.reg.f ^ CARRY) & CARRY));
+    }
 
-        let addr = self.bus.read(Size::Word, self.pc as usize);
-        self.pc += 2;
+    fn di(&mut self) {
+        self.ime = false;
+        self.ei_pending = false;
+    }
 
-        if self.reg.f & cond != 0 {
-            self.reg.push_word(self.pc);
-            self.pc = addr as u16;
-        }
+    fn ei(&mut self) {
+        self.ei_pending = true;
     }
 }
 
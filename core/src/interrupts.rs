@@ -0,0 +1,88 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    VBlank,
+    Stat,
+    Timer,
+    Serial,
+    Joypad,
+}
+
+impl Interrupt {
+    // Ordered by priority, highest first
+    pub const ALL: [Interrupt; 5] = [
+        Interrupt::VBlank,
+        Interrupt::Stat,
+        Interrupt::Timer,
+        Interrupt::Serial,
+        Interrupt::Joypad,
+    ];
+
+    pub fn bit(&self) -> u8 {
+        match self {
+            Interrupt::VBlank => 0b0000_0001,
+            Interrupt::Stat => 0b0000_0010,
+            Interrupt::Timer => 0b0000_0100,
+            Interrupt::Serial => 0b0000_1000,
+            Interrupt::Joypad => 0b0001_0000,
+        }
+    }
+
+    pub fn vector(&self) -> u16 {
+        match self {
+            Interrupt::VBlank => 0x40,
+            Interrupt::Stat => 0x48,
+            Interrupt::Timer => 0x50,
+            Interrupt::Serial => 0x58,
+            Interrupt::Joypad => 0x60,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Interrupts {
+    pub enable: u8,
+    pub flag: u8,
+}
+
+impl Interrupts {
+    pub fn new() -> Self {
+        Self { enable: 0, flag: 0 }
+    }
+
+    pub fn request(&mut self, interrupt: Interrupt) {
+        self.flag |= interrupt.bit();
+    }
+
+    pub fn acknowledge(&mut self, interrupt: Interrupt) {
+        self.flag &= !interrupt.bit();
+    }
+
+    pub fn pending(&self) -> u8 {
+        self.enable & self.flag & 0x1f
+    }
+
+    pub fn highest_pending(&self) -> Option<Interrupt> {
+        let pending = self.pending();
+
+        Interrupt::ALL
+            .into_iter()
+            .find(|interrupt| pending & interrupt.bit() != 0)
+    }
+
+    pub fn read_flag(&self) -> u8 {
+        // The upper three bits of IF are unused and always read as 1
+        self.flag | 0xe0
+    }
+
+    pub fn write_flag(&mut self, data: u8) {
+        self.flag = data & 0x1f;
+    }
+
+    pub fn read_enable(&self) -> u8 {
+        self.enable
+    }
+
+    pub fn write_enable(&mut self, data: u8) {
+        self.enable = data;
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::Display;
+
+pub const IO_START: u16 = 0xFF00;
+pub const IO_SIZE: usize = 0x80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Device {
+    Joypad,
+    Serial,
+    Timer,
+    Interrupts,
+    Apu,
+    Ppu,
+    Cgb,
+    BootRom,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IoRegister {
+    pub addr: u16,
+    pub name: &'static str,
+    // Bits outside the read mask are unused or write only and always read back as 1
+    pub read_mask: u8,
+    // Bits outside the write mask keep their value on writes
+    pub write_mask: u8,
+    pub device: Device,
+}
+
+impl IoRegister {
+    const fn new(
+        addr: u16,
+        name: &'static str,
+        read_mask: u8,
+        write_mask: u8,
+        device: Device,
+    ) -> Self {
+        Self {
+            addr,
+            name,
+            read_mask,
+            write_mask,
+            device,
+        }
+    }
+
+    pub fn read(&self, value: u8) -> u8 {
+        value | !self.read_mask
+    }
+
+    pub fn write(&self, old: u8, data: u8) -> u8 {
+        (old & !self.write_mask) | (data & self.write_mask)
+    }
+}
+
+impl Display for IoRegister {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({:04X})", self.name, self.addr)
+    }
+}
+
+#[rustfmt::skip]
+pub static REGISTERS: [IoRegister; 74] = [
+    IoRegister::new(0xFF00, "P1", 0x3F, 0x30, Device::Joypad),
+    IoRegister::new(0xFF01, "SB", 0xFF, 0xFF, Device::Serial),
+    IoRegister::new(0xFF02, "SC", 0x83, 0x83, Device::Serial),
+    IoRegister::new(0xFF04, "DIV", 0xFF, 0xFF, Device::Timer),
+    IoRegister::new(0xFF05, "TIMA", 0xFF, 0xFF, Device::Timer),
+    IoRegister::new(0xFF06, "TMA", 0xFF, 0xFF, Device::Timer),
+    IoRegister::new(0xFF07, "TAC", 0x07, 0x07, Device::Timer),
+    IoRegister::new(0xFF0F, "IF", 0x1F, 0x1F, Device::Interrupts),
+    IoRegister::new(0xFF10, "NR10", 0x7F, 0x7F, Device::Apu),
+    IoRegister::new(0xFF11, "NR11", 0xC0, 0xFF, Device::Apu),
+    IoRegister::new(0xFF12, "NR12", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF13, "NR13", 0x00, 0xFF, Device::Apu),
+    IoRegister::new(0xFF14, "NR14", 0x40, 0xC7, Device::Apu),
+    IoRegister::new(0xFF16, "NR21", 0xC0, 0xFF, Device::Apu),
+    IoRegister::new(0xFF17, "NR22", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF18, "NR23", 0x00, 0xFF, Device::Apu),
+    IoRegister::new(0xFF19, "NR24", 0x40, 0xC7, Device::Apu),
+    IoRegister::new(0xFF1A, "NR30", 0x80, 0x80, Device::Apu),
+    IoRegister::new(0xFF1B, "NR31", 0x00, 0xFF, Device::Apu),
+    IoRegister::new(0xFF1C, "NR32", 0x60, 0x60, Device::Apu),
+    IoRegister::new(0xFF1D, "NR33", 0x00, 0xFF, Device::Apu),
+    IoRegister::new(0xFF1E, "NR34", 0x40, 0xC7, Device::Apu),
+    IoRegister::new(0xFF20, "NR41", 0x00, 0x3F, Device::Apu),
+    IoRegister::new(0xFF21, "NR42", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF22, "NR43", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF23, "NR44", 0x40, 0xC0, Device::Apu),
+    IoRegister::new(0xFF24, "NR50", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF25, "NR51", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF26, "NR52", 0x8F, 0x80, Device::Apu),
+    IoRegister::new(0xFF30, "WAVE0", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF31, "WAVE1", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF32, "WAVE2", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF33, "WAVE3", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF34, "WAVE4", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF35, "WAVE5", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF36, "WAVE6", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF37, "WAVE7", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF38, "WAVE8", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF39, "WAVE9", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF3A, "WAVE10", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF3B, "WAVE11", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF3C, "WAVE12", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF3D, "WAVE13", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF3E, "WAVE14", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF3F, "WAVE15", 0xFF, 0xFF, Device::Apu),
+    IoRegister::new(0xFF40, "LCDC", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF41, "STAT", 0x7F, 0x78, Device::Ppu),
+    IoRegister::new(0xFF42, "SCY", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF43, "SCX", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF44, "LY", 0xFF, 0x00, Device::Ppu),
+    IoRegister::new(0xFF45, "LYC", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF46, "DMA", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF47, "BGP", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF48, "OBP0", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF49, "OBP1", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF4A, "WY", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF4B, "WX", 0xFF, 0xFF, Device::Ppu),
+    IoRegister::new(0xFF4D, "KEY1", 0x81, 0x01, Device::Cgb),
+    IoRegister::new(0xFF4F, "VBK", 0x01, 0x01, Device::Cgb),
+    IoRegister::new(0xFF50, "BANK", 0x00, 0x01, Device::BootRom),
+    IoRegister::new(0xFF51, "HDMA1", 0x00, 0xFF, Device::Cgb),
+    IoRegister::new(0xFF52, "HDMA2", 0x00, 0xF0, Device::Cgb),
+    IoRegister::new(0xFF53, "HDMA3", 0x00, 0x1F, Device::Cgb),
+    IoRegister::new(0xFF54, "HDMA4", 0x00, 0xF0, Device::Cgb),
+    IoRegister::new(0xFF55, "HDMA5", 0xFF, 0xFF, Device::Cgb),
+    IoRegister::new(0xFF56, "RP", 0xC3, 0xC1, Device::Cgb),
+    IoRegister::new(0xFF68, "BCPS", 0xBF, 0xBF, Device::Cgb),
+    IoRegister::new(0xFF69, "BCPD", 0xFF, 0xFF, Device::Cgb),
+    IoRegister::new(0xFF6A, "OCPS", 0xBF, 0xBF, Device::Cgb),
+    IoRegister::new(0xFF6B, "OCPD", 0xFF, 0xFF, Device::Cgb),
+    IoRegister::new(0xFF6C, "OPRI", 0x01, 0x01, Device::Cgb),
+    IoRegister::new(0xFF70, "SVBK", 0x07, 0x07, Device::Cgb),
+    IoRegister::new(0xFF76, "PCM12", 0xFF, 0x00, Device::Apu),
+    IoRegister::new(0xFF77, "PCM34", 0xFF, 0x00, Device::Apu),
+];
+
+// Registers indexed by their offset from 0xFF00, built once from the list above
+static INDEX: [Option<IoRegister>; IO_SIZE] = build_index();
+
+const fn build_index() -> [Option<IoRegister>; IO_SIZE] {
+    let mut index = [None; IO_SIZE];
+    let mut i = 0;
+
+    while i < REGISTERS.len() {
+        index[(REGISTERS[i].addr - IO_START) as usize] = Some(REGISTERS[i]);
+        i += 1;
+    }
+
+    index
+}
+
+pub fn lookup(addr: u16) -> Option<&'static IoRegister> {
+    match addr.checked_sub(IO_START) {
+        Some(offset) if (offset as usize) < IO_SIZE => INDEX[offset as usize].as_ref(),
+        _ => None,
+    }
+}
+
+pub fn lookup_name(name: &str) -> Option<&'static IoRegister> {
+    REGISTERS.iter().find(|reg| reg.name == name)
+}
+
+// Backing store for registers whose device is not emulated yet
+pub struct IoRegisters {
+    regs: [u8; IO_SIZE],
+}
+
+impl IoRegisters {
+    pub fn new() -> Self {
+        Self { regs: [0; IO_SIZE] }
+    }
+
+    pub fn read(&self, addr: u16) -> u8 {
+        self.regs[(addr - IO_START) as usize]
+    }
+
+    pub fn write(&mut self, addr: u16, data: u8) {
+        self.regs[(addr - IO_START) as usize] = data;
+    }
+}
+
+impl Default for IoRegisters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
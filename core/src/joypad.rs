@@ -0,0 +1,135 @@
+use crate::interrupts::{Interrupt, Interrupts};
+use std::collections::VecDeque;
+
+pub const P1: u16 = 0xFF00;
+
+// Writing 0 to a select bit connects that button group to the input lines
+const SELECT_DIRECTIONS: u8 = 0b0001_0000;
+const SELECT_ACTIONS: u8 = 0b0010_0000;
+const SELECT_MASK: u8 = SELECT_DIRECTIONS | SELECT_ACTIONS;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Button {
+    Right,
+    Left,
+    Up,
+    Down,
+    A,
+    B,
+    Select,
+    Start,
+}
+
+impl Button {
+    pub const ALL: [Button; 8] = [
+        Button::Right,
+        Button::Left,
+        Button::Up,
+        Button::Down,
+        Button::A,
+        Button::B,
+        Button::Select,
+        Button::Start,
+    ];
+
+    // Directions occupy the low nibble and actions the high one, each button sits on
+    // the same input line as its counterpart in the other group
+    pub fn bit(&self) -> u8 {
+        1 << (*self as u8)
+    }
+}
+
+pub struct Joypad {
+    select: u8,
+    pressed: u8,
+    // Input changes waiting for their time, ordered by it
+    queue: VecDeque<(usize, Button, bool)>,
+}
+
+impl Joypad {
+    pub fn new() -> Self {
+        Self {
+            // Both groups are selected after the boot ROM, P1 reads 0xCF
+            select: 0x00,
+            pressed: 0x00,
+            queue: VecDeque::new(),
+        }
+    }
+
+    pub fn read(&self) -> u8 {
+        0xC0 | self.select | self.lines()
+    }
+
+    pub fn write(&mut self, data: u8, interrupts: &mut Interrupts) {
+        let before = self.lines();
+        self.select = data & SELECT_MASK;
+
+        self.check_falling(before, interrupts);
+    }
+
+    pub fn pressed(&self, button: Button) -> bool {
+        self.pressed & button.bit() != 0
+    }
+
+    pub fn set(&mut self, button: Button, pressed: bool, interrupts: &mut Interrupts) {
+        let before = self.lines();
+
+        if pressed {
+            self.pressed |= button.bit();
+        } else {
+            self.pressed &= !button.bit();
+        }
+
+        self.check_falling(before, interrupts);
+    }
+
+    // Applies the change once the given time is reached, changes for the same time
+    // apply in the order they were queued
+    pub fn set_at(&mut self, at: usize, button: Button, pressed: bool) {
+        let index = self.queue.partition_point(|(time, _, _)| *time <= at);
+        self.queue.insert(index, (at, button, pressed));
+    }
+
+    pub fn next_event(&self) -> Option<usize> {
+        self.queue.front().map(|(at, _, _)| *at)
+    }
+
+    pub fn sync(&mut self, now: usize, interrupts: &mut Interrupts) {
+        while let Some((at, button, pressed)) = self.queue.front().copied() {
+            if at > now {
+                break;
+            }
+
+            self.queue.pop_front();
+            self.set(button, pressed, interrupts);
+        }
+    }
+
+    // The four input lines as seen on P10-P13, low when a button on a selected group
+    // is held. With both groups selected the lines are shared
+    pub fn lines(&self) -> u8 {
+        let mut lines = 0x0F;
+
+        if self.select & SELECT_DIRECTIONS == 0 {
+            lines &= !(self.pressed & 0x0F);
+        }
+
+        if self.select & SELECT_ACTIONS == 0 {
+            lines &= !(self.pressed >> 4);
+        }
+
+        lines
+    }
+
+    fn check_falling(&self, before: u8, interrupts: &mut Interrupts) {
+        if before & !self.lines() != 0 {
+            interrupts.request(Interrupt::Joypad);
+        }
+    }
+}
+
+impl Default for Joypad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
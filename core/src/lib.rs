@@ -1,6 +1,13 @@
+pub mod blargg;
 pub mod bus;
 pub mod cartridge;
+pub mod interrupts;
+pub mod io;
+pub mod joypad;
+pub mod logger;
 pub mod memory;
-pub mod types;
+pub mod scheduler;
+pub mod serial;
+pub mod timer;
 
 pub mod cpu;
@@ -0,0 +1,157 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    Memory,
+    Io,
+    Cpu,
+    Mbc,
+    Ppu,
+    Apu,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Category::Memory,
+        Category::Io,
+        Category::Cpu,
+        Category::Mbc,
+        Category::Ppu,
+        Category::Apu,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Memory => "memory",
+            Category::Io => "io",
+            Category::Cpu => "cpu",
+            Category::Mbc => "mbc",
+            Category::Ppu => "ppu",
+            Category::Apu => "apu",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Ordered by verbosity, a category logs everything up to its configured level
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+pub struct Record<'a> {
+    pub category: Category,
+    pub level: Level,
+    pub args: fmt::Arguments<'a>,
+}
+
+pub trait Sink: Send + Sync {
+    fn log(&self, record: &Record);
+}
+
+// Writes every record to stderr as "[LEVEL category] message"
+pub struct StderrSink;
+
+impl Sink for StderrSink {
+    fn log(&self, record: &Record) {
+        eprintln!("[{} {}] {}", record.level, record.category, record.args);
+    }
+}
+
+// Zero means the category is off, which is the default for all of them
+const OFF: u8 = 0;
+
+static LEVELS: [AtomicU8; Category::ALL.len()] =
+    [const { AtomicU8::new(OFF) }; Category::ALL.len()];
+
+static SINK: RwLock<Option<Box<dyn Sink>>> = RwLock::new(None);
+
+pub fn set_sink(sink: Box<dyn Sink>) {
+    *SINK.write().unwrap_or_else(|err| err.into_inner()) = Some(sink);
+}
+
+pub fn clear_sink() {
+    *SINK.write().unwrap_or_else(|err| err.into_inner()) = None;
+}
+
+pub fn enable(category: Category, level: Level) {
+    LEVELS[category as usize].store(level as u8, Ordering::Relaxed);
+}
+
+pub fn disable(category: Category) {
+    LEVELS[category as usize].store(OFF, Ordering::Relaxed);
+}
+
+pub fn enable_all(level: Level) {
+    for category in Category::ALL {
+        enable(category, level);
+    }
+}
+
+pub fn disable_all() {
+    for category in Category::ALL {
+        disable(category);
+    }
+}
+
+// The only thing a disabled log statement costs, the message is never formatted
+#[inline(always)]
+pub fn enabled(category: Category, level: Level) -> bool {
+    level as u8 <= LEVELS[category as usize].load(Ordering::Relaxed)
+}
+
+#[cold]
+pub fn dispatch(category: Category, level: Level, args: fmt::Arguments) {
+    if let Some(sink) = SINK.read().unwrap_or_else(|err| err.into_inner()).as_ref() {
+        sink.log(&Record {
+            category,
+            level,
+            args,
+        });
+    }
+}
+
+#[macro_export]
+macro_rules! log {
+    ($category:ident, $level:ident, $($arg:tt)+) => {
+        if $crate::logger::enabled(
+            $crate::logger::Category::$category,
+            $crate::logger::Level::$level,
+        ) {
+            $crate::logger::dispatch(
+                $crate::logger::Category::$category,
+                $crate::logger::Level::$level,
+                format_args!($($arg)+),
+            );
+        }
+    };
+}
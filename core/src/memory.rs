@@ -1,5 +1,3 @@
-use crate::types::Size;
-
 pub struct Memory {
     ram: Vec<u8>,
 }
@@ -13,35 +11,33 @@ impl Memory {
         self.ram.len()
     }
 
-    pub fn write(&mut self, size: Size, addr: usize, data: usize) {
-        match size {
-            Size::Byte => self.write_byte(addr, data),
-            Size::Word => self.write_word(addr, data),
-        }
+    pub fn data(&self) -> &[u8] {
+        &self.ram
     }
 
-    fn write_byte(&mut self, addr: usize, data: usize) {
-        self.ram[addr as usize] = data as u8
-    }
-
-    fn write_word(&mut self, addr: usize, data: usize) {
-        self.ram[addr as usize] = (data & 0xff) as u8;
-        self.ram[(addr + 1) as usize] = ((data >> 8) & 0xff) as u8;
+    // Out of range reads float high and out of range writes are dropped
+    #[inline]
+    pub fn read(&self, addr: usize) -> u8 {
+        match self.ram.get(addr) {
+            Some(data) => *data,
+            None => 0xff,
+        }
     }
 
-    pub fn read(&self, size: Size, addr: usize) -> usize {
-        match size {
-            Size::Byte => self.read_byte(addr),
-            Size::Word => self.read_word(addr),
+    #[inline]
+    pub fn write(&mut self, addr: usize, data: u8) {
+        if let Some(byte) = self.ram.get_mut(addr) {
+            *byte = data;
         }
     }
 
-    fn read_byte(&self, addr: usize) -> usize {
-        self.ram[addr as usize] as usize
+    pub fn read_word(&self, addr: usize) -> u16 {
+        (self.read(addr) as u16) | ((self.read(addr + 1) as u16) << 8)
     }
 
-    fn read_word(&self, addr: usize) -> usize {
-        (self.ram[addr as usize] as usize) | ((self.ram[(addr + 1) as usize] as usize) << 8)
+    pub fn write_word(&mut self, addr: usize, data: u16) {
+        self.write(addr, (data & 0xff) as u8);
+        self.write(addr + 1, (data >> 8) as u8);
     }
 }
 
@@ -0,0 +1,101 @@
+// Components that need to act at a point in time, ties fire in this order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    Timer,
+    Serial,
+    Joypad,
+    Ppu,
+    Apu,
+    Dma,
+    Rtc,
+}
+
+impl Event {
+    pub const ALL: [Event; 7] = [
+        Event::Timer,
+        Event::Serial,
+        Event::Joypad,
+        Event::Ppu,
+        Event::Apu,
+        Event::Dma,
+        Event::Rtc,
+    ];
+}
+
+const NEVER: usize = usize::MAX;
+
+// Every event has at most one pending deadline in absolute T-cycles. There are only
+// a handful of them so a flat table beats a heap, and the earliest deadline is kept
+// around so the common case of nothing being due is a single compare
+pub struct Scheduler {
+    deadlines: [usize; Event::ALL.len()],
+    next: usize,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self {
+            deadlines: [NEVER; Event::ALL.len()],
+            next: NEVER,
+        }
+    }
+
+    // Replaces any deadline the event already had
+    pub fn schedule(&mut self, event: Event, at: usize) {
+        self.deadlines[event as usize] = at;
+        self.update_next();
+    }
+
+    pub fn cancel(&mut self, event: Event) {
+        self.deadlines[event as usize] = NEVER;
+        self.update_next();
+    }
+
+    pub fn deadline(&self, event: Event) -> Option<usize> {
+        match self.deadlines[event as usize] {
+            NEVER => None,
+            at => Some(at),
+        }
+    }
+
+    // Earliest pending deadline of any event
+    #[inline]
+    pub fn next_deadline(&self) -> Option<usize> {
+        match self.next {
+            NEVER => None,
+            at => Some(at),
+        }
+    }
+
+    pub fn next(&self) -> Option<(Event, usize)> {
+        let next = self.next_deadline()?;
+        let event = Event::ALL
+            .into_iter()
+            .find(|event| self.deadlines[*event as usize] == next)?;
+
+        Some((event, next))
+    }
+
+    // Removes and returns the earliest event that is due at the given time
+    #[inline]
+    pub fn pop_due(&mut self, now: usize) -> Option<(Event, usize)> {
+        if self.next > now {
+            return None;
+        }
+
+        let (event, at) = self.next()?;
+        self.cancel(event);
+
+        Some((event, at))
+    }
+
+    fn update_next(&mut self) {
+        self.next = self.deadlines.iter().copied().min().unwrap_or(NEVER);
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,232 @@
+use crate::interrupts::{Interrupt, Interrupts};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+pub const SB: u16 = 0xFF01;
+pub const SC: u16 = 0xFF02;
+
+const SC_START: u8 = 0b1000_0000;
+const SC_FAST: u8 = 0b0000_0010;
+const SC_INTERNAL: u8 = 0b0000_0001;
+
+// T-cycles per bit at 8192 Hz, and at 262144 Hz with the CGB fast clock
+const BIT_CYCLES: usize = 512;
+const FAST_BIT_CYCLES: usize = 16;
+
+// The other end of the link cable. A transfer on the internal clock hands the
+// outgoing byte over when it starts and shifts in whatever comes back one bit at a time
+pub trait SerialLink: Send {
+    fn exchange(&mut self, data: u8) -> u8;
+}
+
+// Nothing plugged in, the input line floats high
+pub struct Disconnected;
+
+impl SerialLink for Disconnected {
+    fn exchange(&mut self, _data: u8) -> u8 {
+        0xFF
+    }
+}
+
+// The output line wired straight back into the input
+pub struct Loopback;
+
+impl SerialLink for Loopback {
+    fn exchange(&mut self, data: u8) -> u8 {
+        data
+    }
+}
+
+// Prints every byte sent as a character, the way test ROMs report their results
+pub struct Stdout;
+
+impl SerialLink for Stdout {
+    fn exchange(&mut self, data: u8) -> u8 {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(&[data]);
+        let _ = stdout.flush();
+
+        0xFF
+    }
+}
+
+// Records every byte sent. Clones share the same buffer, so one can be plugged into
+// the bus and the other kept around to read the output
+#[derive(Clone, Default)]
+pub struct Capture {
+    output: Arc<Mutex<Vec<u8>>>,
+}
+
+impl Capture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bytes(&self) -> Vec<u8> {
+        self.output
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.bytes()).into_owned()
+    }
+
+    pub fn clear(&self) {
+        self.output
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    }
+}
+
+impl SerialLink for Capture {
+    fn exchange(&mut self, data: u8) -> u8 {
+        self.output
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(data);
+
+        0xFF
+    }
+}
+
+pub struct Serial {
+    sb: u8,
+    sc: u8,
+    link: Box<dyn SerialLink>,
+    // Byte coming in from the link, shifted into SB from the top
+    incoming: u8,
+    bits_left: u8,
+    next_bit: Option<usize>,
+}
+
+impl Serial {
+    pub fn new() -> Self {
+        Self {
+            sb: 0x00,
+            sc: 0x00,
+            link: Box::new(Disconnected),
+            incoming: 0xFF,
+            bits_left: 0,
+            next_bit: None,
+        }
+    }
+
+    // Plugs in a new link, any transfer in flight keeps the byte it already received
+    pub fn connect(&mut self, link: Box<dyn SerialLink>) {
+        self.link = link;
+    }
+
+    // The fast clock bit in SC only exists in CGB mode
+    pub fn read(&self, addr: u16, cgb: bool) -> u8 {
+        match addr {
+            SB => self.sb,
+            // Without the fast clock the speed bit is unused and reads as 1
+            SC if !cgb => self.sc | SC_FAST,
+            SC => self.sc,
+            _ => 0xFF,
+        }
+    }
+
+    pub fn write(
+        &mut self,
+        addr: u16,
+        data: u8,
+        cgb: bool,
+        now: usize,
+        interrupts: &mut Interrupts,
+    ) {
+        self.sync(now, interrupts);
+
+        match addr {
+            SB => self.sb = data,
+            SC => {
+                self.sc = if cgb {
+                    data & (SC_START | SC_FAST | SC_INTERNAL)
+                } else {
+                    data & (SC_START | SC_INTERNAL)
+                };
+
+                self.bits_left = 0;
+                self.next_bit = None;
+
+                if self.sc & SC_START == 0 {
+                    return;
+                }
+
+                self.bits_left = 8;
+
+                // On the external clock the transfer waits for the other side
+                if self.sc & SC_INTERNAL != 0 {
+                    self.incoming = self.link.exchange(self.sb);
+                    self.next_bit = Some(now + self.bit_cycles());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn transferring(&self) -> bool {
+        self.sc & SC_START != 0
+    }
+
+    // Drives a whole transfer from the other side when the external clock is selected.
+    // Returns the byte that was shifted out, or None if no transfer is waiting
+    pub fn clock_external(&mut self, data: u8, interrupts: &mut Interrupts) -> Option<u8> {
+        if !self.transferring() || self.sc & SC_INTERNAL != 0 {
+            return None;
+        }
+
+        let sent = self.sb;
+
+        self.sb = data;
+        self.finish(interrupts);
+
+        Some(sent)
+    }
+
+    pub fn next_event(&self) -> Option<usize> {
+        self.next_bit
+    }
+
+    pub fn sync(&mut self, now: usize, interrupts: &mut Interrupts) {
+        while let Some(at) = self.next_bit {
+            if at > now {
+                return;
+            }
+
+            self.sb = (self.sb << 1) | (self.incoming >> 7);
+            self.incoming <<= 1;
+            self.bits_left -= 1;
+
+            if self.bits_left == 0 {
+                self.finish(interrupts);
+            } else {
+                self.next_bit = Some(at + self.bit_cycles());
+            }
+        }
+    }
+
+    fn finish(&mut self, interrupts: &mut Interrupts) {
+        self.sc &= !SC_START;
+        self.bits_left = 0;
+        self.next_bit = None;
+
+        interrupts.request(Interrupt::Serial);
+    }
+
+    fn bit_cycles(&self) -> usize {
+        match self.sc & SC_FAST {
+            0 => BIT_CYCLES,
+            _ => FAST_BIT_CYCLES,
+        }
+    }
+}
+
+impl Default for Serial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,210 @@
+use crate::interrupts::{Interrupt, Interrupts};
+
+pub const DIV: u16 = 0xFF04;
+pub const TIMA: u16 = 0xFF05;
+pub const TMA: u16 = 0xFF06;
+pub const TAC: u16 = 0xFF07;
+
+const M_CYCLE: usize = 4;
+
+const TAC_ENABLE: u8 = 0b0000_0100;
+const TAC_CLOCK: u8 = 0b0000_0011;
+
+// Internal divider value left behind by the DMG boot ROM
+const POST_BOOT_COUNTER: u16 = 0xABCC;
+
+// DIV is the upper byte of a 16 bit counter that runs at the full clock. TIMA counts
+// falling edges of one of its bits ANDed with the enable bit, so anything that drops
+// that signal counts as a tick, including DIV writes and TAC changes.
+//
+// The timer is evaluated lazily: the counter is only brought up to date when a
+// register is written or an event fires, and the bus schedules an event at the next
+// TIMA overflow and reload so nothing that raises an interrupt is ever skipped
+pub struct Timer {
+    counter: u16,
+    tima: u8,
+    tma: u8,
+    tac: u8,
+    // Time the counter was last brought up to date
+    last: usize,
+    // TIMA holds 0 for one M-cycle after overflowing before TMA is loaded
+    reload_at: Option<usize>,
+    // TIMA writes in the M-cycle of the reload lose against TMA
+    reloaded_at: Option<usize>,
+}
+
+impl Timer {
+    pub fn new() -> Self {
+        Self {
+            counter: POST_BOOT_COUNTER,
+            tima: 0,
+            tma: 0,
+            tac: 0,
+            last: 0,
+            reload_at: None,
+            reloaded_at: None,
+        }
+    }
+
+    pub fn read(&self, addr: u16, now: usize) -> u8 {
+        match addr {
+            DIV => (self.counter_at(now) >> 8) as u8,
+            // No overflow can be pending, the bus syncs the timer at each one
+            TIMA => self.tima.wrapping_add(self.edges_until(now) as u8),
+            TMA => self.tma,
+            TAC => self.tac,
+            _ => 0xFF,
+        }
+    }
+
+    pub fn write(&mut self, addr: u16, data: u8, now: usize, interrupts: &mut Interrupts) {
+        self.sync(now, interrupts);
+
+        match addr {
+            DIV => {
+                let before = self.signal();
+                self.counter = 0;
+
+                if before && !self.signal() {
+                    self.increment(now);
+                }
+            }
+            TIMA => {
+                if self.reloaded_at == Some(now) {
+                    return;
+                }
+
+                // Writing during the window cancels the reload and the interrupt
+                self.reload_at = None;
+                self.tima = data;
+            }
+            TMA => {
+                self.tma = data;
+
+                if self.reloaded_at == Some(now) {
+                    self.tima = data;
+                }
+            }
+            TAC => {
+                let before = self.signal();
+                self.tac = data & (TAC_ENABLE | TAC_CLOCK);
+
+                if before && !self.signal() {
+                    self.increment(now);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // When the timer next needs to be synced, either for a pending reload or for
+    // the falling edge that overflows TIMA
+    pub fn next_event(&self) -> Option<usize> {
+        if self.reload_at.is_some() {
+            return self.reload_at;
+        }
+
+        if !self.enabled() {
+            return None;
+        }
+
+        Some(self.edge_time(0x100 - self.tima as usize))
+    }
+
+    // Brings the timer up to the given time, overflowing and reloading on the way
+    pub fn sync(&mut self, now: usize, interrupts: &mut Interrupts) {
+        loop {
+            let target = match self.reload_at {
+                Some(at) if at <= now => at,
+                _ => now,
+            };
+
+            let until_overflow = 0x100 - self.tima as usize;
+
+            if self.edges_until(target) >= until_overflow {
+                let at = self.edge_time(until_overflow);
+
+                self.advance(at);
+                self.tima = 0;
+                self.reload_at = Some(at + M_CYCLE);
+
+                continue;
+            }
+
+            self.tima += self.edges_until(target) as u8;
+            self.advance(target);
+
+            if self.reload_at == Some(target) {
+                self.reload_at = None;
+                self.reloaded_at = Some(target);
+                self.tima = self.tma;
+
+                interrupts.request(Interrupt::Timer);
+            }
+
+            if target == now {
+                return;
+            }
+        }
+    }
+
+    fn increment(&mut self, now: usize) {
+        if self.tima == 0xFF {
+            self.tima = 0;
+            self.reload_at = Some(now + M_CYCLE);
+        } else {
+            self.tima += 1;
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        self.tac & TAC_ENABLE != 0
+    }
+
+    // Distance between two falling edges of the selected counter bit
+    fn period(&self) -> usize {
+        match self.tac & TAC_CLOCK {
+            0b00 => 1024,
+            0b01 => 16,
+            0b10 => 64,
+            _ => 256,
+        }
+    }
+
+    fn signal(&self) -> bool {
+        self.enabled() && self.counter as usize & (self.period() / 2) != 0
+    }
+
+    fn counter_at(&self, now: usize) -> u16 {
+        self.counter.wrapping_add((now - self.last) as u16)
+    }
+
+    fn advance(&mut self, to: usize) {
+        self.counter = self.counter_at(to);
+        self.last = to;
+    }
+
+    // Falling edges between the last sync and the given time
+    fn edges_until(&self, to: usize) -> usize {
+        if !self.enabled() {
+            return 0;
+        }
+
+        let period = self.period();
+
+        (self.counter as usize % period + (to - self.last)) / period
+    }
+
+    // Time of the nth falling edge after the last sync
+    fn edge_time(&self, n: usize) -> usize {
+        let period = self.period();
+
+        self.last + (period - self.counter as usize % period) + (n - 1) * period
+    }
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
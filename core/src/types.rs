@@ -1,4 +0,0 @@
-pub enum Size {
-    Byte,
-    Word,
-}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use core::cpu::alu;
+
+    const ZERO: u8 = 0b1000_0000;
+    const SUB: u8 = 0b0100_0000;
+    const HALF_CARRY: u8 = 0b0010_0000;
+    const CARRY: u8 = 0b0001_0000;
+
+    #[test]
+    fn test_add() {
+        assert_eq!(alu::add(0x01, 0x02, false), (0x03, 0));
+        assert_eq!(alu::add(0x0f, 0x01, false), (0x10, HALF_CARRY));
+        assert_eq!(alu::add(0xf0, 0x10, false), (0x00, ZERO | CARRY));
+        assert_eq!(
+            alu::add(0xff, 0x01, false),
+            (0x00, ZERO | HALF_CARRY | CARRY)
+        );
+        assert_eq!(alu::add(0x0e, 0x01, true), (0x10, HALF_CARRY));
+        assert_eq!(
+            alu::add(0xff, 0x00, true),
+            (0x00, ZERO | HALF_CARRY | CARRY)
+        );
+    }
+
+    #[test]
+    fn test_sub() {
+        assert_eq!(alu::sub(0x03, 0x02, false), (0x01, SUB));
+        assert_eq!(alu::sub(0x10, 0x01, false), (0x0f, SUB | HALF_CARRY));
+        assert_eq!(alu::sub(0x00, 0x10, false), (0xf0, SUB | CARRY));
+        assert_eq!(alu::sub(0x01, 0x01, false), (0x00, ZERO | SUB));
+        assert_eq!(alu::sub(0x10, 0x0f, true), (0x00, ZERO | SUB | HALF_CARRY));
+        assert_eq!(
+            alu::sub(0x00, 0xff, true),
+            (0x00, ZERO | SUB | HALF_CARRY | CARRY)
+        );
+    }
+
+    #[test]
+    fn test_inc_dec() {
+        assert_eq!(alu::inc(0x0f, CARRY), (0x10, HALF_CARRY | CARRY));
+        assert_eq!(alu::inc(0xff, 0), (0x00, ZERO | HALF_CARRY));
+        assert_eq!(alu::dec(0x10, CARRY), (0x0f, SUB | HALF_CARRY | CARRY));
+        assert_eq!(alu::dec(0x01, 0), (0x00, ZERO | SUB));
+        assert_eq!(alu::dec(0x00, 0), (0xff, SUB | HALF_CARRY));
+    }
+
+    #[test]
+    fn test_add_word() {
+        assert_eq!(
+            alu::add_word(0x0fff, 0x0001, ZERO),
+            (0x1000, ZERO | HALF_CARRY)
+        );
+        assert_eq!(alu::add_word(0x00ff, 0x0001, 0), (0x0100, 0));
+        assert_eq!(
+            alu::add_word(0xffff, 0x0001, 0),
+            (0x0000, HALF_CARRY | CARRY)
+        );
+        assert_eq!(alu::add_word(0x8000, 0x8000, SUB), (0x0000, CARRY));
+    }
+
+    #[test]
+    fn test_add_word_signed() {
+        assert_eq!(
+            alu::add_word_signed(0xfff8, 0x08),
+            (0x0000, HALF_CARRY | CARRY)
+        );
+        assert_eq!(alu::add_word_signed(0x0000, -1), (0xffff, 0));
+        assert_eq!(
+            alu::add_word_signed(0x00ff, 0x01),
+            (0x0100, HALF_CARRY | CARRY)
+        );
+        assert_eq!(
+            alu::add_word_signed(0x0001, -1),
+            (0x0000, HALF_CARRY | CARRY)
+        );
+        assert_eq!(alu::add_word_signed(0x0f00, 0x10), (0x0f10, 0));
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(alu::rlc(0x80), (0x01, CARRY));
+        assert_eq!(alu::rrc(0x01), (0x80, CARRY));
+        assert_eq!(alu::rl(0x80, false), (0x00, ZERO | CARRY));
+        assert_eq!(alu::rr(0x00, true), (0x80, 0));
+        assert_eq!(alu::sla(0xc0), (0x80, CARRY));
+        assert_eq!(alu::sra(0x81), (0xc0, CARRY));
+        assert_eq!(alu::srl(0x81), (0x40, CARRY));
+        assert_eq!(alu::swap(0x12), (0x21, 0));
+    }
+}
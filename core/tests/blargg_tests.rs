@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+    use core::blargg::{self, Status, Subtest, DEFAULT_CYCLES};
+    use core::cartridge::header::HeaderError;
+    use core::cpu::sm83::CpuError;
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    // Prints a message over serial one byte at a time and then loops forever
+    fn serial_rom(message: &str) -> Vec<u8> {
+        let mut rom = vec![
+            0x21, 0x17, 0x01, // LD HL, message
+            0x2a, // LD A, (HL+)
+            0xb7, // OR A
+            0x28, 0x0e, // JR Z, done
+            0xe0, 0x01, // LDH (SB), A
+            0x3e, 0x81, // LD A, 0x81
+            0xe0, 0x02, // LDH (SC), A
+            0xf0, 0x02, // wait: LDH A, (SC)
+            0xcb, 0x7f, // BIT 7, A
+            0x20, 0xfa, // JR NZ, wait
+            0x18, 0xee, // JR loop
+            0x18, 0xfe, // done: JR done
+        ];
+
+        rom.extend(message.bytes());
+        rom.push(0x00);
+
+        create_rom(rom)
+    }
+
+    // Reports through cartridge RAM, then loops forever
+    fn memory_rom(status: u8, message: &str) -> Vec<u8> {
+        let mut rom = vec![
+            0x21, 0x00, 0xa0, // LD HL, 0xA000
+        ];
+
+        for byte in [0x80, 0xde, 0xb0, 0x61]
+            .into_iter()
+            .chain(message.bytes())
+            .chain([0x00])
+        {
+            rom.extend([0x3e, byte, 0x22]); // LD A, n; LD (HL+), A
+        }
+
+        rom.extend([
+            0x3e, status, // LD A, status
+            0xea, 0x00, 0xa0, // LD (0xA000), A
+            0x18, 0xfe, // JR -2
+        ]);
+
+        // Moves the program past the header it would otherwise run into
+        let mut rom = create_rom([vec![0xc3, 0x50, 0x01], vec![0; 0x4d], rom].concat());
+
+        // 8KB of cartridge RAM
+        rom[0x149] = 0x02;
+
+        rom
+    }
+
+    #[test]
+    fn test_serial_passed() {
+        let report = blargg::run(&serial_rom("test\n\nPassed\n"), DEFAULT_CYCLES).unwrap();
+
+        assert_eq!(report.status, Status::Passed);
+        assert!(report.passed());
+        assert_eq!(report.serial, "test\n\nPassed\n");
+        assert_eq!(report.lines, vec!["test", "Passed"]);
+        assert_eq!(report.memory, None);
+        assert!(report.cycles < DEFAULT_CYCLES);
+    }
+
+    #[test]
+    fn test_serial_subtests() {
+        let report = blargg::run(
+            &serial_rom("cpu_instrs\n\n01:ok  02:03  \n\nFailed 1 tests\n"),
+            DEFAULT_CYCLES,
+        )
+        .unwrap();
+
+        assert_eq!(report.status, Status::Failed);
+        assert_eq!(
+            report.subtests,
+            vec![
+                Subtest {
+                    name: "01".to_string(),
+                    result: "ok".to_string()
+                },
+                Subtest {
+                    name: "02".to_string(),
+                    result: "03".to_string()
+                },
+            ]
+        );
+        assert!(report.subtests[0].passed());
+        assert!(!report.subtests[1].passed());
+    }
+
+    #[test]
+    fn test_memory_passed() {
+        let report = blargg::run(&memory_rom(0x00, "mem\nPassed\n"), DEFAULT_CYCLES).unwrap();
+
+        assert_eq!(report.status, Status::Passed);
+        assert_eq!(report.memory_status, Some(0x00));
+        assert_eq!(report.memory.as_deref(), Some("mem\nPassed\n"));
+        assert_eq!(report.output(), "mem\nPassed\n");
+        assert_eq!(report.lines, vec!["mem", "Passed"]);
+        assert_eq!(report.serial, "");
+    }
+
+    #[test]
+    fn test_memory_failed() {
+        let report = blargg::run(&memory_rom(0x02, "Failed #2\n"), DEFAULT_CYCLES).unwrap();
+
+        assert_eq!(report.status, Status::Failed);
+        assert_eq!(report.memory_status, Some(0x02));
+        assert_eq!(report.lines, vec!["Failed #2"]);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let report = blargg::run(&serial_rom(""), 1_000_000).unwrap();
+
+        assert_eq!(report.status, Status::TimedOut);
+        assert!(report.cycles >= 1_000_000);
+        assert!(report.lines.is_empty());
+    }
+
+    #[test]
+    fn test_crash() {
+        let rom = create_rom(vec![
+            0xdd, // illegal
+        ]);
+
+        let report = blargg::run(&rom, DEFAULT_CYCLES).unwrap();
+
+        assert_eq!(
+            report.status,
+            Status::Crashed(CpuError::IllegalOpcode {
+                opcode: 0xdd,
+                addr: 0x100
+            })
+        );
+    }
+
+    #[test]
+    fn test_bad_rom() {
+        assert_eq!(
+            blargg::run(&[0; 0x10], DEFAULT_CYCLES).unwrap_err(),
+            HeaderError::TooShort { len: 0x10 }
+        );
+    }
+
+    #[test]
+    fn test_cpu_instrs() {
+        let rom = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tools/cpu_instrs.gb"
+        ))
+        .unwrap();
+
+        let report = blargg::run(&rom, DEFAULT_CYCLES).unwrap();
+
+        assert!(report.serial.contains("Passed all tests"), "{}", report);
+        assert_eq!(report.subtests.len(), 11);
+        assert!(report.subtests.iter().all(|subtest| subtest.passed()));
+    }
+}
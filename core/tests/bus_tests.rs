@@ -2,7 +2,6 @@
 mod tests {
     use core::bus::Bus;
     use core::cartridge::Cartridge;
-    use core::types::Size;
 
     fn create_fake_cartridge(ram_size: Option<u8>) -> Cartridge {
         let mut rom = vec![0; 0x150];
@@ -28,38 +27,134 @@ mod tests {
     }
 
     #[test]
-    fn test_hram_00() {
+    fn test_wram_00() {
         let mut bus = Bus::default();
-        bus.write(Size::Byte, 0xC000, 0x01);
+        bus.write(0xC000, 0x01);
 
-        assert_eq!(bus.mem.read(Size::Byte, 0x0000), 0x01);
-        assert_eq!(bus.read(Size::Byte, 0xC000), 0x01);
+        assert_eq!(bus.wram.read(0x0000), 0x01);
+        assert_eq!(bus.read(0xC000), 0x01);
 
-        bus.write(Size::Byte, 0xC001, 0x02);
+        bus.write(0xC001, 0x02);
 
-        assert_eq!(bus.mem.read(Size::Byte, 0x0001), 0x02);
-        assert_eq!(bus.read(Size::Byte, 0xC001), 0x02);
+        assert_eq!(bus.wram.read(0x0001), 0x02);
+        assert_eq!(bus.read(0xC001), 0x02);
 
-        bus.write(Size::Byte, 0xCFFF, 0x03);
+        bus.write(0xCFFF, 0x03);
 
-        assert_eq!(bus.mem.read(Size::Byte, 0x0FFF), 0x03);
-        assert_eq!(bus.read(Size::Byte, 0xCFFF), 0x03);
+        assert_eq!(bus.wram.read(0x0FFF), 0x03);
+        assert_eq!(bus.read(0xCFFF), 0x03);
     }
 
     #[test]
-    fn test_hram_01() {
+    fn test_wram_01() {
         let cart = create_fake_cartridge(None); // 2KB RAM
         let mut bus = Bus::new(Some(cart));
 
-        bus.write(Size::Byte, 0xD000, 0x01);
+        bus.write(0xD000, 0x01);
 
-        assert_eq!(bus.rom.ram.read(Size::Byte, 0x0000), 0x01);
-        assert_eq!(bus.read(Size::Byte, 0xD000), 0x01);
+        assert_eq!(bus.wram.read(0x1000), 0x01);
+        assert_eq!(bus.cartridge().ram.read(0x0000), 0x00);
+        assert_eq!(bus.read(0xD000), 0x01);
 
-        bus.write(Size::Byte, 0xD001, 0x02);
+        bus.write(0xDFFF, 0x02);
 
-        assert_eq!(bus.rom.ram.read(Size::Byte, 0x0001), 0x02);
-        assert_eq!(bus.read(Size::Byte, 0xD001), 0x02);
+        assert_eq!(bus.wram.read(0x1FFF), 0x02);
+        assert_eq!(bus.read(0xDFFF), 0x02);
+    }
+
+    #[test]
+    fn test_echo_ram() {
+        let mut bus = Bus::default();
+
+        bus.write(0xC123, 0x01);
+        assert_eq!(bus.read(0xE123), 0x01);
+
+        bus.write(0xFDFF, 0x02);
+        assert_eq!(bus.read(0xDDFF), 0x02);
+    }
+
+    #[test]
+    fn test_vram() {
+        let mut bus = Bus::default();
+
+        bus.write(0x8000, 0x01);
+        bus.write(0x9FFF, 0x02);
+
+        assert_eq!(bus.read(0x8000), 0x01);
+        assert_eq!(bus.read(0x9FFF), 0x02);
+        assert_eq!(bus.vram.read(0x1FFF), 0x02);
+    }
+
+    #[test]
+    fn test_external_ram() {
+        let cart = create_fake_cartridge(None); // 2KB RAM
+        let mut bus = Bus::new(Some(cart));
+
+        bus.write(0xA000, 0x01);
+        bus.write(0xA7FF, 0x02);
+
+        assert_eq!(bus.read(0xA000), 0x01);
+        assert_eq!(bus.read(0xA7FF), 0x02);
+        assert_eq!(bus.cartridge().ram.read(0x0000), 0x01);
+
+        // Past the end of the RAM chip
+        bus.write(0xA800, 0x03);
+        assert_eq!(bus.read(0xA800), 0xFF);
+    }
+
+    #[test]
+    fn test_no_external_ram() {
+        let cart = create_fake_cartridge(Some(0x00));
+        let mut bus = Bus::new(Some(cart));
+
+        bus.write(0xA000, 0x01);
+
+        assert_eq!(bus.read(0xA000), 0xFF);
+        assert_eq!(bus.read(0xBFFF), 0xFF);
+    }
+
+    #[test]
+    fn test_oam() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFE00, 0x01);
+        bus.write(0xFE9F, 0x02);
+
+        assert_eq!(bus.read(0xFE00), 0x01);
+        assert_eq!(bus.read(0xFE9F), 0x02);
+    }
+
+    #[test]
+    fn test_unusable() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFEA0, 0x01);
+        bus.write(0xFEFF, 0x02);
+
+        assert_eq!(bus.read(0xFEA0), 0x00);
+        assert_eq!(bus.read(0xFEFF), 0x00);
+    }
+
+    #[test]
+    fn test_hram() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFF80, 0x01);
+        bus.write(0xFFFE, 0x02);
+
+        assert_eq!(bus.read(0xFF80), 0x01);
+        assert_eq!(bus.read(0xFFFE), 0x02);
+        assert_eq!(bus.hram.read(0x7E), 0x02);
+    }
+
+    #[test]
+    fn test_ie() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFFFF, 0x1F);
+
+        assert_eq!(bus.read(0xFFFF), 0x1F);
+        assert_eq!(bus.interrupts.enable, 0x1F);
     }
 
     #[test]
@@ -67,8 +162,83 @@ mod tests {
         let cart = create_fake_cartridge(None); // 2KB RAM
         let bus = Bus::new(Some(cart));
 
-        assert_eq!(bus.read(Size::Byte, 0x148), 0x01); // ROM size
-        assert_eq!(bus.read(Size::Byte, 0x149), 0x01); // RAM size
-        assert_eq!(bus.read(Size::Byte, 0x134), 0x54); // T from header title
+        assert_eq!(bus.read(0x148), 0x01); // ROM size
+        assert_eq!(bus.read(0x149), 0x01); // RAM size
+        assert_eq!(bus.read(0x134), 0x54); // T from header title
+    }
+
+    #[test]
+    fn test_rom_nn() {
+        let mut rom = vec![0; 0x8000];
+        rom[0x4000] = 0x01;
+        rom[0x7FFF] = 0x02;
+
+        let mut bus = Bus::new(Some(Cartridge::new(&rom).unwrap()));
+
+        assert_eq!(bus.read(0x4000), 0x01);
+        assert_eq!(bus.read(0x7FFF), 0x02);
+
+        // ROM is read only
+        bus.write(0x4000, 0x03);
+        assert_eq!(bus.read(0x4000), 0x01);
+    }
+
+    #[test]
+    fn test_rom_past_end() {
+        let bus = Bus::default();
+
+        assert_eq!(bus.read(0x4000), 0xFF);
+    }
+
+    #[test]
+    fn test_word() {
+        let mut bus = Bus::default();
+
+        bus.write_word(0xC000, 0x1234);
+
+        assert_eq!(bus.read(0xC000), 0x34);
+        assert_eq!(bus.read(0xC001), 0x12);
+        assert_eq!(bus.read_word(0xC000), 0x1234);
+    }
+
+    #[test]
+    fn test_word_wraps() {
+        let mut bus = Bus::default();
+
+        bus.write_word(0xFFFF, 0x1234);
+
+        // The high byte lands in ROM and is dropped
+        assert_eq!(bus.read(0xFFFF), 0x34);
+        assert_eq!(bus.read_word(0xFFFF), 0x0034);
+    }
+
+    #[test]
+    fn test_load_cartridge_remaps() {
+        let mut bus = Bus::default();
+
+        bus.write(0xA000, 0x01);
+        assert_eq!(bus.read(0xA000), 0xFF);
+
+        bus.load_cartridge(create_fake_cartridge(Some(0x02))); // 8KB RAM
+
+        bus.write(0xA000, 0x01);
+        bus.write(0xBFFF, 0x02);
+
+        assert_eq!(bus.read(0xA000), 0x01);
+        assert_eq!(bus.read(0xBFFF), 0x02);
+    }
+
+    #[test]
+    fn test_load_rom_remaps() {
+        let mut rom = vec![0; 0x150];
+        rom[0x149] = 0x02; // 8KB RAM
+
+        let mut bus = Bus::default();
+        bus.load_rom(&rom).unwrap();
+
+        bus.write(0xA000, 0x42);
+
+        assert_eq!(bus.read(0xA000), 0x42);
+        assert_eq!(bus.cartridge().ram.size(), 8 * 1024);
     }
 }
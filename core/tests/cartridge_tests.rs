@@ -1,5 +1,7 @@
 #[cfg(test)]
 mod tests {
+    use core::bus::Bus;
+    use core::cartridge::header::{self, CartridgeType, HeaderError, RamSize, NINTENDO_LOGO};
     use core::cartridge::Cartridge;
 
     fn create_fake_cartridge(ram_size: Option<u8>) -> Cartridge {
@@ -22,22 +24,65 @@ mod tests {
         cart.unwrap()
     }
 
+    // A ROM only image with the logo and both checksums in place
+    fn create_valid_rom() -> Vec<u8> {
+        let mut rom = vec![0; 0x8000];
+        rom[0x104..0x134].copy_from_slice(&NINTENDO_LOGO);
+
+        rom[0x14D] = header::header_checksum(&rom);
+
+        let global = header::global_checksum(&rom);
+        rom[0x14E] = (global >> 8) as u8;
+        rom[0x14F] = global as u8;
+
+        rom
+    }
+
     #[test]
     fn test_cartridge_title() {
         let cart = create_fake_cartridge(None);
-        assert_eq!(cart.title, "TEST ROM");
+        assert_eq!(cart.header.title, "TEST ROM");
     }
 
     #[test]
     fn test_cartridge_type() {
         let cart = create_fake_cartridge(None);
-        assert_eq!(cart.cartridge_type, 0x00);
+        assert_eq!(cart.header.cartridge_type, CartridgeType::RomOnly);
     }
 
     #[test]
     fn test_cartridge_ram_size() {
         let cart = create_fake_cartridge(None);
-        assert_eq!(cart.ram_size, 0x01);
+        assert_eq!(cart.header.ram_size, RamSize::Kib2);
         assert_eq!(cart.ram.size(), 2 * 1024);
     }
+
+    #[test]
+    fn test_cartridge_short_rom() {
+        assert!(Cartridge::new(&[0; 0x14f]).is_err());
+        assert!(Cartridge::new(&[]).is_err());
+    }
+
+    #[test]
+    fn test_cartridge_verified() {
+        let mut rom = create_valid_rom();
+        assert!(Cartridge::new_verified(&rom).is_ok());
+
+        let stored = rom[0x14D];
+        rom[0x14D] = stored.wrapping_add(1);
+
+        let err = HeaderError::HeaderChecksum {
+            computed: stored,
+            stored: stored.wrapping_add(1),
+        };
+
+        // Only the verified constructors look at the checksums
+        let cart = Cartridge::new(&rom).unwrap();
+        assert_eq!(cart.verify(), Err(err.clone()));
+        assert_eq!(Cartridge::new_verified(&rom).err(), Some(err.clone()));
+
+        let mut bus = Bus::default();
+        assert_eq!(bus.load_rom_verified(&rom), Err(err));
+        assert!(bus.load_rom(&rom).is_ok());
+    }
 }
@@ -0,0 +1,24 @@
+use core::bus::Bus;
+use core::cartridge::Cartridge;
+
+const BANK: usize = 0x4000;
+
+// Every bank starts with its own 16 bit number so the mapping can be read back
+pub fn create_banked_rom(banks: usize, cartridge_type: u8, rom_size: u8, ram_size: u8) -> Vec<u8> {
+    let mut rom = vec![0; banks * BANK];
+
+    for bank in 0..banks {
+        rom[bank * BANK] = bank as u8;
+        rom[bank * BANK + 1] = (bank >> 8) as u8;
+    }
+
+    rom[0x147] = cartridge_type;
+    rom[0x148] = rom_size;
+    rom[0x149] = ram_size;
+
+    rom
+}
+
+pub fn create_bus(rom: &[u8]) -> Bus {
+    Bus::new(Some(Cartridge::new(rom).unwrap()))
+}
@@ -0,0 +1,231 @@
+#[cfg(test)]
+mod tests {
+    use core::cartridge::header::{
+        self, CartridgeType, CgbSupport, Controller, Destination, Header, HeaderError, Licensee,
+        RamSize, RomSize, NINTENDO_LOGO,
+    };
+
+    // A header that passes every check, with the checksums filled in last
+    fn create_rom(title: &[u8], cgb_flag: u8) -> Vec<u8> {
+        let mut rom = vec![0; 0x8000];
+
+        rom[0x104..0x134].copy_from_slice(&NINTENDO_LOGO);
+        rom[0x134..0x134 + title.len()].copy_from_slice(title);
+        rom[0x143] = cgb_flag;
+        rom[0x147] = 0x03; // MBC1 + RAM + battery
+        rom[0x148] = 0x00; // 32 KiB
+        rom[0x149] = 0x02; // 8 KiB
+        rom[0x14A] = 0x01; // Overseas
+        rom[0x14B] = 0x01; // Nintendo
+        rom[0x14C] = 0x02; // Version
+
+        fix_checksums(&mut rom);
+
+        rom
+    }
+
+    fn fix_checksums(rom: &mut [u8]) {
+        rom[0x14D] = header::header_checksum(rom);
+
+        let global = header::global_checksum(rom);
+        rom[0x14E] = (global >> 8) as u8;
+        rom[0x14F] = global as u8;
+    }
+
+    #[test]
+    fn test_parse() {
+        let rom = create_rom(b"TETRIS", 0x00);
+        let header = Header::parse(&rom).unwrap();
+
+        assert_eq!(header.title, "TETRIS");
+        assert_eq!(header.manufacturer, None);
+        assert_eq!(header.cgb, CgbSupport::None);
+        assert!(!header.sgb);
+        assert_eq!(header.licensee, Licensee::Old(0x01));
+        assert_eq!(header.cartridge_type, CartridgeType::Mbc1RamBattery);
+        assert_eq!(header.rom_size, RomSize::Kib32);
+        assert_eq!(header.ram_size, RamSize::Kib8);
+        assert_eq!(header.destination, Destination::Overseas);
+        assert_eq!(header.version, 0x02);
+
+        assert_eq!(header.verify(&rom), Ok(()));
+    }
+
+    #[test]
+    fn test_full_length_title() {
+        // The last character doubles as the CGB flag
+        let rom = create_rom(b"SIXTEEN CHARS!!!", b'!');
+
+        assert_eq!(Header::parse(&rom).unwrap().title, "SIXTEEN CHARS!!!");
+    }
+
+    #[test]
+    fn test_cgb_title() {
+        let rom = create_rom(b"POKEMON_SLV", 0x80);
+        let header = Header::parse(&rom).unwrap();
+
+        assert_eq!(header.title, "POKEMON_SLV");
+        assert_eq!(header.manufacturer, None);
+        assert_eq!(header.cgb, CgbSupport::Enhanced);
+
+        let rom = create_rom(b"POKEMON_SLVAAXJ", 0xC0);
+        let header = Header::parse(&rom).unwrap();
+
+        assert_eq!(header.title, "POKEMON_SLV");
+        assert_eq!(header.manufacturer.as_deref(), Some("AAXJ"));
+        assert_eq!(header.cgb, CgbSupport::Only);
+    }
+
+    #[test]
+    fn test_cgb_title_without_manufacturer() {
+        let rom = create_rom(b"ZELDA DX GAME", 0x80);
+        let header = Header::parse(&rom).unwrap();
+
+        assert_eq!(header.title, "ZELDA DX GAME");
+        assert_eq!(header.manufacturer, None);
+    }
+
+    #[test]
+    fn test_non_utf8_title() {
+        let rom = create_rom(&[b'A', 0xFF, b'B'], 0x00);
+
+        assert_eq!(Header::parse(&rom).unwrap().title, "A\u{FFFD}B");
+    }
+
+    #[test]
+    fn test_new_licensee_and_sgb() {
+        let mut rom = create_rom(b"SGB", 0x00);
+        rom[0x144] = b'0';
+        rom[0x145] = b'1';
+        rom[0x146] = 0x03;
+        rom[0x14B] = 0x33;
+
+        let header = Header::parse(&rom).unwrap();
+
+        assert_eq!(header.licensee, Licensee::New(*b"01"));
+        assert!(header.sgb);
+    }
+
+    #[test]
+    fn test_too_short() {
+        assert_eq!(
+            Header::parse(&[0; 0x14F]),
+            Err(HeaderError::TooShort { len: 0x14F })
+        );
+        assert_eq!(Header::parse(&[]), Err(HeaderError::TooShort { len: 0 }));
+    }
+
+    #[test]
+    fn test_unknown_codes() {
+        let mut rom = create_rom(b"", 0x00);
+        rom[0x147] = 0x04;
+        assert_eq!(
+            Header::parse(&rom),
+            Err(HeaderError::UnknownCartridgeType(0x04))
+        );
+
+        let mut rom = create_rom(b"", 0x00);
+        rom[0x148] = 0x09;
+        assert_eq!(Header::parse(&rom), Err(HeaderError::UnknownRomSize(0x09)));
+
+        let mut rom = create_rom(b"", 0x00);
+        rom[0x149] = 0x06;
+        assert_eq!(Header::parse(&rom), Err(HeaderError::UnknownRamSize(0x06)));
+    }
+
+    #[test]
+    fn test_bad_logo() {
+        let mut rom = create_rom(b"", 0x00);
+        rom[0x104] = 0x00;
+
+        let header = Header::parse(&rom).unwrap();
+
+        assert_eq!(header.verify(&rom), Err(HeaderError::BadLogo));
+    }
+
+    #[test]
+    fn test_header_checksum() {
+        let mut rom = create_rom(b"", 0x00);
+        let stored = rom[0x14D];
+        rom[0x14D] = stored.wrapping_add(1);
+
+        let header = Header::parse(&rom).unwrap();
+
+        assert_eq!(
+            header.verify(&rom),
+            Err(HeaderError::HeaderChecksum {
+                computed: stored,
+                stored: stored.wrapping_add(1)
+            })
+        );
+    }
+
+    #[test]
+    fn test_global_checksum() {
+        let mut rom = create_rom(b"", 0x00);
+        let computed = header::global_checksum(&rom);
+        rom[0x7FFF] = 0x01;
+
+        let header = Header::parse(&rom).unwrap();
+
+        assert_eq!(
+            header.verify(&rom),
+            Err(HeaderError::GlobalChecksum {
+                computed: computed + 1,
+                stored: computed
+            })
+        );
+        assert_eq!(
+            header.verify(&rom).unwrap_err().to_string(),
+            format!(
+                "Global checksum mismatch: computed {:#06X}, stored {:#06X}",
+                computed + 1,
+                computed
+            )
+        );
+    }
+
+    #[test]
+    fn test_cpu_instrs() {
+        let rom = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tools/cpu_instrs.gb"
+        ))
+        .unwrap();
+
+        let header = Header::parse(&rom).unwrap();
+
+        assert_eq!(header.title, "CPU_INSTRS");
+        assert_eq!(header.cartridge_type, CartridgeType::Mbc1);
+        assert_eq!(header.rom_size.bytes(), rom.len());
+
+        // The boot ROM checks pass, the unchecked global checksum was never fixed up
+        assert_eq!(
+            header.verify(&rom),
+            Err(HeaderError::GlobalChecksum {
+                computed: 0xB171,
+                stored: 0xF530
+            })
+        );
+    }
+
+    #[test]
+    fn test_cartridge_type_features() {
+        let mbc3 = CartridgeType::from_code(0x10).unwrap();
+
+        assert_eq!(mbc3.controller(), Controller::Mbc3);
+        assert!(mbc3.has_ram());
+        assert!(mbc3.has_battery());
+        assert!(mbc3.has_timer());
+        assert!(!mbc3.has_rumble());
+
+        let mbc5 = CartridgeType::from_code(0x1C).unwrap();
+
+        assert_eq!(mbc5.controller(), Controller::Mbc5);
+        assert!(!mbc5.has_ram());
+        assert!(mbc5.has_rumble());
+
+        assert_eq!(RomSize::from_code(0x05).unwrap().banks(), 64);
+        assert_eq!(RamSize::from_code(0x05).unwrap().bytes(), 64 * 1024);
+    }
+}
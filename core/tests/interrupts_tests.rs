@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use core::bus::Bus;
+    use core::interrupts::{Interrupt, Interrupts};
+
+    #[test]
+    fn test_request_acknowledge() {
+        let mut interrupts = Interrupts::new();
+
+        interrupts.request(Interrupt::Timer);
+        assert_eq!(interrupts.flag, 0x04);
+
+        interrupts.request(Interrupt::VBlank);
+        assert_eq!(interrupts.flag, 0x05);
+
+        interrupts.acknowledge(Interrupt::Timer);
+        assert_eq!(interrupts.flag, 0x01);
+    }
+
+    #[test]
+    fn test_priority() {
+        let mut interrupts = Interrupts::new();
+        interrupts.enable = 0x1f;
+
+        assert_eq!(interrupts.highest_pending(), None);
+
+        interrupts.request(Interrupt::Joypad);
+        interrupts.request(Interrupt::Serial);
+        assert_eq!(interrupts.highest_pending(), Some(Interrupt::Serial));
+
+        interrupts.request(Interrupt::Stat);
+        assert_eq!(interrupts.highest_pending(), Some(Interrupt::Stat));
+
+        interrupts.enable = 0x10;
+        assert_eq!(interrupts.highest_pending(), Some(Interrupt::Joypad));
+    }
+
+    #[test]
+    fn test_bus_registers() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFFFF, 0x1f);
+        bus.write(0xFF0F, 0xff);
+
+        assert_eq!(bus.interrupts.enable, 0x1f);
+        assert_eq!(bus.interrupts.flag, 0x1f);
+        assert_eq!(bus.read(0xFFFF), 0x1f);
+        assert_eq!(bus.read(0xFF0F), 0xff);
+
+        bus.write(0xFF0F, 0x00);
+        assert_eq!(bus.read(0xFF0F), 0xe0);
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use core::bus::Bus;
+    use core::io::{lookup, lookup_name, Device, REGISTERS};
+
+    #[test]
+    fn test_registers_unique() {
+        for (i, reg) in REGISTERS.iter().enumerate() {
+            assert!((0xFF00..0xFF80).contains(&reg.addr), "{}", reg);
+            assert_eq!(lookup(reg.addr), Some(reg));
+
+            for other in &REGISTERS[i + 1..] {
+                assert_ne!(reg.addr, other.addr, "{} {}", reg, other);
+                assert_ne!(reg.name, other.name, "{} {}", reg, other);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        let tac = lookup(0xFF07).unwrap();
+
+        assert_eq!(tac.name, "TAC");
+        assert_eq!(tac.device, Device::Timer);
+        assert_eq!(tac.to_string(), "TAC (FF07)");
+
+        assert_eq!(lookup_name("LY").unwrap().addr, 0xFF44);
+        assert_eq!(lookup(0xFF03), None);
+        assert_eq!(lookup(0xFF80), None);
+        assert_eq!(lookup(0x0000), None);
+    }
+
+    #[test]
+    fn test_unmapped() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFF03, 0x00);
+        bus.write(0xFF7F, 0x00);
+
+        assert_eq!(bus.read(0xFF03), 0xFF);
+        assert_eq!(bus.read(0xFF7F), 0xFF);
+    }
+
+    #[test]
+    fn test_unused_bits_read_as_1() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFF07, 0x05); // TAC
+
+        assert_eq!(bus.read(0xFF07), 0xFD);
+
+        bus.write(0xFF0F, 0x01); // IF
+
+        assert_eq!(bus.read(0xFF0F), 0xE1);
+        assert_eq!(bus.interrupts.flag, 0x01);
+    }
+
+    #[test]
+    fn test_write_only() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFF13, 0x12); // NR13
+
+        assert_eq!(bus.read(0xFF13), 0xFF);
+        assert_eq!(bus.io.read(0xFF13), 0x12);
+    }
+
+    #[test]
+    fn test_read_only() {
+        let mut bus = Bus::default();
+
+        bus.io.write(0xFF41, 0x03); // STAT mode bits
+        bus.io.write(0xFF44, 0x90); // LY
+
+        bus.write(0xFF41, 0xFC);
+        bus.write(0xFF44, 0x00);
+
+        assert_eq!(bus.read(0xFF41), 0xFB);
+        assert_eq!(bus.read(0xFF44), 0x90);
+    }
+
+    #[test]
+    fn test_read_write() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFF42, 0x12); // SCY
+        bus.write(0xFF30, 0x34); // WAVE0
+
+        assert_eq!(bus.read(0xFF42), 0x12);
+        assert_eq!(bus.read(0xFF30), 0x34);
+    }
+
+    #[test]
+    fn test_cgb_registers_on_dmg() {
+        let mut bus = Bus::default();
+
+        bus.write(0xFF4D, 0x01); // KEY1
+        bus.write(0xFF70, 0x02); // SVBK
+
+        assert_eq!(bus.read(0xFF4D), 0xFF);
+        assert_eq!(bus.read(0xFF70), 0xFF);
+
+        bus.cgb = true;
+        bus.write(0xFF4D, 0x01);
+        bus.write(0xFF70, 0x02);
+
+        assert_eq!(bus.read(0xFF4D), 0x7F);
+        assert_eq!(bus.read(0xFF70), 0xFA);
+    }
+}
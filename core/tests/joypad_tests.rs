@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests {
+    use core::bus::Bus;
+    use core::cpu::sm83::{CpuState, SM83};
+    use core::interrupts::Interrupt;
+    use core::joypad::Button;
+
+    const P1: u16 = 0xFF00;
+
+    const DIRECTIONS: u8 = 0x20;
+    const ACTIONS: u8 = 0x10;
+    const NONE: u8 = 0x30;
+    const BOTH: u8 = 0x00;
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    fn joypad_requested(bus: &Bus) -> bool {
+        bus.interrupts.flag & Interrupt::Joypad.bit() != 0
+    }
+
+    #[test]
+    fn test_post_boot() {
+        let bus = Bus::default();
+
+        assert_eq!(bus.read(P1), 0xCF);
+    }
+
+    #[test]
+    fn test_select_lines() {
+        let mut bus = Bus::default();
+
+        bus.set_button(Button::Down, true);
+        bus.set_button(Button::A, true);
+        bus.set_button(Button::Start, true);
+
+        bus.write(P1, DIRECTIONS);
+        assert_eq!(bus.read(P1), 0xE7);
+
+        bus.write(P1, ACTIONS);
+        assert_eq!(bus.read(P1), 0xD6);
+
+        // Both groups pull the shared lines low
+        bus.write(P1, BOTH);
+        assert_eq!(bus.read(P1), 0xC6);
+
+        bus.write(P1, NONE);
+        assert_eq!(bus.read(P1), 0xFF);
+    }
+
+    #[test]
+    fn test_lower_bits_read_only() {
+        let mut bus = Bus::default();
+
+        bus.write(P1, 0xC0 | DIRECTIONS);
+
+        assert_eq!(bus.read(P1), 0xEF);
+    }
+
+    #[test]
+    fn test_release() {
+        let mut bus = Bus::default();
+        bus.write(P1, DIRECTIONS);
+
+        bus.set_button(Button::Left, true);
+        assert_eq!(bus.read(P1), 0xED);
+        assert!(bus.joypad.pressed(Button::Left));
+
+        bus.set_button(Button::Left, false);
+        assert_eq!(bus.read(P1), 0xEF);
+        assert!(!bus.joypad.pressed(Button::Left));
+    }
+
+    #[test]
+    fn test_interrupt_on_press() {
+        let mut bus = Bus::default();
+        bus.write(P1, DIRECTIONS);
+
+        bus.set_button(Button::Up, true);
+        assert!(joypad_requested(&bus));
+
+        // Releasing is a low to high transition
+        bus.interrupts.flag = 0;
+        bus.set_button(Button::Up, false);
+        assert!(!joypad_requested(&bus));
+    }
+
+    #[test]
+    fn test_no_interrupt_for_unselected_group() {
+        let mut bus = Bus::default();
+        bus.write(P1, DIRECTIONS);
+
+        bus.set_button(Button::B, true);
+        assert!(!joypad_requested(&bus));
+    }
+
+    #[test]
+    fn test_no_interrupt_for_held_line() {
+        let mut bus = Bus::default();
+        bus.write(P1, BOTH);
+
+        bus.set_button(Button::Right, true);
+        bus.interrupts.flag = 0;
+
+        // A shares its line with Right, which is already low
+        bus.set_button(Button::A, true);
+        assert!(!joypad_requested(&bus));
+    }
+
+    #[test]
+    fn test_interrupt_on_select() {
+        let mut bus = Bus::default();
+        bus.write(P1, NONE);
+
+        bus.set_button(Button::Select, true);
+        assert!(!joypad_requested(&bus));
+
+        // Selecting a group with a held button pulls its line low
+        bus.write(P1, ACTIONS);
+        assert!(joypad_requested(&bus));
+    }
+
+    #[test]
+    fn test_set_at() {
+        let mut bus = Bus::default();
+        bus.write(P1, ACTIONS);
+
+        bus.set_button_at(100, Button::Start, true);
+        bus.set_button_at(200, Button::Start, false);
+        bus.set_button_at(100, Button::A, true);
+
+        bus.tick(96);
+        assert_eq!(bus.read(P1), 0xDF);
+
+        bus.tick(4);
+        assert_eq!(bus.read(P1), 0xD6);
+        assert!(joypad_requested(&bus));
+
+        bus.tick(100);
+        assert_eq!(bus.read(P1), 0xDE);
+        assert_eq!(bus.cycles_to_next_event(), None);
+    }
+
+    #[test]
+    fn test_interrupt_wakes_halt() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x76, // HALT
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+        cpu.bus.interrupts.write_enable(Interrupt::Joypad.bit());
+        cpu.bus.set_button_at(1000, Button::B, true);
+
+        // Sleeps straight to the input
+        let elapsed = cpu.run_until(|cpu| cpu.pc == 0x102).unwrap();
+
+        assert_eq!(elapsed, 1000 + 4);
+        assert_eq!(cpu.state, CpuState::Running);
+    }
+
+    #[test]
+    fn test_button_wakes_stop() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x10, // STOP
+            0x00, // padding
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+        cpu.run_for(100).unwrap();
+
+        assert_eq!(cpu.state, CpuState::Stopped);
+        assert_eq!(cpu.pc, 0x102);
+
+        cpu.bus.set_button(Button::Down, true);
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Running);
+        assert_eq!(cpu.pc, 0x103);
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use core::bus::Bus;
+    use core::cpu::sm83::SM83;
+    use core::logger::{self, Category, Level, Record, Sink};
+    use std::sync::{Arc, Mutex};
+
+    // The sink and levels are global, every test in this file goes through this lock
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    impl Sink for Capture {
+        fn log(&self, record: &Record) {
+            self.0.lock().unwrap().push(format!(
+                "[{} {}] {}",
+                record.level, record.category, record.args
+            ));
+        }
+    }
+
+    fn capture() -> Arc<Mutex<Vec<String>>> {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+
+        logger::disable_all();
+        logger::set_sink(Box::new(Capture(lines.clone())));
+
+        lines
+    }
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let _lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let lines = capture();
+
+        let mut bus = Bus::default();
+        bus.write(0xC000, 0x01);
+        bus.write(0xFF03, 0x02);
+
+        for category in Category::ALL {
+            assert!(!logger::enabled(category, Level::Error));
+        }
+
+        assert!(lines.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_level_filter() {
+        let _lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let _lines = capture();
+
+        logger::enable(Category::Io, Level::Info);
+
+        assert!(logger::enabled(Category::Io, Level::Error));
+        assert!(logger::enabled(Category::Io, Level::Info));
+        assert!(!logger::enabled(Category::Io, Level::Debug));
+        assert!(!logger::enabled(Category::Memory, Level::Error));
+
+        logger::disable(Category::Io);
+
+        assert!(!logger::enabled(Category::Io, Level::Error));
+
+        logger::enable_all(Level::Trace);
+
+        for category in Category::ALL {
+            assert!(logger::enabled(category, Level::Trace));
+        }
+
+        logger::disable_all();
+    }
+
+    #[test]
+    fn test_categories() {
+        let _lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let lines = capture();
+
+        logger::enable(Category::Io, Level::Debug);
+        logger::enable(Category::Mbc, Level::Debug);
+
+        let mut bus = Bus::default();
+        bus.write(0xC000, 0x01); // memory, not enabled
+        bus.write(0xFF03, 0x02); // unmapped register
+        bus.write(0x2000, 0x03); // bank controller
+
+        logger::disable_all();
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                "[DEBUG io] Write 02 to unmapped register FF03",
+                "[DEBUG mbc] Write 03 to 2000",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_memory() {
+        let _lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let lines = capture();
+
+        logger::enable(Category::Memory, Level::Trace);
+
+        let mut bus = Bus::default();
+        bus.write(0xC000, 0x01);
+        bus.read(0xC000);
+
+        logger::disable_all();
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                "[TRACE memory] Write 01 to C000",
+                "[TRACE memory] Read 01 from C000",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_register_categories() {
+        let _lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let lines = capture();
+
+        logger::enable(Category::Io, Level::Trace);
+        logger::enable(Category::Ppu, Level::Trace);
+        logger::enable(Category::Apu, Level::Trace);
+
+        let mut bus = Bus::default();
+        bus.write(0xFF0F, 0x01);
+        bus.write(0xFF40, 0x91);
+        bus.write(0xFF26, 0x80);
+
+        logger::disable_all();
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                "[TRACE io] Write 01 to IF (FF0F)",
+                "[TRACE ppu] Write 91 to LCDC (FF40)",
+                "[TRACE apu] Write 80 to NR52 (FF26)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cpu() {
+        let _lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let lines = capture();
+
+        logger::enable(Category::Cpu, Level::Error);
+
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x00, // NOP
+            0xdd, // illegal
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+        assert!(cpu.step().is_err());
+
+        logger::disable_all();
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec!["[ERROR cpu] Illegal opcode DD at 0101"]
+        );
+    }
+}
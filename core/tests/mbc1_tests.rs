@@ -0,0 +1,171 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{create_banked_rom, create_bus};
+    use core::cartridge::header::NINTENDO_LOGO;
+
+    const BANK: usize = 0x4000;
+
+    #[test]
+    fn test_default_banks() {
+        let bus = create_bus(&create_banked_rom(8, 0x03, 0x02, 0x00));
+
+        assert_eq!(bus.read(0x0000), 0);
+        assert_eq!(bus.read(0x4000), 1);
+    }
+
+    #[test]
+    fn test_rom_bank_switch() {
+        let mut bus = create_bus(&create_banked_rom(8, 0x03, 0x02, 0x00));
+
+        bus.write(0x2000, 0x05);
+        assert_eq!(bus.read(0x4000), 5);
+
+        // The whole 0x2000-0x3FFF range decodes to the same register
+        bus.write(0x3FFF, 0x03);
+        assert_eq!(bus.read(0x4000), 3);
+    }
+
+    #[test]
+    fn test_rom_bank_zero_selects_one() {
+        let mut bus = create_bus(&create_banked_rom(64, 0x03, 0x05, 0x00));
+
+        bus.write(0x2000, 0x00);
+        assert_eq!(bus.read(0x4000), 1);
+
+        // Only the low five bits are checked, so 0x20 is bank 0x21 and not 0x20
+        bus.write(0x4000, 0x01);
+        bus.write(0x2000, 0x00);
+        assert_eq!(bus.read(0x4000), 0x21);
+
+        // On a ROM with only 16 banks 0x10 passes the zero check and wraps to bank 0
+        let mut bus = create_bus(&create_banked_rom(16, 0x03, 0x03, 0x00));
+        bus.write(0x2000, 0x10);
+        assert_eq!(bus.read(0x4000), 0);
+    }
+
+    #[test]
+    fn test_rom_bank_masked_by_size() {
+        let mut bus = create_bus(&create_banked_rom(8, 0x03, 0x02, 0x00));
+
+        bus.write(0x2000, 0x1D);
+        assert_eq!(bus.read(0x4000), 5);
+
+        // The upper bits are not connected on a 128 KiB ROM
+        bus.write(0x4000, 0x03);
+        assert_eq!(bus.read(0x4000), 5);
+    }
+
+    #[test]
+    fn test_large_rom_upper_bits() {
+        let mut bus = create_bus(&create_banked_rom(128, 0x03, 0x06, 0x00));
+
+        bus.write(0x2000, 0x02);
+        bus.write(0x4000, 0x03);
+        assert_eq!(bus.read(0x4000), 0x62);
+
+        // Mode 0 keeps bank 0 at 0x0000-0x3FFF
+        assert_eq!(bus.read(0x0000), 0);
+    }
+
+    #[test]
+    fn test_mode1_remaps_bank0() {
+        let mut bus = create_bus(&create_banked_rom(128, 0x03, 0x06, 0x00));
+
+        bus.write(0x4000, 0x02);
+        bus.write(0x6000, 0x01);
+        assert_eq!(bus.read(0x0000), 0x40);
+        assert_eq!(bus.read(0x4000), 0x41);
+
+        bus.write(0x6000, 0x00);
+        assert_eq!(bus.read(0x0000), 0);
+    }
+
+    #[test]
+    fn test_ram_enable() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x03, 0x01, 0x02));
+
+        // Disabled RAM ignores writes and reads as open bus
+        bus.write(0xA000, 0x12);
+        assert_eq!(bus.read(0xA000), 0xFF);
+
+        bus.write(0x0000, 0x0A);
+        bus.write(0xA000, 0x12);
+        assert_eq!(bus.read(0xA000), 0x12);
+
+        // Only the low nibble has to be 0xA
+        bus.write(0x1FFF, 0xFA);
+        assert_eq!(bus.read(0xA000), 0x12);
+
+        bus.write(0x0000, 0x00);
+        assert_eq!(bus.read(0xA000), 0xFF);
+    }
+
+    #[test]
+    fn test_ram_banking() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x03, 0x01, 0x03));
+        bus.write(0x0000, 0x0A);
+
+        bus.write(0xA000, 0x10);
+
+        // Mode 0 always uses RAM bank 0
+        bus.write(0x4000, 0x02);
+        assert_eq!(bus.read(0xA000), 0x10);
+
+        bus.write(0x6000, 0x01);
+        bus.write(0xA000, 0x12);
+        assert_eq!(bus.read(0xA000), 0x12);
+
+        bus.write(0x4000, 0x00);
+        assert_eq!(bus.read(0xA000), 0x10);
+        assert_eq!(bus.cartridge().ram.read(0x4000), 0x12);
+    }
+
+    #[test]
+    fn test_small_ram_mirrors() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x03, 0x01, 0x01));
+        bus.write(0x0000, 0x0A);
+
+        bus.write(0xA000, 0x34);
+        assert_eq!(bus.read(0xA800), 0x34);
+        assert_eq!(bus.read(0xB800), 0x34);
+    }
+
+    #[test]
+    fn test_multicart() {
+        let mut rom = create_banked_rom(64, 0x03, 0x05, 0x00);
+
+        for game in 0..4 {
+            let logo = game * 0x10 * BANK + 0x104;
+            rom[logo..logo + NINTENDO_LOGO.len()].copy_from_slice(&NINTENDO_LOGO);
+        }
+
+        let mut bus = create_bus(&rom);
+
+        // bank2 sits one bit lower and bank1 loses its top bit
+        bus.write(0x4000, 0x01);
+        bus.write(0x2000, 0x13);
+        assert_eq!(bus.read(0x4000), 0x13);
+
+        bus.write(0x4000, 0x02);
+        bus.write(0x2000, 0x05);
+        assert_eq!(bus.read(0x4000), 0x25);
+
+        // Mode 1 boots the other games from 0x0000
+        bus.write(0x6000, 0x01);
+        bus.write(0x4000, 0x03);
+        assert_eq!(bus.read(0x0000), 0x30);
+    }
+
+    #[test]
+    fn test_plain_1mib_rom_is_not_multicart() {
+        let mut rom = create_banked_rom(64, 0x03, 0x05, 0x00);
+        rom[0x104..0x134].copy_from_slice(&NINTENDO_LOGO);
+
+        let mut bus = create_bus(&rom);
+        bus.write(0x4000, 0x01);
+        bus.write(0x2000, 0x13);
+        assert_eq!(bus.read(0x4000), 0x33);
+    }
+}
@@ -0,0 +1,117 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{create_banked_rom, create_bus};
+    use core::bus::Bus;
+    use core::cartridge::Cartridge;
+
+    // MBC2 + battery, the header lists no RAM because it is inside the controller
+    fn create_mbc2_bus() -> Bus {
+        create_bus(&create_banked_rom(16, 0x06, 0x03, 0x00))
+    }
+
+    #[test]
+    fn test_built_in_ram() {
+        let cart = Cartridge::new(&create_banked_rom(16, 0x06, 0x03, 0x00)).unwrap();
+
+        assert_eq!(cart.ram.size(), 512);
+    }
+
+    #[test]
+    fn test_rom_bank_switch() {
+        let mut bus = create_mbc2_bus();
+
+        assert_eq!(bus.read(0x4000), 1);
+
+        bus.write(0x2100, 0x05);
+        assert_eq!(bus.read(0x4000), 5);
+
+        bus.write(0x0100, 0x00);
+        assert_eq!(bus.read(0x4000), 1);
+
+        // Only the low nibble counts
+        bus.write(0x3FFF, 0xFE);
+        assert_eq!(bus.read(0x4000), 0x0E);
+        assert_eq!(bus.read(0x0000), 0);
+    }
+
+    #[test]
+    fn test_rom_bank_masked_by_size() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x05, 0x01, 0x00));
+
+        bus.write(0x2100, 0x07);
+        assert_eq!(bus.read(0x4000), 3);
+    }
+
+    #[test]
+    fn test_register_select() {
+        let mut bus = create_mbc2_bus();
+
+        // Address bit 8 clear goes to the RAM enable, even in the upper half
+        bus.write(0x2000, 0x0A);
+        assert_eq!(bus.read(0x4000), 1);
+
+        bus.write(0xA000, 0x03);
+        assert_eq!(bus.read(0xA000), 0xF3);
+
+        // Address bit 8 set never touches the RAM enable
+        bus.write(0x0100, 0x00);
+        assert_eq!(bus.read(0xA000), 0xF3);
+
+        bus.write(0x1E00, 0x00);
+        assert_eq!(bus.read(0xA000), 0xFF);
+    }
+
+    #[test]
+    fn test_ram_nibbles() {
+        let mut bus = create_mbc2_bus();
+        bus.write(0x0000, 0x0A);
+
+        bus.write(0xA010, 0x5C);
+
+        assert_eq!(bus.read(0xA010), 0xFC);
+        assert_eq!(bus.cartridge().ram.read(0x010), 0x0C);
+    }
+
+    #[test]
+    fn test_ram_echo() {
+        let mut bus = create_mbc2_bus();
+        bus.write(0x0000, 0x0A);
+
+        bus.write(0xA1FF, 0x07);
+        bus.write(0xA000, 0x09);
+
+        assert_eq!(bus.read(0xA3FF), 0xF7);
+        assert_eq!(bus.read(0xBFFF), 0xF7);
+        assert_eq!(bus.read(0xBE00), 0xF9);
+
+        bus.write(0xB200, 0x01);
+        assert_eq!(bus.read(0xA000), 0xF1);
+    }
+
+    #[test]
+    fn test_battery_ram() {
+        let mut bus = create_mbc2_bus();
+        bus.write(0x0000, 0x0A);
+        bus.write(0xA005, 0x0B);
+
+        let save = bus.cartridge().battery_ram().unwrap().to_vec();
+        assert_eq!(save.len(), 512);
+        assert_eq!(save[5], 0x0B);
+
+        let mut cart = Cartridge::new(&create_banked_rom(16, 0x06, 0x03, 0x00)).unwrap();
+        cart.load_battery_ram(&save);
+
+        let mut bus = Bus::new(Some(cart));
+        bus.write(0x0000, 0x0A);
+        assert_eq!(bus.read(0xA005), 0xFB);
+    }
+
+    #[test]
+    fn test_no_battery() {
+        let cart = Cartridge::new(&create_banked_rom(16, 0x05, 0x03, 0x00)).unwrap();
+
+        assert!(cart.battery_ram().is_none());
+    }
+}
@@ -0,0 +1,259 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{create_banked_rom, create_bus};
+    use core::bus::Bus;
+    use core::cartridge::rtc::{ClockSource, RTC_DH, RTC_DL, RTC_H, RTC_M, RTC_S};
+
+    const SECOND: usize = 4_194_304;
+
+    // MBC3 + timer + RAM + battery with 32 KiB of RAM
+    fn create_rtc_bus() -> Bus {
+        let mut bus = create_bus(&create_banked_rom(4, 0x10, 0x01, 0x03));
+        bus.write(0x0000, 0x0A);
+        bus
+    }
+
+    fn latch(bus: &mut Bus) {
+        bus.write(0x6000, 0x00);
+        bus.write(0x6000, 0x01);
+    }
+
+    fn read_rtc(bus: &mut Bus, reg: u8) -> u8 {
+        bus.write(0x4000, reg);
+        bus.read(0xA000)
+    }
+
+    fn write_rtc(bus: &mut Bus, reg: u8, data: u8) {
+        bus.write(0x4000, reg);
+        bus.write(0xA000, data);
+    }
+
+    #[test]
+    fn test_rom_bank_switch() {
+        let mut bus = create_bus(&create_banked_rom(128, 0x11, 0x06, 0x00));
+
+        assert_eq!(bus.read(0x4000), 1);
+
+        bus.write(0x2000, 0x7F);
+        assert_eq!(bus.read(0x4000), 0x7F);
+        assert_eq!(bus.read(0x0000), 0);
+
+        bus.write(0x2000, 0x00);
+        assert_eq!(bus.read(0x4000), 1);
+
+        // Only seven bits on a plain MBC3
+        bus.write(0x2000, 0x85);
+        assert_eq!(bus.read(0x4000), 0x05);
+    }
+
+    #[test]
+    fn test_mbc30_rom_bank() {
+        let mut bus = create_bus(&create_banked_rom(256, 0x11, 0x07, 0x00));
+
+        bus.write(0x2000, 0x85);
+        assert_eq!(bus.read(0x4000), 0x85);
+    }
+
+    #[test]
+    fn test_ram_banks() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x13, 0x01, 0x03));
+        bus.write(0x0000, 0x0A);
+
+        for bank in 0..4 {
+            bus.write(0x4000, bank);
+            bus.write(0xA000, 0x10 + bank);
+        }
+
+        for bank in 0..4 {
+            bus.write(0x4000, bank);
+            assert_eq!(bus.read(0xA000), 0x10 + bank);
+            assert_eq!(
+                bus.cartridge().ram.read(bank as usize * 0x2000),
+                0x10 + bank
+            );
+        }
+
+        bus.write(0x0000, 0x00);
+        assert_eq!(bus.read(0xA000), 0xFF);
+    }
+
+    #[test]
+    fn test_mbc30_ram_banks() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x13, 0x01, 0x05));
+        bus.write(0x0000, 0x0A);
+
+        bus.write(0x4000, 0x07);
+        bus.write(0xA000, 0x77);
+
+        assert_eq!(bus.cartridge().ram.read(7 * 0x2000), 0x77);
+    }
+
+    #[test]
+    fn test_rtc_needs_timer() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x13, 0x01, 0x03));
+        bus.write(0x0000, 0x0A);
+
+        assert_eq!(read_rtc(&mut bus, RTC_S), 0xFF);
+        assert!(bus.rtc().is_none());
+    }
+
+    #[test]
+    fn test_rtc_latch() {
+        let mut bus = create_rtc_bus();
+
+        bus.tick(3 * SECOND);
+        assert_eq!(read_rtc(&mut bus, RTC_S), 0);
+
+        latch(&mut bus);
+        assert_eq!(read_rtc(&mut bus, RTC_S), 3);
+
+        // The latched copy holds still while the clock keeps running
+        bus.tick(2 * SECOND);
+        assert_eq!(read_rtc(&mut bus, RTC_S), 3);
+
+        // Writing 0x01 again without the 0x00 first does not latch
+        bus.write(0x6000, 0x01);
+        assert_eq!(read_rtc(&mut bus, RTC_S), 3);
+
+        latch(&mut bus);
+        assert_eq!(read_rtc(&mut bus, RTC_S), 5);
+    }
+
+    #[test]
+    fn test_rtc_counts_time() {
+        let mut bus = create_rtc_bus();
+
+        bus.tick(((24 + 2) * 3600 + 3 * 60 + 4) * SECOND + SECOND / 2);
+        latch(&mut bus);
+
+        assert_eq!(read_rtc(&mut bus, RTC_S), 4);
+        assert_eq!(read_rtc(&mut bus, RTC_M), 3);
+        assert_eq!(read_rtc(&mut bus, RTC_H), 2);
+        assert_eq!(read_rtc(&mut bus, RTC_DL), 1);
+        assert_eq!(read_rtc(&mut bus, RTC_DH), 0);
+
+        // The half second carries over to the next latch
+        bus.tick(SECOND / 2);
+        latch(&mut bus);
+        assert_eq!(read_rtc(&mut bus, RTC_S), 5);
+    }
+
+    #[test]
+    fn test_rtc_write() {
+        let mut bus = create_rtc_bus();
+
+        write_rtc(&mut bus, RTC_H, 0x17);
+        write_rtc(&mut bus, RTC_DL, 0x34);
+        write_rtc(&mut bus, RTC_DH, 0x01);
+
+        assert_eq!(read_rtc(&mut bus, RTC_H), 0x17);
+        assert_eq!(read_rtc(&mut bus, RTC_DL), 0x34);
+        assert_eq!(read_rtc(&mut bus, RTC_DH), 0x01);
+
+        let rtc = bus.rtc().unwrap();
+        assert_eq!(rtc.hours, 0x17);
+        assert_eq!(rtc.days, 0x134);
+    }
+
+    #[test]
+    fn test_rtc_write_seconds_resets_divider() {
+        let mut bus = create_rtc_bus();
+
+        bus.tick(SECOND - 4);
+        write_rtc(&mut bus, RTC_S, 10);
+
+        bus.tick(SECOND - 4);
+        latch(&mut bus);
+        assert_eq!(read_rtc(&mut bus, RTC_S), 10);
+
+        bus.tick(4);
+        latch(&mut bus);
+        assert_eq!(read_rtc(&mut bus, RTC_S), 11);
+    }
+
+    #[test]
+    fn test_rtc_halt() {
+        let mut bus = create_rtc_bus();
+
+        write_rtc(&mut bus, RTC_DH, 0x40);
+        bus.tick(10 * SECOND);
+        latch(&mut bus);
+
+        assert_eq!(read_rtc(&mut bus, RTC_S), 0);
+        assert_eq!(read_rtc(&mut bus, RTC_DH), 0x40);
+
+        write_rtc(&mut bus, RTC_DH, 0x00);
+        bus.tick(2 * SECOND);
+        latch(&mut bus);
+
+        assert_eq!(read_rtc(&mut bus, RTC_S), 2);
+    }
+
+    #[test]
+    fn test_rtc_day_carry() {
+        let mut bus = create_rtc_bus();
+
+        write_rtc(&mut bus, RTC_S, 59);
+        write_rtc(&mut bus, RTC_M, 59);
+        write_rtc(&mut bus, RTC_H, 23);
+        write_rtc(&mut bus, RTC_DL, 0xFF);
+        write_rtc(&mut bus, RTC_DH, 0x01);
+
+        bus.tick(SECOND);
+        latch(&mut bus);
+
+        assert_eq!(read_rtc(&mut bus, RTC_S), 0);
+        assert_eq!(read_rtc(&mut bus, RTC_H), 0);
+        assert_eq!(read_rtc(&mut bus, RTC_DL), 0);
+        assert_eq!(read_rtc(&mut bus, RTC_DH), 0x80);
+
+        // The carry sticks until it is cleared
+        bus.tick(SECOND);
+        latch(&mut bus);
+        assert_eq!(read_rtc(&mut bus, RTC_DH), 0x80);
+
+        write_rtc(&mut bus, RTC_DH, 0x00);
+        assert_eq!(read_rtc(&mut bus, RTC_DH), 0x00);
+    }
+
+    #[test]
+    fn test_rtc_out_of_range() {
+        let mut bus = create_rtc_bus();
+
+        // Seconds set past 59 count up to 63 and wrap without a minute carry
+        write_rtc(&mut bus, RTC_S, 62);
+        bus.tick(2 * SECOND);
+        latch(&mut bus);
+
+        assert_eq!(read_rtc(&mut bus, RTC_S), 0);
+        assert_eq!(read_rtc(&mut bus, RTC_M), 0);
+
+        bus.tick(SECOND);
+        latch(&mut bus);
+        assert_eq!(read_rtc(&mut bus, RTC_S), 1);
+    }
+
+    #[test]
+    fn test_rtc_host_source() {
+        let mut bus = create_rtc_bus();
+        let now = bus.cycles;
+
+        bus.rtc().unwrap().set_source(ClockSource::Host, now);
+        assert_eq!(bus.rtc().unwrap().source(), ClockSource::Host);
+
+        // Emulated time no longer moves the clock
+        bus.tick(100 * SECOND);
+        latch(&mut bus);
+        assert!(read_rtc(&mut bus, RTC_S) < 2);
+    }
+
+    #[test]
+    fn test_rtc_disabled() {
+        let mut bus = create_rtc_bus();
+        bus.write(0x0000, 0x00);
+
+        assert_eq!(read_rtc(&mut bus, RTC_S), 0xFF);
+    }
+}
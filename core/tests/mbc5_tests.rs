@@ -0,0 +1,125 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{create_banked_rom, create_bus};
+    use core::bus::Bus;
+    use std::sync::{Arc, Mutex};
+
+    fn read_bank(bus: &Bus) -> u16 {
+        bus.read_word(0x4000)
+    }
+
+    #[test]
+    fn test_9bit_rom_bank() {
+        let mut bus = create_bus(&create_banked_rom(512, 0x19, 0x08, 0x00));
+
+        assert_eq!(read_bank(&bus), 1);
+
+        bus.write(0x2000, 0xFF);
+        assert_eq!(read_bank(&bus), 0xFF);
+
+        bus.write(0x3000, 0x01);
+        assert_eq!(read_bank(&bus), 0x1FF);
+
+        // The low register keeps the high bit
+        bus.write(0x2000, 0x23);
+        assert_eq!(read_bank(&bus), 0x123);
+
+        bus.write(0x3FFF, 0x00);
+        assert_eq!(read_bank(&bus), 0x23);
+        assert_eq!(bus.read(0x0000), 0);
+    }
+
+    #[test]
+    fn test_rom_bank_zero() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x19, 0x01, 0x00));
+
+        bus.write(0x2000, 0x00);
+        assert_eq!(read_bank(&bus), 0);
+    }
+
+    #[test]
+    fn test_rom_bank_masked_by_size() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x19, 0x01, 0x00));
+
+        bus.write(0x2000, 0x06);
+        bus.write(0x3000, 0x01);
+        assert_eq!(read_bank(&bus), 2);
+    }
+
+    #[test]
+    fn test_ram_enable() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x1B, 0x01, 0x02));
+
+        bus.write(0x0000, 0x0A);
+        bus.write(0xA000, 0x12);
+        assert_eq!(bus.read(0xA000), 0x12);
+
+        // The whole byte has to match
+        bus.write(0x0000, 0x1A);
+        assert_eq!(bus.read(0xA000), 0xFF);
+    }
+
+    #[test]
+    fn test_16_ram_banks() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x1B, 0x01, 0x04));
+        bus.write(0x0000, 0x0A);
+
+        for bank in 0..16 {
+            bus.write(0x4000, bank);
+            bus.write(0xBFFF, 0x20 + bank);
+        }
+
+        for bank in 0..16 {
+            bus.write(0x4000, bank);
+            assert_eq!(bus.read(0xBFFF), 0x20 + bank);
+            assert_eq!(
+                bus.cartridge().ram.read(bank as usize * 0x2000 + 0x1FFF),
+                0x20 + bank
+            );
+        }
+    }
+
+    #[test]
+    fn test_rumble() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x1E, 0x01, 0x03));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+
+        bus.on_rumble(Box::new(move |motor, at| {
+            sink.lock().unwrap().push((motor, at))
+        }));
+        bus.write(0x0000, 0x0A);
+
+        bus.write(0x4000, 0x0A);
+        bus.tick(100);
+        bus.write(0x4000, 0x0B);
+        bus.write(0x4000, 0x03);
+
+        assert_eq!(*events.lock().unwrap(), vec![(true, 0), (false, 100)]);
+
+        // Bit 3 is not a RAM bank bit on rumble cartridges
+        bus.write(0x4000, 0x0A);
+        bus.write(0xA000, 0x55);
+        assert_eq!(bus.cartridge().ram.read(2 * 0x2000), 0x55);
+    }
+
+    #[test]
+    fn test_no_rumble_without_motor() {
+        let mut bus = create_bus(&create_banked_rom(4, 0x1B, 0x01, 0x04));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+
+        bus.on_rumble(Box::new(move |motor, at| {
+            sink.lock().unwrap().push((motor, at))
+        }));
+        bus.write(0x0000, 0x0A);
+
+        bus.write(0x4000, 0x08);
+        bus.write(0xA000, 0x66);
+
+        assert!(events.lock().unwrap().is_empty());
+        assert_eq!(bus.cartridge().ram.read(8 * 0x2000), 0x66);
+    }
+}
@@ -1,18 +1,27 @@
 #[cfg(test)]
 mod tests {
-    use core::{memory::Memory, types::Size};
+    use core::memory::Memory;
 
     #[test]
     fn test_byte() {
         let mut mem = Memory::default();
-        mem.write(Size::Byte, 0x00, 0x01);
-        assert_eq!(mem.read(Size::Byte, 0x00), 0x01);
+        mem.write(0x00, 0x01);
+        assert_eq!(mem.read(0x00), 0x01);
     }
 
     #[test]
     fn test_word() {
         let mut mem = Memory::default();
-        mem.write(Size::Word, 0x00, 0x0102);
-        assert_eq!(mem.read(Size::Word, 0x00), 0x0102);
+        mem.write_word(0x00, 0x0102);
+        assert_eq!(mem.read_word(0x00), 0x0102);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let mut mem = Memory::new(0x10);
+        mem.write(0x10, 0x01);
+
+        assert_eq!(mem.read(0x10), 0xff);
+        assert_eq!(mem.read_word(0x0f), 0xff00);
     }
 }
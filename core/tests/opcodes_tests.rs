@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use core::cpu::{
+        opcodes::{lookup, lookup_cb, Opcode},
+        registers::{Condition, WordReg},
+        sm83::SM83,
+    };
+
+    const ZERO: u8 = 0b1000_0000;
+    // const SUB: u8 = 0b0100_0000;
+    // const HALF_CARRY: u8 = 0b0010_0000;
+    const CARRY: u8 = 0b0001_0000;
+
+    const WRAM_00: u16 = 0xC000;
+
+    const ILLEGAL: [u8; 11] = [
+        0xd3, 0xdb, 0xdd, 0xe3, 0xe4, 0xeb, 0xec, 0xed, 0xf4, 0xfc, 0xfd,
+    ];
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    // Runs the instruction with every pointer register aimed at WRAM and immediate
+    // operands of 0xC000, returning the cycles taken and the resulting PC
+    fn run(code: Vec<u8>, flags: u8) -> (usize, u16) {
+        let mut cpu = SM83::new();
+        cpu.reg.f = flags;
+        cpu.reg.set_word(WordReg::BC, WRAM_00);
+        cpu.reg.set_word(WordReg::DE, WRAM_00);
+        cpu.reg.set_word(WordReg::HL, WRAM_00);
+        cpu.reg.set_word(WordReg::SP, WRAM_00 + 0x100);
+
+        let mut code = code;
+        code.extend([0x00, 0xc0]);
+
+        cpu.bus.load_rom(&create_rom(code)).unwrap();
+
+        let cycles = cpu.step().unwrap();
+
+        (cycles, cpu.pc)
+    }
+
+    fn is_jump(opcode: &Opcode) -> bool {
+        matches!(
+            opcode.mnemonic,
+            "JP" | "JR" | "CALL" | "RET" | "RETI" | "RST"
+        )
+    }
+
+    // Flags that make the condition (not taken, taken)
+    fn condition_flags(cond: Condition) -> (u8, u8) {
+        match cond {
+            Condition::NZ => (ZERO, 0),
+            Condition::Z => (0, ZERO),
+            Condition::NC => (CARRY, 0),
+            Condition::C => (0, CARRY),
+        }
+    }
+
+    #[test]
+    fn test_illegal_opcodes() {
+        for op in 0..=0xff {
+            assert_eq!(lookup(op).is_none(), ILLEGAL.contains(&op), "{:02x}", op);
+        }
+    }
+
+    #[test]
+    fn test_decoder_matches_table() {
+        for op in 0..=0xff {
+            let opcode = match lookup(op) {
+                Some(opcode) if op != 0xcb => opcode,
+                _ => continue,
+            };
+
+            let flags = match opcode.condition() {
+                Some(cond) => condition_flags(cond),
+                None => (0, 0),
+            };
+
+            let (cycles, pc) = run(vec![op], flags.0);
+
+            assert_eq!(cycles, opcode.cycles, "{:02x} {}", op, opcode);
+
+            if opcode.condition().is_some() || !is_jump(opcode) {
+                assert_eq!(pc, 0x100 + opcode.length as u16, "{:02x} {}", op, opcode);
+            }
+
+            if let Some(branch_cycles) = opcode.branch_cycles {
+                let (cycles, _) = run(vec![op], flags.1);
+
+                assert_eq!(cycles, branch_cycles, "{:02x} {}", op, opcode);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cb_decoder_matches_table() {
+        for op in 0..=0xff {
+            let opcode = lookup_cb(op);
+
+            let (cycles, pc) = run(vec![0xcb, op], 0);
+
+            assert_eq!(cycles, opcode.cycles, "cb {:02x} {}", op, opcode);
+            assert_eq!(pc, 0x100 + opcode.length as u16, "cb {:02x} {}", op, opcode);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(lookup(0x00).unwrap().to_string(), "NOP");
+        assert_eq!(lookup(0xfa).unwrap().to_string(), "LD A, (nn)");
+        assert_eq!(lookup(0x22).unwrap().to_string(), "LD (HL+), A");
+        assert_eq!(lookup(0x20).unwrap().to_string(), "JR NZ, e");
+        assert_eq!(lookup(0xe0).unwrap().to_string(), "LDH (n), A");
+        assert_eq!(lookup(0xf8).unwrap().to_string(), "LD HL, SP+e");
+        assert_eq!(lookup(0xff).unwrap().to_string(), "RST 38h");
+        assert_eq!(lookup_cb(0x7e).to_string(), "BIT 7, (HL)");
+        assert_eq!(lookup_cb(0x37).to_string(), "SWAP A");
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use core::bus::Bus;
+    use core::scheduler::{Event, Scheduler};
+
+    #[test]
+    fn test_empty() {
+        let mut scheduler = Scheduler::new();
+
+        assert_eq!(scheduler.next(), None);
+        assert_eq!(scheduler.next_deadline(), None);
+        assert_eq!(scheduler.pop_due(usize::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_order() {
+        let mut scheduler = Scheduler::new();
+
+        scheduler.schedule(Event::Ppu, 300);
+        scheduler.schedule(Event::Timer, 100);
+        scheduler.schedule(Event::Serial, 200);
+
+        assert_eq!(scheduler.next(), Some((Event::Timer, 100)));
+        assert_eq!(scheduler.pop_due(99), None);
+        assert_eq!(scheduler.pop_due(250), Some((Event::Timer, 100)));
+        assert_eq!(scheduler.pop_due(250), Some((Event::Serial, 200)));
+        assert_eq!(scheduler.pop_due(250), None);
+        assert_eq!(scheduler.next(), Some((Event::Ppu, 300)));
+    }
+
+    #[test]
+    fn test_ties() {
+        let mut scheduler = Scheduler::new();
+
+        scheduler.schedule(Event::Rtc, 100);
+        scheduler.schedule(Event::Timer, 100);
+
+        assert_eq!(scheduler.pop_due(100), Some((Event::Timer, 100)));
+        assert_eq!(scheduler.pop_due(100), Some((Event::Rtc, 100)));
+    }
+
+    #[test]
+    fn test_reschedule_and_cancel() {
+        let mut scheduler = Scheduler::new();
+
+        scheduler.schedule(Event::Timer, 100);
+        scheduler.schedule(Event::Timer, 500);
+
+        assert_eq!(scheduler.deadline(Event::Timer), Some(500));
+        assert_eq!(scheduler.next_deadline(), Some(500));
+
+        scheduler.schedule(Event::Apu, 400);
+        scheduler.cancel(Event::Apu);
+
+        assert_eq!(scheduler.deadline(Event::Apu), None);
+        assert_eq!(scheduler.next(), Some((Event::Timer, 500)));
+    }
+
+    #[test]
+    fn test_bus_tick() {
+        let mut bus = Bus::default();
+
+        bus.scheduler.schedule(Event::Timer, 100);
+
+        assert_eq!(bus.cycles_to_next_event(), Some(100));
+
+        bus.tick(96);
+
+        assert_eq!(bus.cycles_to_next_event(), Some(4));
+
+        bus.tick(4);
+
+        assert_eq!(bus.scheduler.deadline(Event::Timer), None);
+        assert_eq!(bus.cycles_to_next_event(), None);
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use core::bus::Bus;
+    use core::interrupts::Interrupt;
+    use core::serial::{Capture, Loopback};
+
+    const SB: u16 = 0xFF01;
+    const SC: u16 = 0xFF02;
+
+    fn serial_requested(bus: &Bus) -> bool {
+        bus.interrupts.flag & Interrupt::Serial.bit() != 0
+    }
+
+    #[test]
+    fn test_sc_reads() {
+        let mut bus = Bus::default();
+
+        assert_eq!(bus.read(SC), 0x7E);
+
+        bus.write(SC, 0x81);
+        assert_eq!(bus.read(SC), 0xFF);
+
+        // The fast clock bit only exists on CGB
+        bus.cgb = true;
+        bus.write(SC, 0x01);
+        assert_eq!(bus.read(SC), 0x7D);
+    }
+
+    #[test]
+    fn test_internal_clock() {
+        let mut bus = Bus::default();
+        let capture = Capture::new();
+        bus.serial.connect(Box::new(capture.clone()));
+
+        bus.write(SB, b'A');
+        bus.write(SC, 0x81);
+
+        // Half way the top nibble is out and four high bits came in
+        bus.tick(4 * 512);
+        assert_eq!(bus.read(SB), 0x1F);
+        assert!(!serial_requested(&bus));
+
+        bus.tick(4 * 512 - 4);
+        assert!(!serial_requested(&bus));
+        assert_eq!(bus.read(SC), 0xFF);
+
+        bus.tick(4);
+        assert!(serial_requested(&bus));
+        assert_eq!(bus.read(SB), 0xFF);
+        assert_eq!(bus.read(SC), 0x7F);
+        assert_eq!(capture.text(), "A");
+    }
+
+    #[test]
+    fn test_capture_text() {
+        let mut bus = Bus::default();
+        let capture = Capture::new();
+        bus.serial.connect(Box::new(capture.clone()));
+
+        for byte in b"Passed\n" {
+            bus.write(SB, *byte);
+            bus.write(SC, 0x81);
+            bus.tick(8 * 512);
+        }
+
+        assert_eq!(capture.text(), "Passed\n");
+
+        capture.clear();
+        assert!(capture.bytes().is_empty());
+    }
+
+    #[test]
+    fn test_loopback() {
+        let mut bus = Bus::default();
+        bus.serial.connect(Box::new(Loopback));
+
+        bus.write(SB, 0xA5);
+        bus.write(SC, 0x81);
+        bus.tick(8 * 512);
+
+        assert_eq!(bus.read(SB), 0xA5);
+        assert!(serial_requested(&bus));
+    }
+
+    #[test]
+    fn test_fast_clock() {
+        let mut bus = Bus::default();
+        bus.cgb = true;
+
+        bus.write(SB, 0x00);
+        bus.write(SC, 0x83);
+
+        bus.tick(8 * 16 - 4);
+        assert!(!serial_requested(&bus));
+
+        bus.tick(4);
+        assert!(serial_requested(&bus));
+        assert_eq!(bus.read(SB), 0xFF);
+    }
+
+    #[test]
+    fn test_fast_clock_needs_cgb() {
+        let mut bus = Bus::default();
+
+        bus.write(SC, 0x83);
+        bus.tick(8 * 16);
+
+        assert!(!serial_requested(&bus));
+    }
+
+    #[test]
+    fn test_external_clock() {
+        let mut bus = Bus::default();
+
+        bus.write(SB, 0x12);
+
+        // Nothing to clock
+        assert_eq!(bus.clock_serial(0x34), None);
+
+        bus.write(SC, 0x80);
+        bus.tick(100_000);
+
+        // Waits for the other side
+        assert!(!serial_requested(&bus));
+        assert_eq!(bus.read(SC), 0xFE);
+        assert_eq!(bus.cycles_to_next_event(), None);
+
+        assert_eq!(bus.clock_serial(0x34), Some(0x12));
+        assert!(serial_requested(&bus));
+        assert_eq!(bus.read(SB), 0x34);
+        assert_eq!(bus.read(SC), 0x7E);
+    }
+
+    #[test]
+    fn test_abort() {
+        let mut bus = Bus::default();
+
+        bus.write(SB, 0x00);
+        bus.write(SC, 0x81);
+        bus.tick(512);
+        bus.write(SC, 0x01);
+        bus.tick(8 * 512);
+
+        assert!(!serial_requested(&bus));
+        assert_eq!(bus.read(SB), 0x01);
+    }
+}
@@ -7,6 +7,8 @@ mod tests {
     const HALF_CARRY: u8 = 0b0010_0000;
     const CARRY: u8 = 0b0001_0000;
 
+    const WRAM_00: u16 = 0xC000;
+
     fn create_rom(rom: Vec<u8>) -> Vec<u8> {
         let mut new_rom = vec![0; 0x100];
         new_rom.extend(rom);
@@ -30,21 +32,21 @@ mod tests {
             0x8f, // ADC A, A
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x03);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x06);
         assert_eq!(cpu.reg.f, 0x00);
@@ -62,9 +64,9 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
@@ -80,9 +82,9 @@ mod tests {
             0x01, // 0x100
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
@@ -101,21 +103,21 @@ mod tests {
             0x87, // ADD A, A
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x03);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x06);
         assert_eq!(cpu.reg.f, 0x00);
@@ -133,9 +135,9 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
@@ -151,9 +153,9 @@ mod tests {
             0x01, // 0x100
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
@@ -175,30 +177,30 @@ mod tests {
             0x39, // ADD HL, SP
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::HL), 0x04);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::HL), 0x06);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::HL), 0xc);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x103);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::HL), 0x10);
-        assert_eq!(cpu.reg.f, 0x20);
+        assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x104);
     }
 
@@ -212,9 +214,9 @@ mod tests {
             0x01, // 0x100
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.sp, 0x02);
         assert_eq!(cpu.reg.f, 0x00);
@@ -234,21 +236,21 @@ mod tests {
             0xbf, // CP A, A
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, ZERO | SUB);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
-        assert_eq!(cpu.reg.f, SUB | HALF_CARRY);
+        assert_eq!(cpu.reg.f, SUB | HALF_CARRY | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, ZERO | SUB); // cp A, A flags = 0b1100_0000
@@ -267,9 +269,9 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, ZERO | SUB);
@@ -286,9 +288,9 @@ mod tests {
             0x01, // 0x101
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, ZERO | SUB);
@@ -310,27 +312,27 @@ mod tests {
             0x15, // DEC D
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO | SUB);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x00);
         assert_eq!(cpu.reg.f, ZERO | SUB);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x01);
         assert_eq!(cpu.reg.f, SUB);
         assert_eq!(cpu.pc, 0x103);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0xff);
         assert_eq!(cpu.reg.f, SUB | HALF_CARRY);
@@ -340,20 +342,28 @@ mod tests {
     #[test]
     fn test_dec_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, 0x102);
+        cpu.reg.set_word(WordReg::HL, WRAM_00);
 
         let rom = create_rom(vec![
             0x35, // DEC (HL)
-            0x00, // F = 0x00
-            0x01, // A = 0x01
+            0x35, // DEC (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.bus.write(WRAM_00, 0x01);
 
-        assert_eq!(cpu.reg.f, SUB | HALF_CARRY);
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.bus.read(WRAM_00), 0x00);
+        assert_eq!(cpu.reg.f, ZERO | SUB);
         assert_eq!(cpu.pc, 0x101);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.bus.read(WRAM_00), 0xff);
+        assert_eq!(cpu.reg.f, SUB | HALF_CARRY);
+        assert_eq!(cpu.pc, 0x102);
     }
 
     #[test]
@@ -371,27 +381,27 @@ mod tests {
             0x3b, // DEC SP
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::BC), 0x101);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::DE), 0x101);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::HL), 0x101);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x103);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::SP), 0xff);
         assert_eq!(cpu.reg.f, 0x00);
@@ -415,56 +425,65 @@ mod tests {
             0x1c, // INC E
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x02);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x03);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x103);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.e, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.f, ZERO | HALF_CARRY);
         assert_eq!(cpu.pc, 0x105);
     }
 
     #[test]
     fn test_inc_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, 0x102);
+        cpu.reg.set_word(WordReg::HL, WRAM_00);
+        cpu.reg.f = CARRY;
 
         let rom = create_rom(vec![
             0x34, // INC (HL)
-            0x00, // F = 0x00
-            0x01, // A = 0x01
+            0x34, // INC (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.bus.write(WRAM_00, 0x0e);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.f, 0x20);
+        assert_eq!(cpu.bus.read(WRAM_00), 0x0f);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x101);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.bus.read(WRAM_00), 0x10);
+        assert_eq!(cpu.reg.f, HALF_CARRY | CARRY);
+        assert_eq!(cpu.pc, 0x102);
     }
 
     #[test]
@@ -482,27 +501,27 @@ mod tests {
             0x33, // INC SP
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::BC), 0x103);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::DE), 0x103);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::HL), 0x103);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x103);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.get_word(WordReg::SP), 0x100);
         assert_eq!(cpu.reg.f, 0x00);
@@ -519,12 +538,12 @@ mod tests {
             0x9f, // SBC A, A
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x01);
-        assert_eq!(cpu.reg.f, SUB | CARRY);
+        assert_eq!(cpu.reg.a, 0xff);
+        assert_eq!(cpu.reg.f, SUB | HALF_CARRY | CARRY);
         assert_eq!(cpu.pc, 0x101);
     }
 
@@ -542,24 +561,24 @@ mod tests {
             0x9b, // SBC A, E
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO | SUB);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0xfe);
-        assert_eq!(cpu.reg.f, SUB | HALF_CARRY);
+        assert_eq!(cpu.reg.f, SUB | HALF_CARRY | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0xff);
-        assert_eq!(cpu.reg.f, SUB | HALF_CARRY);
+        assert_eq!(cpu.reg.a, 0xfe);
+        assert_eq!(cpu.reg.f, SUB | HALF_CARRY | CARRY);
         assert_eq!(cpu.pc, 0x103);
     }
 
@@ -576,12 +595,12 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x01);
-        assert_eq!(cpu.reg.f, SUB);
+        assert_eq!(cpu.reg.a, 0xff);
+        assert_eq!(cpu.reg.f, SUB | HALF_CARRY | CARRY);
         assert_eq!(cpu.pc, 0x101);
     }
 
@@ -596,12 +615,12 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x01);
-        assert_eq!(cpu.reg.f, SUB);
+        assert_eq!(cpu.reg.a, 0xff);
+        assert_eq!(cpu.reg.f, SUB | HALF_CARRY | CARRY);
         assert_eq!(cpu.pc, 0x102);
     }
 
@@ -614,9 +633,9 @@ mod tests {
             0x97, // SUB A, A
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO | SUB);
@@ -637,24 +656,24 @@ mod tests {
             0x93, // SUB A, E
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO | SUB);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0xfe);
-        assert_eq!(cpu.reg.f, SUB | HALF_CARRY);
+        assert_eq!(cpu.reg.f, SUB | HALF_CARRY | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0xff);
-        assert_eq!(cpu.reg.f, SUB | HALF_CARRY);
+        assert_eq!(cpu.reg.f, SUB | HALF_CARRY | CARRY);
         assert_eq!(cpu.pc, 0x103);
     }
 
@@ -670,9 +689,9 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO | SUB);
@@ -689,12 +708,78 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO | SUB);
         assert_eq!(cpu.pc, 0x102);
     }
+
+    #[test]
+    fn test_add_overflow() {
+        let mut cpu = SM83::new();
+        cpu.reg.a = 0xff;
+        cpu.reg.b = 0x01;
+        cpu.reg.set_word(WordReg::HL, 0xffff);
+        cpu.reg.set_word(WordReg::DE, 0x0001);
+
+        let rom = create_rom(vec![
+            0x80, // ADD A, B
+            0x19, // ADD HL, DE
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x00);
+        assert_eq!(cpu.reg.f, ZERO | HALF_CARRY | CARRY);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.get_word(WordReg::HL), 0x0000);
+        assert_eq!(cpu.reg.f, ZERO | HALF_CARRY | CARRY);
+    }
+
+    #[test]
+    fn test_add_sp_e_negative() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = 0xfff8;
+        cpu.reg.f = ZERO | SUB;
+
+        let rom = create_rom(vec![
+            0xe8, // ADD SP, e
+            0xff,
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, 0xfff7);
+        assert_eq!(cpu.reg.f, HALF_CARRY | CARRY);
+        assert_eq!(cpu.pc, 0x102);
+    }
+
+    #[test]
+    fn test_ld_hl_sp_e() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = 0x00ff;
+
+        let rom = create_rom(vec![
+            0xf8, // LD HL, SP+e
+            0x01,
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.get_word(WordReg::HL), 0x0100);
+        assert_eq!(cpu.reg.sp, 0x00ff);
+        assert_eq!(cpu.reg.f, HALF_CARRY | CARRY);
+        assert_eq!(cpu.pc, 0x102);
+    }
 }
@@ -1,16 +1,13 @@
 #[cfg(test)]
 mod tests {
-    use core::{
-        cpu::{registers::WordReg, sm83::SM83},
-        types::Size,
-    };
+    use core::cpu::{registers::WordReg, sm83::SM83};
 
     const ZERO: u8 = 0b1000_0000;
     // const SUB: u8 = 0b0100_0000;
     const HALF_CARRY: u8 = 0b0010_0000;
     // const CARRY: u8 = 0b0001_0000;
 
-    const WRAM_00: usize = 0xC000;
+    const WRAM_00: u16 = 0xC000;
 
     fn create_rom(rom: Vec<u8>) -> Vec<u8> {
         let mut new_rom = vec![0; 0x100];
@@ -42,15 +39,15 @@ mod tests {
             0xcb, // PREFIX
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, HALF_CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x01);
         assert_eq!(cpu.reg.f, ZERO | HALF_CARRY);
@@ -60,7 +57,7 @@ mod tests {
     #[test]
     fn test_bit_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -81,18 +78,18 @@ mod tests {
             0x7e, // BIT 7, (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0xff);
+        cpu.bus.write(WRAM_00 + 0x01, 0xff);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.f, HALF_CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x00);
+        cpu.bus.write(WRAM_00 + 0x01, 0x00);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.f, ZERO | HALF_CARRY);
         assert_eq!(cpu.pc, 0x104);
@@ -114,19 +111,19 @@ mod tests {
             0x91, // RES 2, C
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0xfe);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0xfd);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0xfb);
         assert_eq!(cpu.pc, 0x106);
@@ -135,7 +132,7 @@ mod tests {
     #[test]
     fn test_res_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -145,20 +142,20 @@ mod tests {
             0xcb, // PREFIX
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0xff);
+        cpu.bus.write(WRAM_00 + 0x01, 0xff);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0xfe);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0xfe);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0xff);
+        cpu.bus.write(WRAM_00 + 0x01, 0xff);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0xfd);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0xfd);
         assert_eq!(cpu.pc, 0x104);
     }
 
@@ -178,19 +175,19 @@ mod tests {
             0xd1, // SET 2, C
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x02);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x04);
         assert_eq!(cpu.pc, 0x106);
@@ -199,7 +196,7 @@ mod tests {
     #[test]
     fn test_set_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -209,20 +206,20 @@ mod tests {
             0xcb, // PREFIX
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x00);
+        cpu.bus.write(WRAM_00 + 0x01, 0x00);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x01);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x01);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x00);
+        cpu.bus.write(WRAM_00 + 0x01, 0x00);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x02);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x02);
         assert_eq!(cpu.pc, 0x104);
     }
 
@@ -245,27 +242,27 @@ mod tests {
             0x32, // SWAP D
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x21);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x43);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x65);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x106);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
@@ -275,7 +272,7 @@ mod tests {
     #[test]
     fn test_swap_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -284,21 +281,21 @@ mod tests {
             0x36, // SWAP (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x12);
+        cpu.bus.write(WRAM_00 + 0x01, 0x12);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x21);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x21);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x00);
+        cpu.bus.write(WRAM_00 + 0x01, 0x00);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x00);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x104);
     }
@@ -1,16 +1,13 @@
 #[cfg(test)]
 mod tests {
-    use core::{
-        cpu::{registers::WordReg, sm83::SM83},
-        types::Size,
-    };
+    use core::cpu::{registers::WordReg, sm83::SM83};
 
     const ZERO: u8 = 0b1000_0000;
     // const SUB: u8 = 0b0100_0000;
     // const HALF_CARRY: u8 = 0b0010_0000;
     const CARRY: u8 = 0b0001_0000;
 
-    const WRAM_00: usize = 0xC000;
+    const WRAM_00: u16 = 0xC000;
 
     fn create_rom(rom: Vec<u8>) -> Vec<u8> {
         let mut new_rom = vec![0; 0x100];
@@ -45,44 +42,44 @@ mod tests {
             0x13, // RL E
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x02);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x04);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x106);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x108);
 
         cpu.reg.set_flags(CARRY);
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.e, 0x01);
-        assert_eq!(cpu.reg.f, 0x00);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x10a);
     }
 
     #[test]
     fn test_rl_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -91,22 +88,22 @@ mod tests {
             0x16, // RL (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x80);
+        cpu.bus.write(WRAM_00 + 0x01, 0x80);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x00);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x80);
+        cpu.bus.write(WRAM_00 + 0x01, 0x80);
         cpu.reg.set_flags(CARRY);
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x01);
-        assert_eq!(cpu.reg.f, 0x00);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x01);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x104);
     }
 
@@ -132,36 +129,36 @@ mod tests {
             0x03, // RLC E
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x02);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x04);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x106);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x108);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.e, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.e, 0x01);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x10a);
     }
 
@@ -187,44 +184,44 @@ mod tests {
             0x1b, // RR E
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.b, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.b, 0x80);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.c, 0x01);
+        assert_eq!(cpu.reg.c, 0x81);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x106);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x108);
 
         cpu.reg.set_flags(CARRY);
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.e, 0x80);
-        assert_eq!(cpu.reg.f, 0x00);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x10a);
     }
 
     #[test]
     fn test_rr_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -233,22 +230,22 @@ mod tests {
             0x1e, // RR (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x01);
+        cpu.bus.write(WRAM_00 + 0x01, 0x01);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x00);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x01);
+        cpu.bus.write(WRAM_00 + 0x01, 0x01);
         cpu.reg.set_flags(CARRY);
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x80);
-        assert_eq!(cpu.reg.f, 0x00);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x80);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x104);
     }
 
@@ -277,49 +274,49 @@ mod tests {
             0x0c, // RRC H
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.a, 0x80);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.b, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.b, 0x80);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x01);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x106);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x108);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.e, 0x40);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x10a);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.h, 0x7f);
-        assert_eq!(cpu.reg.f, 0x00);
+        assert_eq!(cpu.reg.h, 0xff);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x10c);
     }
 
     #[test]
     fn test_rrc_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -328,20 +325,20 @@ mod tests {
             0x0e, // RRC (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x01);
+        cpu.bus.write(WRAM_00 + 0x01, 0x01);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x80);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x80);
-        cpu.step();
+        cpu.bus.write(WRAM_00 + 0x01, 0x80);
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x40);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x40);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x104);
     }
@@ -371,49 +368,49 @@ mod tests {
             0x24, // SLA H
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x02);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x04);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x106);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x108);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.e, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x10a);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.h, 0xfe);
-        assert_eq!(cpu.reg.f, 0x00);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x10c);
     }
 
     #[test]
     fn test_sla_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -422,21 +419,21 @@ mod tests {
             0x26, // SLA (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x01);
+        cpu.bus.write(WRAM_00 + 0x01, 0x01);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x02);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x02);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x80);
-        cpu.step();
+        cpu.bus.write(WRAM_00 + 0x01, 0x80);
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x00);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x104);
     }
 
@@ -465,49 +462,49 @@ mod tests {
             0x2c, // SRA H
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x01);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x106);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x108);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.e, 0xc0);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x10a);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.h, 0xff);
-        assert_eq!(cpu.reg.f, 0x00);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x10c);
     }
 
     #[test]
     fn test_sra_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -516,20 +513,20 @@ mod tests {
             0x2e, // SRA (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x01);
+        cpu.bus.write(WRAM_00 + 0x01, 0x01);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x00);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x80);
-        cpu.step();
+        cpu.bus.write(WRAM_00 + 0x01, 0x80);
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0xc0);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0xc0);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x104);
     }
@@ -559,49 +556,49 @@ mod tests {
             0x3c, // SRL H
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.b, 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x104);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.c, 0x01);
         assert_eq!(cpu.reg.f, 0);
         assert_eq!(cpu.pc, 0x106);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.d, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x108);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.e, 0x40);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x10a);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.h, 0x7f);
-        assert_eq!(cpu.reg.f, 0x00);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x10c);
     }
 
     #[test]
     fn test_srl_hl() {
         let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
         let rom = create_rom(vec![
             0xcb, // PREFIX
@@ -610,20 +607,20 @@ mod tests {
             0x3e, // SRL (HL)
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x01);
+        cpu.bus.write(WRAM_00 + 0x01, 0x01);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x00);
-        assert_eq!(cpu.reg.f, ZERO);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x00);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x80);
-        cpu.step();
+        cpu.bus.write(WRAM_00 + 0x01, 0x80);
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x40);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x40);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x104);
     }
@@ -637,9 +634,9 @@ mod tests {
             0x17, // RLA
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.reg.f, 0);
@@ -655,9 +652,9 @@ mod tests {
             0x07, // RLCA
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.reg.f, 0);
@@ -673,12 +670,12 @@ mod tests {
             0x1f, // RRA
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
-        assert_eq!(cpu.reg.f, 0);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x101);
     }
 
@@ -691,12 +688,12 @@ mod tests {
             0x0f, // RRCA
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x00);
-        assert_eq!(cpu.reg.f, 0);
+        assert_eq!(cpu.reg.a, 0x80);
+        assert_eq!(cpu.reg.f, CARRY);
         assert_eq!(cpu.pc, 0x101);
     }
 }
@@ -1,12 +1,14 @@
 #[cfg(test)]
 mod tests {
-    use core::cpu::sm83::SM83;
+    use core::cpu::{registers::WordReg, sm83::SM83};
 
-    // const ZERO: u8 = 0b1000_0000;
+    const ZERO: u8 = 0b1000_0000;
     // const SUB: u8 = 0b0100_0000;
     // const HALF_CARRY: u8 = 0b0010_0000;
     const CARRY: u8 = 0b0001_0000;
 
+    const WRAM_00: u16 = 0xC000;
+
     fn create_rom(rom: Vec<u8>) -> Vec<u8> {
         let mut new_rom = vec![0; 0x100];
         new_rom.extend(rom);
@@ -21,47 +23,296 @@ mod tests {
     #[test]
     fn test_call_nn() {
         let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00 + 0x10;
 
         let rom = create_rom(vec![
             0xcd, // CALL nn
-            0x00,
-            0x01,
+            0x00, 0x01,
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.sp, 0x103);
+        assert_eq!(cpu.reg.sp, WRAM_00 + 0x0e);
+        assert_eq!(cpu.bus.read_word(WRAM_00 + 0x0e), 0x103);
         assert_eq!(cpu.pc, 0x100);
     }
 
     #[test]
     fn test_call_cc_nn() {
         let mut cpu = SM83::new();
-        cpu.reg.sp = 0xFFFE;
+        cpu.reg.sp = WRAM_00 + 0x10;
+
+        let rom = create_rom(vec![
+            0xdc, 0x00, 0x00, // CALL C, nn
+            0xdc, 0x00, 0x01, // CALL C, nn
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.reg.f = 0;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, WRAM_00 + 0x10);
+        assert_eq!(cpu.pc, 0x103);
+
+        cpu.reg.f = CARRY;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, WRAM_00 + 0x0e);
+        assert_eq!(cpu.bus.read_word(WRAM_00 + 0x0e), 0x106);
+        assert_eq!(cpu.pc, 0x100);
+    }
+
+    #[test]
+    fn test_jp_nn() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0xc3, // JP nn
+            0x34, 0x12,
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x1234);
+    }
+
+    #[test]
+    fn test_jp_hl() {
+        let mut cpu = SM83::new();
+        cpu.reg.set_word(WordReg::HL, 0x1234);
+
+        let rom = create_rom(vec![
+            0xe9, // JP HL
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x1234);
+    }
+
+    #[test]
+    fn test_jp_cc_nn() {
+        let mut cpu = SM83::new();
 
         let rom = create_rom(vec![
-            0xdc, // CALL C, nn
-            0x00,
-            0x00,
-            0xdc, // CALL C, nn
-            0x00,
-            0x01,
+            0xca, 0x00, 0x02, // JP Z, nn
+            0xc2, 0x00, 0x02, // JP NZ, nn
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
         cpu.reg.f = 0;
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.sp, 0xFFFE);
         assert_eq!(cpu.pc, 0x103);
 
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x200);
+
+        let rom = create_rom(vec![
+            0xda, 0x00, 0x02, // JP C, nn
+            0xd2, 0x00, 0x02, // JP NC, nn
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.pc = 0x100;
         cpu.reg.f = CARRY;
-        cpu.step();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x200);
+
+        cpu.pc = 0x103;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x106);
+    }
+
+    #[test]
+    fn test_jr_e() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x18, 0x02, // JR e
+            0x00, // NOP
+            0x00, // NOP
+            0x18, 0xfa, // JR e
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x104);
+
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.sp, 0x106);
         assert_eq!(cpu.pc, 0x100);
     }
+
+    #[test]
+    fn test_jr_cc_e() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x28, 0x10, // JR Z, e
+            0x20, 0x10, // JR NZ, e
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.reg.f = ZERO;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x112);
+
+        cpu.pc = 0x102;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x104);
+
+        let rom = create_rom(vec![
+            0x38, 0x10, // JR C, e
+            0x30, 0x10, // JR NC, e
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.pc = 0x100;
+        cpu.reg.f = 0;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x102);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x114);
+    }
+
+    #[test]
+    fn test_ret() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00;
+
+        let rom = create_rom(vec![
+            0xc9, // RET
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.bus.write_word(WRAM_00, 0x1234);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, WRAM_00 + 2);
+        assert_eq!(cpu.pc, 0x1234);
+    }
+
+    #[test]
+    fn test_ret_cc() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00;
+
+        let rom = create_rom(vec![
+            0xd8, // RET C
+            0xd0, // RET NC
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.bus.write_word(WRAM_00, 0x1234);
+
+        cpu.reg.f = 0;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, WRAM_00);
+        assert_eq!(cpu.pc, 0x101);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, WRAM_00 + 2);
+        assert_eq!(cpu.pc, 0x1234);
+    }
+
+    #[test]
+    fn test_reti() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00;
+
+        let rom = create_rom(vec![
+            0xd9, // RETI
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.bus.write_word(WRAM_00, 0x1234);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, WRAM_00 + 2);
+        assert_eq!(cpu.pc, 0x1234);
+        assert!(cpu.ime);
+    }
+
+    #[test]
+    fn test_rst() {
+        let vectors = [
+            (0xc7, 0x00),
+            (0xcf, 0x08),
+            (0xd7, 0x10),
+            (0xdf, 0x18),
+            (0xe7, 0x20),
+            (0xef, 0x28),
+            (0xf7, 0x30),
+            (0xff, 0x38),
+        ];
+
+        for (op, vector) in vectors {
+            let mut cpu = SM83::new();
+            cpu.reg.sp = WRAM_00 + 0x10;
+
+            let rom = create_rom(vec![
+                op, // RST n
+            ]);
+
+            cpu.bus.load_rom(&rom).unwrap();
+
+            cpu.step().unwrap();
+
+            assert_eq!(cpu.reg.sp, WRAM_00 + 0x0e);
+            assert_eq!(cpu.bus.read_word(WRAM_00 + 0x0e), 0x101);
+            assert_eq!(cpu.pc, vector);
+        }
+    }
+
+    #[test]
+    fn test_call_ret() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00 + 0x10;
+
+        let rom = create_rom(vec![
+            0xcd, 0x04, 0x01, // CALL nn
+            0x00, // NOP
+            0xc9, // RET
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x104);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, WRAM_00 + 0x10);
+        assert_eq!(cpu.pc, 0x103);
+    }
 }
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use core::cpu::{registers::Condition, sm83::SM83};
+
+    const ZERO: u8 = 0b1000_0000;
+    // const SUB: u8 = 0b0100_0000;
+    // const HALF_CARRY: u8 = 0b0010_0000;
+    const CARRY: u8 = 0b0001_0000;
+
+    const WRAM_00: u16 = 0xC000;
+
+    const CONDITIONS: [Condition; 4] = [Condition::NZ, Condition::Z, Condition::NC, Condition::C];
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    fn expected(cond: Condition, flags: u8) -> bool {
+        match cond {
+            Condition::NZ => flags & ZERO == 0,
+            Condition::Z => flags & ZERO != 0,
+            Condition::NC => flags & CARRY == 0,
+            Condition::C => flags & CARRY != 0,
+        }
+    }
+
+    // Runs a single conditional instruction with every combination of flags and
+    // checks both the branch outcome and the cycles it took
+    fn check_all_flags(
+        ops: [u8; 4],
+        operands: &[u8],
+        target: u16,
+        cycles: usize,
+        branch_cycles: usize,
+    ) {
+        for (op, cond) in ops.into_iter().zip(CONDITIONS) {
+            for flags in (0..16).map(|f| f << 4) {
+                let mut cpu = SM83::new();
+                cpu.reg.sp = WRAM_00 + 0x10;
+                cpu.reg.f = flags;
+
+                let mut rom = vec![op];
+                rom.extend_from_slice(operands);
+
+                cpu.bus.load_rom(&create_rom(rom)).unwrap();
+                cpu.bus.write_word(WRAM_00 + 0x10, target);
+
+                let start = cpu.bus.cycles;
+                cpu.step().unwrap();
+
+                let taken = expected(cond, flags);
+
+                assert_eq!(cond.check(flags), taken, "{} with F={:02x}", cond, flags);
+
+                if taken {
+                    assert_eq!(cpu.pc, target, "{} with F={:02x}", cond, flags);
+                    assert_eq!(cpu.bus.cycles - start, branch_cycles);
+                } else {
+                    assert_eq!(cpu.pc, 0x101 + operands.len() as u16);
+                    assert_eq!(cpu.bus.cycles - start, cycles);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_jp_cc_nn() {
+        check_all_flags([0xc2, 0xca, 0xd2, 0xda], &[0x34, 0x12], 0x1234, 12, 16);
+    }
+
+    #[test]
+    fn test_jr_cc_e() {
+        check_all_flags([0x20, 0x28, 0x30, 0x38], &[0x10], 0x112, 8, 12);
+    }
+
+    #[test]
+    fn test_call_cc_nn() {
+        check_all_flags([0xc4, 0xcc, 0xd4, 0xdc], &[0x34, 0x12], 0x1234, 12, 24);
+    }
+
+    #[test]
+    fn test_ret_cc() {
+        check_all_flags([0xc0, 0xc8, 0xd0, 0xd8], &[], 0x1234, 8, 20);
+    }
+}
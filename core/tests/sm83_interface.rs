@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod tests {
+    use core::cpu::interface::{FlatMemory, MemoryInterface};
+    use core::cpu::sm83::{CpuState, SM83};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Access {
+        Tick,
+        Read(u16, u8),
+        Write(u16, u8),
+    }
+
+    // Flat RAM that remembers every access in order
+    struct Recorder {
+        ram: FlatMemory,
+        log: Vec<Access>,
+    }
+
+    impl Recorder {
+        fn new(program: &[u8]) -> Self {
+            let mut ram = FlatMemory::new();
+            ram.load(0x100, program);
+
+            Self {
+                ram,
+                log: Vec::new(),
+            }
+        }
+    }
+
+    impl MemoryInterface for Recorder {
+        fn read(&mut self, addr: u16) -> u8 {
+            let data = self.ram.read(addr);
+            self.log.push(Access::Read(addr, data));
+
+            data
+        }
+
+        fn write(&mut self, addr: u16, data: u8) {
+            self.log.push(Access::Write(addr, data));
+            self.ram.write(addr, data);
+        }
+
+        fn tick(&mut self, cycles: usize) {
+            assert_eq!(cycles, 4);
+            self.log.push(Access::Tick);
+        }
+
+        fn pending_interrupts(&mut self) -> u8 {
+            0
+        }
+    }
+
+    // Raises the timer interrupt once a deadline passes
+    struct Alarm {
+        ram: FlatMemory,
+        at: usize,
+    }
+
+    impl MemoryInterface for Alarm {
+        fn read(&mut self, addr: u16) -> u8 {
+            self.ram.read(addr)
+        }
+
+        fn write(&mut self, addr: u16, data: u8) {
+            self.ram.write(addr, data);
+        }
+
+        fn tick(&mut self, cycles: usize) {
+            self.ram.tick(cycles);
+
+            if self.ram.cycles >= self.at {
+                let flag = self.ram.read(0xff0f);
+                self.ram.write(0xff0f, flag | 0x04);
+            }
+        }
+
+        fn cycles_to_next_event(&self) -> Option<usize> {
+            self.at.checked_sub(self.ram.cycles)
+        }
+    }
+
+    #[test]
+    fn test_flat_memory() {
+        let mut ram = FlatMemory::new();
+        ram.load(
+            0x100,
+            &[
+                0x3e, 0x42, // LD A, 0x42
+                0xea, 0x00, 0x80, // LD (0x8000), A
+                0xe0, 0x80, // LDH (0x80), A
+            ],
+        );
+
+        let mut cpu = SM83::with_bus(ram);
+
+        assert_eq!(cpu.run_for(36), Ok(36));
+
+        // No cartridge or I/O in the way, every address is plain RAM
+        assert_eq!(cpu.bus.ram.read(0x8000), 0x42);
+        assert_eq!(cpu.bus.ram.read(0xff80), 0x42);
+        assert_eq!(cpu.bus.cycles, 36);
+        assert_eq!(cpu.cycles, 36);
+        assert_eq!(cpu.pc, 0x107);
+    }
+
+    #[test]
+    fn test_flat_memory_interrupts() {
+        let mut ram = FlatMemory::new();
+        ram.load(
+            0x100,
+            &[
+                0x00, // NOP
+            ],
+        );
+
+        // IE and IF are read through the memory map
+        ram.write(0xffff, 0x04);
+        ram.write(0xff0f, 0x04);
+
+        let mut cpu = SM83::with_bus(ram);
+        cpu.reg.sp = 0xd000;
+        cpu.ime = true;
+
+        assert_eq!(cpu.step(), Ok(20));
+
+        assert_eq!(cpu.pc, 0x50);
+        assert_eq!(cpu.bus.ram.read(0xff0f), 0x00);
+        assert_eq!(cpu.bus.ram.read_word(0xcffe), 0x100);
+    }
+
+    #[test]
+    fn test_access_order() {
+        let mut cpu = SM83::with_bus(Recorder::new(&[
+            0xc5, // PUSH BC
+            0x34, // INC (HL)
+        ]));
+        cpu.reg.sp = 0xd000;
+        cpu.reg.b = 0x12;
+        cpu.reg.c = 0x34;
+        cpu.reg.h = 0xc0;
+        cpu.reg.l = 0x00;
+
+        assert_eq!(cpu.step(), Ok(16));
+        assert_eq!(cpu.step(), Ok(12));
+
+        assert_eq!(
+            cpu.bus.log,
+            vec![
+                // PUSH BC
+                Access::Tick,
+                Access::Read(0x100, 0xc5),
+                Access::Tick,
+                Access::Tick,
+                Access::Write(0xcfff, 0x12),
+                Access::Tick,
+                Access::Write(0xcffe, 0x34),
+                // INC (HL)
+                Access::Tick,
+                Access::Read(0x101, 0x34),
+                Access::Tick,
+                Access::Read(0xc000, 0x00),
+                Access::Tick,
+                Access::Write(0xc000, 0x01),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_halt_sleeps_until_event() {
+        let mut ram = FlatMemory::new();
+        ram.load(
+            0x100,
+            &[
+                0x76, // HALT
+                0x00, // NOP
+            ],
+        );
+        ram.write(0xffff, 0x04);
+
+        let mut cpu = SM83::with_bus(Alarm { ram, at: 1002 });
+
+        assert_eq!(cpu.step(), Ok(4));
+
+        // Sleeps in one go, rounded up to a whole M-cycle
+        assert_eq!(cpu.step(), Ok(1000));
+        assert_eq!(cpu.state, CpuState::Halted);
+
+        assert_eq!(cpu.step(), Ok(4));
+        assert_eq!(cpu.state, CpuState::Running);
+        assert_eq!(cpu.pc, 0x102);
+    }
+
+    #[test]
+    fn test_halt_sleep_within_budget() {
+        let mut ram = FlatMemory::new();
+        ram.load(
+            0x100,
+            &[
+                0x76, // HALT
+            ],
+        );
+
+        let mut cpu = SM83::with_bus(Alarm { ram, at: 100_000 });
+
+        assert_eq!(cpu.run_for(400), Ok(400));
+        assert_eq!(cpu.run_for(401), Ok(404));
+        assert_eq!(cpu.bus.ram.cycles, 804);
+    }
+}
@@ -0,0 +1,268 @@
+#[cfg(test)]
+mod tests {
+    use core::{
+        cpu::sm83::{CpuState, SM83},
+        interrupts::Interrupt,
+    };
+
+    const WRAM_00: u16 = 0xC000;
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let vectors = [
+            (Interrupt::VBlank, 0x40),
+            (Interrupt::Stat, 0x48),
+            (Interrupt::Timer, 0x50),
+            (Interrupt::Serial, 0x58),
+            (Interrupt::Joypad, 0x60),
+        ];
+
+        for (interrupt, vector) in vectors {
+            let mut cpu = SM83::new();
+            cpu.reg.sp = WRAM_00 + 0x10;
+            cpu.ime = true;
+
+            cpu.bus.interrupts.enable = 0x1f;
+            cpu.bus.interrupts.request(interrupt);
+
+            let cycles = cpu.bus.cycles;
+            cpu.step().unwrap();
+
+            assert_eq!(cpu.bus.cycles - cycles, 20);
+            assert_eq!(cpu.pc, vector);
+            assert_eq!(cpu.reg.sp, WRAM_00 + 0x0e);
+            assert_eq!(cpu.bus.read_word(WRAM_00 + 0x0e), 0x100);
+            assert_eq!(cpu.bus.interrupts.flag, 0x00);
+            assert!(!cpu.ime);
+        }
+    }
+
+    #[test]
+    fn test_dispatch_priority() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00 + 0x10;
+        cpu.ime = true;
+
+        cpu.bus.interrupts.enable = 0x1f;
+        cpu.bus.interrupts.request(Interrupt::Joypad);
+        cpu.bus.interrupts.request(Interrupt::Timer);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x50);
+        assert_eq!(cpu.bus.interrupts.flag, Interrupt::Joypad.bit());
+    }
+
+    #[test]
+    fn test_no_dispatch_without_ime() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.bus.interrupts.enable = 0x1f;
+        cpu.bus.interrupts.request(Interrupt::VBlank);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x101);
+        assert_eq!(cpu.bus.interrupts.flag, Interrupt::VBlank.bit());
+    }
+
+    #[test]
+    fn test_no_dispatch_when_disabled() {
+        let mut cpu = SM83::new();
+        cpu.ime = true;
+
+        let rom = create_rom(vec![
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.bus.interrupts.enable = Interrupt::Timer.bit();
+        cpu.bus.interrupts.request(Interrupt::VBlank);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x101);
+    }
+
+    #[test]
+    fn test_halt_wake() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00 + 0x10;
+        cpu.ime = true;
+        cpu.bus.interrupts.enable = 0x1f;
+
+        let rom = create_rom(vec![
+            0x76, // HALT
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Halted);
+        assert_eq!(cpu.pc, 0x101);
+
+        cpu.bus.interrupts.request(Interrupt::VBlank);
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Running);
+        assert_eq!(cpu.pc, 0x40);
+        assert_eq!(cpu.bus.read_word(WRAM_00 + 0x0e), 0x101);
+    }
+
+    #[test]
+    fn test_halt_wake_without_ime() {
+        let mut cpu = SM83::new();
+        cpu.bus.interrupts.enable = 0x1f;
+
+        let rom = create_rom(vec![
+            0x76, // HALT
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Halted);
+
+        cpu.bus.interrupts.request(Interrupt::VBlank);
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Running);
+        assert_eq!(cpu.pc, 0x102);
+        assert_eq!(cpu.bus.interrupts.flag, Interrupt::VBlank.bit());
+    }
+
+    #[test]
+    fn test_halt_bug() {
+        let mut cpu = SM83::new();
+        cpu.bus.interrupts.enable = 0x1f;
+        cpu.bus.interrupts.request(Interrupt::VBlank);
+
+        let rom = create_rom(vec![
+            0x76, // HALT
+            0x3c, // INC A
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Running);
+        assert_eq!(cpu.pc, 0x101);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x01);
+        assert_eq!(cpu.pc, 0x101);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x02);
+        assert_eq!(cpu.pc, 0x102);
+    }
+
+    #[test]
+    fn test_halt_bug_after_ei() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00 + 0x10;
+        cpu.bus.interrupts.enable = 0x01;
+        cpu.bus.interrupts.request(Interrupt::VBlank);
+
+        let mut rom = create_rom(vec![
+            0xfb, // EI
+            0x76, // HALT
+            0x00, // NOP
+        ]);
+
+        rom[0x40..0x43].copy_from_slice(&[
+            0x04, // INC B
+            0x0c, // INC C
+            0xd9, // RETI
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        // The interrupt is taken right away and returns to the HALT
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x40);
+        assert_eq!(cpu.bus.read_word(WRAM_00 + 0x0e), 0x101);
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.b, 0x01);
+        assert_eq!(cpu.reg.c, 0x01);
+        assert_eq!(cpu.pc, 0x101);
+
+        // Nothing is pending any more, so this time the HALT halts
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Halted);
+        assert_eq!(cpu.pc, 0x102);
+    }
+
+    #[test]
+    fn test_ie_push_cancel() {
+        let mut cpu = SM83::new();
+        cpu.pc = 0x0050;
+        cpu.reg.sp = 0x0000;
+        cpu.ime = true;
+
+        cpu.bus.interrupts.enable = Interrupt::Timer.bit();
+        cpu.bus.interrupts.request(Interrupt::Timer);
+
+        cpu.step().unwrap();
+
+        // The high byte of PC (0x00) was pushed into IE
+        assert_eq!(cpu.bus.interrupts.enable, 0x00);
+        assert_eq!(cpu.bus.interrupts.flag, Interrupt::Timer.bit());
+        assert_eq!(cpu.pc, 0x0000);
+        assert_eq!(cpu.reg.sp, 0xfffe);
+    }
+
+    #[test]
+    fn test_ie_push_redirect() {
+        let mut cpu = SM83::new();
+        cpu.pc = 0x0120;
+        cpu.reg.sp = 0x0000;
+        cpu.ime = true;
+
+        cpu.bus.interrupts.enable = Interrupt::Timer.bit();
+        cpu.bus.interrupts.request(Interrupt::Timer);
+        cpu.bus.interrupts.request(Interrupt::VBlank);
+
+        cpu.step().unwrap();
+
+        // The high byte of PC (0x01) was pushed into IE, enabling only VBlank
+        assert_eq!(cpu.bus.interrupts.enable, Interrupt::VBlank.bit());
+        assert_eq!(cpu.bus.interrupts.flag, Interrupt::Timer.bit());
+        assert_eq!(cpu.pc, 0x40);
+    }
+}
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use core::{
-        cpu::{registers::WordReg, sm83::SM83},
-        types::Size,
+    use core::cpu::{
+        interface::{FlatMemory, MemoryInterface},
+        registers::WordReg,
+        sm83::SM83,
     };
 
     // const ZERO: u8 = 0b1000_0000;
@@ -10,38 +11,31 @@ mod tests {
     // const HALF_CARRY: u8 = 0b0010_0000;
     // const CARRY: u8 = 0b0001_0000;
 
-    const WRAM_00: usize = 0xC000;
+    const WRAM_00: u16 = 0xC000;
 
-    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
-        let mut new_rom = vec![0; 0x100];
-        new_rom.extend(rom);
+    // The program goes where the CPU starts, there is nothing else in memory
+    fn create_cpu(program: &[u8]) -> SM83<FlatMemory> {
+        let mut memory = FlatMemory::new();
+        memory.load(0x100, program);
 
-        if new_rom.len() < 0x150 {
-            new_rom.resize(0x150, 0);
-        }
-
-        new_rom
+        SM83::with_bus(memory)
     }
 
     #[test]
     fn test_ld_r_r() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x01;
-        cpu.reg.b = 0x02;
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0x7f, // LD A, A
             0x78, // LD A, B
         ]);
+        cpu.reg.a = 0x01;
+        cpu.reg.b = 0x02;
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.pc, 0x102);
@@ -49,17 +43,13 @@ mod tests {
 
     #[test]
     fn test_ld_r_n() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x01;
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0x3e, // LD A, n
             0x02,
         ]);
+        cpu.reg.a = 0x01;
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.pc, 0x102);
@@ -67,19 +57,15 @@ mod tests {
 
     #[test]
     fn test_ld_r_hl() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x01;
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0x7e, // LD A, (HL)
         ]);
+        cpu.reg.a = 0x01;
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x02);
+        cpu.bus.write(WRAM_00 + 0x01, 0x02);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.pc, 0x101);
@@ -87,91 +73,73 @@ mod tests {
 
     #[test]
     fn test_ld_hl_r() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x01;
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0x77, // LD (HL), A
         ]);
+        cpu.reg.a = 0x01;
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x01);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x01);
         assert_eq!(cpu.pc, 0x101);
     }
 
     #[test]
     fn test_ld_hl_n() {
-        let mut cpu = SM83::new();
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0x36, // LD (HL), n
             0x02,
         ]);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x02);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x02);
         assert_eq!(cpu.pc, 0x102);
     }
 
     #[test]
     fn test_ld_rr_a() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x01;
-        cpu.reg.set_word(WordReg::BC, WRAM_00 as u16 + 0x01);
-
-        let rom = create_rom(vec![
-            0x02, // LD A, (BC)
+        let mut cpu = create_cpu(&[
+            0x02, // LD (BC), A
         ]);
+        cpu.reg.a = 0x62;
+        cpu.reg.set_word(WordReg::BC, WRAM_00 + 0x01);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x02);
-
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x02);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x62);
         assert_eq!(cpu.pc, 0x101);
     }
 
     #[test]
     fn test_ld_a_rr() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x62;
-        cpu.reg.set_word(WordReg::BC, WRAM_00 as u16 + 0x01);
-
-        let rom = create_rom(vec![
-            0x0a, // LD (BC), A
+        let mut cpu = create_cpu(&[
+            0x1a, // LD A, (DE)
         ]);
+        cpu.reg.a = 0x01;
+        cpu.reg.set_word(WordReg::DE, WRAM_00 + 0x01);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.write(WRAM_00 + 0x01, 0x02);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x62);
+        assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.pc, 0x101);
     }
 
     #[test]
     fn test_ld_a_nn() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x00;
-
-        let rom = create_rom(vec![
-            0xfa, // LD (nn), A
-            0x62,
+        let mut cpu = create_cpu(&[
+            0xfa, // LD A, (nn)
+            0x01, 0xc0,
         ]);
+        cpu.reg.a = 0x00;
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.write(WRAM_00 + 0x01, 0x62);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x62);
         assert_eq!(cpu.pc, 0x103);
@@ -179,173 +147,182 @@ mod tests {
 
     #[test]
     fn test_ld_nn_a() {
-        let mut cpu = SM83::new();
+        let mut cpu = create_cpu(&[
+            0xea, // LD (nn), A
+            0x01, 0xc0,
+        ]);
         cpu.reg.a = 0x62;
 
-        cpu.pc = WRAM_00 as u16;
-
-        cpu.bus.write(Size::Word, WRAM_00, 0xea); // LD A, (nn)
+        cpu.step().unwrap();
 
-        cpu.step();
-
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x62);
-        assert_eq!(cpu.pc, WRAM_00 as u16 + 0x03);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x62);
+        assert_eq!(cpu.pc, 0x103);
     }
 
     #[test]
     fn test_ld_a_hl() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x00;
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0x2a, // LD A, (HL+)
         ]);
+        cpu.reg.a = 0x00;
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x62);
+        cpu.bus.write(WRAM_00 + 0x01, 0x62);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x62);
-        assert_eq!(cpu.reg.get_word(WordReg::HL), WRAM_00 as u16 + 0x02);
+        assert_eq!(cpu.reg.get_word(WordReg::HL), WRAM_00 + 0x02);
         assert_eq!(cpu.pc, 0x101);
     }
 
     #[test]
     fn test_ld_hl_a() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x62;
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0x22, // LD (HL+), A
         ]);
+        cpu.reg.a = 0x62;
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x62);
-        assert_eq!(cpu.reg.get_word(WordReg::HL), WRAM_00 as u16 + 0x02);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x62);
+        assert_eq!(cpu.reg.get_word(WordReg::HL), WRAM_00 + 0x02);
         assert_eq!(cpu.pc, 0x101);
     }
 
     #[test]
     fn test_ld_a_hl_2() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x00;
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0x3a, // LD A, (HL-)
         ]);
+        cpu.reg.a = 0x00;
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.bus.write(Size::Byte, WRAM_00 + 0x01, 0x62);
+        cpu.bus.write(WRAM_00 + 0x01, 0x62);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x62);
-        assert_eq!(cpu.reg.get_word(WordReg::HL), WRAM_00 as u16);
+        assert_eq!(cpu.reg.get_word(WordReg::HL), WRAM_00);
         assert_eq!(cpu.pc, 0x101);
     }
 
     #[test]
     fn test_ld_hl_a_2() {
-        let mut cpu = SM83::new();
+        let mut cpu = create_cpu(&[
+            0x32, // LD (HL-), A
+        ]);
         cpu.reg.a = 0x62;
-        cpu.reg.set_word(WordReg::HL, WRAM_00 as u16 + 0x01);
+        cpu.reg.set_word(WordReg::HL, WRAM_00 + 0x01);
 
-        let rom = create_rom(vec![
-            0x32, // LD (HL-), A
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0x62);
+        assert_eq!(cpu.reg.get_word(WordReg::HL), WRAM_00);
+        assert_eq!(cpu.pc, 0x101);
+    }
+
+    #[test]
+    fn test_ld_rr_nn() {
+        let mut cpu = create_cpu(&[
+            0x21, 0x34, 0x12, // LD HL, nn
+            0x31, 0xfe, 0xff, // LD SP, nn
+        ]);
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.get_word(WordReg::HL), 0x1234);
+        assert_eq!(cpu.reg.get_word(WordReg::SP), 0xfffe);
+        assert_eq!(cpu.pc, 0x106);
+    }
+
+    #[test]
+    fn test_ld_nn_sp() {
+        let mut cpu = create_cpu(&[
+            0x08, // LD (nn), SP
+            0x01, 0xc0,
         ]);
+        cpu.reg.set_word(WordReg::SP, 0xbeef);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x01), 0xef);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x02), 0xbe);
+        assert_eq!(cpu.pc, 0x103);
+    }
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+    #[test]
+    fn test_ld_sp_hl() {
+        let mut cpu = create_cpu(&[
+            0xf9, // LD SP, HL
+        ]);
+        cpu.reg.set_word(WordReg::HL, 0x1234);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, WRAM_00 + 0x01), 0x62);
-        assert_eq!(cpu.reg.get_word(WordReg::HL), WRAM_00 as u16);
+        assert_eq!(cpu.reg.get_word(WordReg::SP), 0x1234);
         assert_eq!(cpu.pc, 0x101);
     }
 
     #[test]
-    #[ignore]
     fn test_ld_a_c() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x00;
-        cpu.reg.c = 0x01;
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0xf2, // LD A, (C)
         ]);
+        cpu.reg.a = 0x00;
+        cpu.reg.c = 0x01;
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.write(0xff00 + 0x01, 0x62);
 
-        cpu.bus.write(Size::Byte, 0xff00 + 0x01, 0x62);
+        cpu.step().unwrap();
 
-        cpu.step();
+        assert_eq!(cpu.reg.a, 0x62);
+        assert_eq!(cpu.pc, 0x101);
     }
 
     #[test]
-    #[ignore]
     fn test_ld_c_a() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x62;
-        cpu.reg.c = 0x01;
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0xe2, // LD (C), A
         ]);
+        cpu.reg.a = 0x62;
+        cpu.reg.c = 0x01;
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, 0xff00 + 0x01), 0x62);
+        assert_eq!(cpu.bus.read(0xff00 + 0x01), 0x62);
         assert_eq!(cpu.pc, 0x101);
     }
 
     #[test]
-    #[ignore]
     fn ldh_a_n() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x00;
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0xf0, // LDH A, (n)
             0x01,
         ]);
+        cpu.reg.a = 0x00;
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.bus.write(Size::Byte, 0xff00 + 0x01, 0x62);
+        cpu.bus.write(0xff00 + 0x01, 0x62);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x62);
-        assert_eq!(cpu.pc, 0x103);
+        assert_eq!(cpu.pc, 0x102);
     }
 
     #[test]
-    #[ignore]
     fn ldh_n_a() {
-        let mut cpu = SM83::new();
-        cpu.reg.a = 0x62;
-
-        let rom = create_rom(vec![
+        let mut cpu = create_cpu(&[
             0xe0, // LDH (n), A
             0x01,
         ]);
+        cpu.reg.a = 0x62;
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
-
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.bus.read(Size::Byte, 0xff00 + 0x01), 0x62);
-        assert_eq!(cpu.pc, 0x103);
+        assert_eq!(cpu.bus.read(0xff00 + 0x01), 0x62);
+        assert_eq!(cpu.pc, 0x102);
     }
 }
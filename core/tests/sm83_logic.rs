@@ -31,21 +31,21 @@ mod tests {
             0xa7, // AND A, A
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, HALF_CARRY);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO | HALF_CARRY);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO | HALF_CARRY);
@@ -64,9 +64,9 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, HALF_CARRY);
@@ -83,9 +83,9 @@ mod tests {
             0x01, // 0x100
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, HALF_CARRY);
@@ -110,27 +110,27 @@ mod tests {
             0xb4, // OR H
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x03);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x03);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x103);
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0xff);
         assert_eq!(cpu.reg.f, 0x00);
@@ -138,7 +138,7 @@ mod tests {
 
         cpu.reg.a = 0x00;
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
@@ -157,9 +157,9 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
@@ -176,9 +176,9 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
         assert_eq!(cpu.reg.a, 0x01);
         assert_eq!(cpu.reg.f, 0x00);
@@ -200,31 +200,31 @@ mod tests {
             0xaf, // XOR A, A
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x01);
+        assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x101);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x01);
+        assert_eq!(cpu.reg.a, 0x02);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x102);
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x01);
+        assert_eq!(cpu.reg.a, 0xfd);
         assert_eq!(cpu.reg.f, 0x00);
         assert_eq!(cpu.pc, 0x103);
 
         cpu.reg.a = 0x01;
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x01);
+        assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x104);
     }
@@ -241,11 +241,11 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x01);
+        assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x101);
     }
@@ -260,11 +260,11 @@ mod tests {
             0x01, // A = 0x01
         ]);
 
-        cpu.bus.rom.load_new_rom(&rom).unwrap();
+        cpu.bus.load_rom(&rom).unwrap();
 
-        cpu.step();
+        cpu.step().unwrap();
 
-        assert_eq!(cpu.reg.a, 0x01);
+        assert_eq!(cpu.reg.a, 0x00);
         assert_eq!(cpu.reg.f, ZERO);
         assert_eq!(cpu.pc, 0x102);
     }
@@ -0,0 +1,309 @@
+#[cfg(test)]
+mod tests {
+    use core::cpu::sm83::{CpuError, CpuState, SM83};
+
+    const ZERO: u8 = 0b1000_0000;
+    const SUB: u8 = 0b0100_0000;
+    const HALF_CARRY: u8 = 0b0010_0000;
+    const CARRY: u8 = 0b0001_0000;
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    #[test]
+    fn test_nop() {
+        let mut cpu = SM83::new();
+        cpu.reg.f = ZERO | CARRY;
+
+        let rom = create_rom(vec![
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
+        assert_eq!(cpu.pc, 0x101);
+    }
+
+    #[test]
+    fn test_daa_after_add() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x27, // DAA
+            0x27, // DAA
+            0x27, // DAA
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        // 0x45 + 0x38
+        cpu.reg.a = 0x7d;
+        cpu.reg.f = 0;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x83);
+        assert_eq!(cpu.reg.f, 0x00);
+
+        // 0x99 + 0x01
+        cpu.reg.a = 0x9a;
+        cpu.reg.f = 0;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x00);
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
+
+        // 0x09 + 0x09
+        cpu.reg.a = 0x12;
+        cpu.reg.f = HALF_CARRY;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x18);
+        assert_eq!(cpu.reg.f, 0x00);
+    }
+
+    #[test]
+    fn test_daa_after_sub() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x27, // DAA
+            0x27, // DAA
+            0x27, // DAA
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        // 0x83 - 0x38
+        cpu.reg.a = 0x4b;
+        cpu.reg.f = SUB | HALF_CARRY;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x45);
+        assert_eq!(cpu.reg.f, SUB);
+
+        // 0x10 - 0x20
+        cpu.reg.a = 0xf0;
+        cpu.reg.f = SUB | CARRY;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x90);
+        assert_eq!(cpu.reg.f, SUB | CARRY);
+
+        // 0x01 - 0x01
+        cpu.reg.a = 0x00;
+        cpu.reg.f = SUB;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x00);
+        assert_eq!(cpu.reg.f, ZERO | SUB);
+    }
+
+    #[test]
+    fn test_cpl() {
+        let mut cpu = SM83::new();
+        cpu.reg.a = 0b1010_0101;
+        cpu.reg.f = ZERO | CARRY;
+
+        let rom = create_rom(vec![
+            0x2f, // CPL
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0b0101_1010);
+        assert_eq!(cpu.reg.f, ZERO | SUB | HALF_CARRY | CARRY);
+    }
+
+    #[test]
+    fn test_scf() {
+        let mut cpu = SM83::new();
+        cpu.reg.f = ZERO | SUB | HALF_CARRY;
+
+        let rom = create_rom(vec![
+            0x37, // SCF
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.f, ZERO | CARRY);
+    }
+
+    #[test]
+    fn test_ccf() {
+        let mut cpu = SM83::new();
+        cpu.reg.f = SUB | HALF_CARRY | CARRY;
+
+        let rom = create_rom(vec![
+            0x3f, // CCF
+            0x3f, // CCF
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.f, 0x00);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.f, CARRY);
+    }
+
+    #[test]
+    fn test_di() {
+        let mut cpu = SM83::new();
+        cpu.ime = true;
+
+        let rom = create_rom(vec![
+            0xf3, // DI
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert!(!cpu.ime);
+    }
+
+    #[test]
+    fn test_ei_delay() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0xfb, // EI
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert!(!cpu.ime);
+
+        cpu.step().unwrap();
+
+        assert!(cpu.ime);
+    }
+
+    #[test]
+    fn test_ei_di() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0xfb, // EI
+            0xf3, // DI
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert!(!cpu.ime);
+    }
+
+    #[test]
+    fn test_halt() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x76, // HALT
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Halted);
+        assert_eq!(cpu.pc, 0x101);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Halted);
+        assert_eq!(cpu.pc, 0x101);
+    }
+
+    #[test]
+    fn test_stop() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x10, // STOP
+            0x00,
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.state, CpuState::Stopped);
+        assert_eq!(cpu.pc, 0x102);
+    }
+
+    #[test]
+    fn test_illegal_opcode() {
+        for opcode in [
+            0xd3, 0xdb, 0xdd, 0xe3, 0xe4, 0xeb, 0xec, 0xed, 0xf4, 0xfc, 0xfd,
+        ] {
+            let mut cpu = SM83::new();
+
+            let rom = create_rom(vec![
+                0x00, // NOP
+                opcode,
+            ]);
+
+            cpu.bus.load_rom(&rom).unwrap();
+
+            cpu.step().unwrap();
+
+            assert_eq!(
+                cpu.step(),
+                Err(CpuError::IllegalOpcode {
+                    opcode,
+                    addr: 0x101
+                })
+            );
+            assert_eq!(cpu.state, CpuState::Locked);
+        }
+    }
+
+    #[test]
+    fn test_locked() {
+        let mut cpu = SM83::new();
+        cpu.ime = true;
+
+        let rom = create_rom(vec![
+            0xdd, // illegal
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        assert!(cpu.step().is_err());
+
+        // Interrupts do not wake a locked up CPU, time keeps passing
+        cpu.bus.interrupts.write_enable(0x01);
+        cpu.bus.interrupts.write_flag(0x01);
+
+        assert_eq!(cpu.run_for(100), Ok(100));
+        assert_eq!(cpu.state, CpuState::Locked);
+        assert_eq!(cpu.pc, 0x101);
+    }
+}
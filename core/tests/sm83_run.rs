@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use core::cpu::sm83::{CpuState, SM83};
+
+    const WRAM_00: u16 = 0xC000;
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    #[test]
+    fn test_step_cycles() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00 + 0x10;
+
+        let rom = create_rom(vec![
+            0x00, // NOP
+            0x3e, 0x01, // LD A, n
+            0xcd, 0x07, 0x01, // CALL nn
+            0x00, // NOP
+            0xc9, // RET
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        assert_eq!(cpu.step().unwrap(), 4);
+        assert_eq!(cpu.step().unwrap(), 8);
+        assert_eq!(cpu.step().unwrap(), 24);
+        assert_eq!(cpu.step().unwrap(), 16);
+        assert_eq!(cpu.pc, 0x106);
+    }
+
+    #[test]
+    fn test_step_halted() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x76, // HALT
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        assert_eq!(cpu.step().unwrap(), 4);
+        assert_eq!(cpu.state, CpuState::Halted);
+        assert_eq!(cpu.step().unwrap(), 4);
+        assert_eq!(cpu.pc, 0x101);
+    }
+
+    #[test]
+    fn test_run_for() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![0x00; 0x40]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        assert_eq!(cpu.run_for(100).unwrap(), 100);
+        assert_eq!(cpu.pc, 0x100 + 25);
+
+        // Overshoots by the length of the last instruction
+        assert_eq!(cpu.run_for(6).unwrap(), 8);
+        assert_eq!(cpu.pc, 0x100 + 27);
+    }
+
+    #[test]
+    fn test_run_until() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x3c, // INC A
+            0x18, // JR e
+            0xfd,
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        let cycles = cpu.run_until(|cpu| cpu.reg.a == 0x05).unwrap();
+
+        assert_eq!(cycles, 4 * 16 + 4);
+        assert_eq!(cpu.pc, 0x101);
+
+        let cycles = cpu.run_until(|cpu| cpu.pc == 0x100).unwrap();
+
+        assert_eq!(cycles, 12);
+        assert_eq!(cpu.reg.a, 0x05);
+    }
+
+    #[test]
+    fn test_run_from_hram() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = 0xfffe;
+
+        let rom = create_rom(vec![
+            0xcd, // CALL nn
+            0x80, 0xff,
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        // A routine copied to HRAM the way games set up their DMA transfer
+        for (i, byte) in [0x3e, 0x62, 0xc9].iter().enumerate() {
+            cpu.bus.write(0xff80 + i as u16, *byte); // LD A, n; RET
+        }
+
+        let cycles = cpu.run_until(|cpu| cpu.pc == 0x103).unwrap();
+
+        assert_eq!(cycles, 24 + 8 + 16);
+        assert_eq!(cpu.reg.a, 0x62);
+        assert_eq!(cpu.reg.sp, 0xfffe);
+    }
+}
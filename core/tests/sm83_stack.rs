@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use core::cpu::{registers::WordReg, sm83::SM83};
+
+    const WRAM_00: u16 = 0xC000;
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    #[test]
+    fn test_push_rr() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00 + 0x10;
+        cpu.reg.set_word(WordReg::BC, 0x1234);
+        cpu.reg.set_word(WordReg::DE, 0x5678);
+        cpu.reg.set_word(WordReg::HL, 0x9abc);
+        cpu.reg.set_word(WordReg::AF, 0xdef0);
+
+        let rom = create_rom(vec![
+            0xc5, // PUSH BC
+            0xd5, // PUSH DE
+            0xe5, // PUSH HL
+            0xf5, // PUSH AF
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, WRAM_00 + 0x0e);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x0f), 0x12);
+        assert_eq!(cpu.bus.read(WRAM_00 + 0x0e), 0x34);
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.sp, WRAM_00 + 0x08);
+        assert_eq!(cpu.bus.read_word(WRAM_00 + 0x0c), 0x5678);
+        assert_eq!(cpu.bus.read_word(WRAM_00 + 0x0a), 0x9abc);
+        assert_eq!(cpu.bus.read_word(WRAM_00 + 0x08), 0xdef0);
+        assert_eq!(cpu.pc, 0x104);
+    }
+
+    #[test]
+    fn test_pop_rr() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00;
+
+        let rom = create_rom(vec![
+            0xc1, // POP BC
+            0xd1, // POP DE
+            0xe1, // POP HL
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.bus.write_word(WRAM_00, 0x1234);
+        cpu.bus.write_word(WRAM_00 + 0x02, 0x5678);
+        cpu.bus.write_word(WRAM_00 + 0x04, 0x9abc);
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.get_word(WordReg::BC), 0x1234);
+        assert_eq!(cpu.reg.get_word(WordReg::DE), 0x5678);
+        assert_eq!(cpu.reg.get_word(WordReg::HL), 0x9abc);
+        assert_eq!(cpu.reg.sp, WRAM_00 + 0x06);
+        assert_eq!(cpu.pc, 0x103);
+    }
+
+    #[test]
+    fn test_pop_af() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00;
+
+        let rom = create_rom(vec![
+            0xf1, // POP AF
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.bus.write_word(WRAM_00, 0x12ff);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.a, 0x12);
+        assert_eq!(cpu.reg.f, 0xf0);
+        assert_eq!(cpu.reg.sp, WRAM_00 + 0x02);
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut cpu = SM83::new();
+        cpu.reg.sp = WRAM_00 + 0x10;
+        cpu.reg.set_word(WordReg::BC, 0x1234);
+
+        let rom = create_rom(vec![
+            0xc5, // PUSH BC
+            0xd1, // POP DE
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.get_word(WordReg::DE), 0x1234);
+        assert_eq!(cpu.reg.sp, WRAM_00 + 0x10);
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use core::cpu::{registers::WordReg, sm83::SM83};
+
+    const ZERO: u8 = 0b1000_0000;
+    // const SUB: u8 = 0b0100_0000;
+    // const HALF_CARRY: u8 = 0b0010_0000;
+    // const CARRY: u8 = 0b0001_0000;
+
+    const WRAM_00: u16 = 0xC000;
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    // Runs a single instruction with HL and SP pointing into WRAM and returns the
+    // cycles it took
+    fn cycles(code: Vec<u8>, flags: u8) -> usize {
+        let mut cpu = SM83::new();
+        cpu.reg.f = flags;
+        cpu.reg.set_word(WordReg::HL, WRAM_00);
+        cpu.reg.set_word(WordReg::SP, WRAM_00 + 0x10);
+
+        cpu.bus.load_rom(&create_rom(code)).unwrap();
+
+        cpu.step().unwrap()
+    }
+
+    #[test]
+    fn test_memory_cycles() {
+        assert_eq!(cycles(vec![0x7e], 0), 8); // LD A, (HL)
+        assert_eq!(cycles(vec![0x77], 0), 8); // LD (HL), A
+        assert_eq!(cycles(vec![0x36, 0x01], 0), 12); // LD (HL), n
+        assert_eq!(cycles(vec![0x34], 0), 12); // INC (HL)
+        assert_eq!(cycles(vec![0x35], 0), 12); // DEC (HL)
+        assert_eq!(cycles(vec![0xfa, 0x00, 0xc0], 0), 16); // LD A, (nn)
+        assert_eq!(cycles(vec![0xea, 0x00, 0xc0], 0), 16); // LD (nn), A
+        assert_eq!(cycles(vec![0xf0, 0x80], 0), 12); // LDH A, (n)
+        assert_eq!(cycles(vec![0xcb, 0x46], 0), 12); // BIT 0, (HL)
+        assert_eq!(cycles(vec![0xcb, 0x86], 0), 16); // RES 0, (HL)
+        assert_eq!(cycles(vec![0xcb, 0x06], 0), 16); // RLC (HL)
+    }
+
+    #[test]
+    fn test_internal_cycles() {
+        assert_eq!(cycles(vec![0x03], 0), 8); // INC BC
+        assert_eq!(cycles(vec![0x0b], 0), 8); // DEC BC
+        assert_eq!(cycles(vec![0x09], 0), 8); // ADD HL, BC
+        assert_eq!(cycles(vec![0xe8, 0x01], 0), 16); // ADD SP, e
+        assert_eq!(cycles(vec![0xf8, 0x01], 0), 12); // LD HL, SP+e
+    }
+
+    #[test]
+    fn test_stack_cycles() {
+        assert_eq!(cycles(vec![0xc5], 0), 16); // PUSH BC
+        assert_eq!(cycles(vec![0xc1], 0), 12); // POP BC
+        assert_eq!(cycles(vec![0xc9], 0), 16); // RET
+        assert_eq!(cycles(vec![0xd9], 0), 16); // RETI
+        assert_eq!(cycles(vec![0xc0], 0), 20); // RET NZ (taken)
+        assert_eq!(cycles(vec![0xc0], ZERO), 8); // RET NZ (not taken)
+        assert_eq!(cycles(vec![0xff], 0), 16); // RST 38h
+        assert_eq!(cycles(vec![0xcd, 0x00, 0x02], 0), 24); // CALL nn
+        assert_eq!(cycles(vec![0xc4, 0x00, 0x02], 0), 24); // CALL NZ, nn (taken)
+        assert_eq!(cycles(vec![0xc4, 0x00, 0x02], ZERO), 12); // CALL NZ, nn (not taken)
+    }
+
+    #[test]
+    fn test_jump_cycles() {
+        assert_eq!(cycles(vec![0xc3, 0x00, 0x02], 0), 16); // JP nn
+        assert_eq!(cycles(vec![0xe9], 0), 4); // JP HL
+        assert_eq!(cycles(vec![0x18, 0x00], 0), 12); // JR e
+        assert_eq!(cycles(vec![0x20, 0x00], ZERO), 8); // JR NZ, e (not taken)
+    }
+
+    #[test]
+    fn test_interrupt_dispatch_cycles() {
+        let mut cpu = SM83::new();
+        cpu.ime = true;
+        cpu.reg.set_word(WordReg::SP, WRAM_00 + 0x10);
+
+        cpu.bus.load_rom(&create_rom(vec![0x00])).unwrap();
+
+        cpu.bus.interrupts.write_enable(0x01);
+        cpu.bus.interrupts.write_flag(0x01);
+
+        assert_eq!(cpu.step().unwrap(), 20);
+        assert_eq!(cpu.pc, 0x40);
+    }
+}
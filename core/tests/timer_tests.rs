@@ -0,0 +1,287 @@
+#[cfg(test)]
+mod tests {
+    use core::bus::Bus;
+    use core::cpu::sm83::{CpuState, SM83};
+    use core::interrupts::Interrupt;
+
+    const DIV: u16 = 0xFF04;
+    const TIMA: u16 = 0xFF05;
+    const TMA: u16 = 0xFF06;
+    const TAC: u16 = 0xFF07;
+
+    fn create_rom(rom: Vec<u8>) -> Vec<u8> {
+        let mut new_rom = vec![0; 0x100];
+        new_rom.extend(rom);
+
+        if new_rom.len() < 0x150 {
+            new_rom.resize(0x150, 0);
+        }
+
+        new_rom
+    }
+
+    fn tick(bus: &mut Bus, m_cycles: usize) {
+        for _ in 0..m_cycles {
+            bus.tick(4);
+        }
+    }
+
+    // Resets the divider at the current time and starts TIMA at 16 cycles per tick
+    fn start(bus: &mut Bus, tima: u8, tma: u8) {
+        bus.write(DIV, 0x00);
+        bus.write(TIMA, tima);
+        bus.write(TMA, tma);
+        bus.write(TAC, 0x05);
+    }
+
+    fn timer_requested(bus: &Bus) -> bool {
+        bus.interrupts.flag & Interrupt::Timer.bit() != 0
+    }
+
+    #[test]
+    fn test_div() {
+        let mut bus = Bus::default();
+
+        bus.write(DIV, 0x12);
+        assert_eq!(bus.read(DIV), 0x00);
+
+        tick(&mut bus, 63);
+        assert_eq!(bus.read(DIV), 0x00);
+
+        tick(&mut bus, 1);
+        assert_eq!(bus.read(DIV), 0x01);
+
+        tick(&mut bus, 64 * 0xff);
+        assert_eq!(bus.read(DIV), 0x00);
+    }
+
+    #[test]
+    fn test_tac_reads() {
+        let mut bus = Bus::default();
+
+        bus.write(TAC, 0xff);
+        assert_eq!(bus.read(TAC), 0xff);
+
+        bus.write(TAC, 0x00);
+        assert_eq!(bus.read(TAC), 0xf8);
+    }
+
+    #[test]
+    fn test_tima_rates() {
+        for (tac, period) in [(0x04, 1024), (0x05, 16), (0x06, 64), (0x07, 256)] {
+            let mut bus = Bus::default();
+
+            bus.write(DIV, 0x00);
+            bus.write(TAC, tac);
+
+            tick(&mut bus, period / 4 - 1);
+            assert_eq!(bus.read(TIMA), 0x00);
+
+            tick(&mut bus, 1);
+            assert_eq!(bus.read(TIMA), 0x01);
+
+            tick(&mut bus, period / 4 * 10);
+            assert_eq!(bus.read(TIMA), 0x0b);
+        }
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut bus = Bus::default();
+
+        bus.write(DIV, 0x00);
+        bus.write(TAC, 0x01);
+
+        tick(&mut bus, 1000);
+
+        assert_eq!(bus.read(TIMA), 0x00);
+        assert_eq!(bus.cycles_to_next_event(), None);
+    }
+
+    #[test]
+    fn test_overflow_reload_delay() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0xff, 0x42);
+
+        tick(&mut bus, 4);
+
+        // TIMA holds 0 for one M-cycle before TMA is loaded and the interrupt fires
+        assert_eq!(bus.read(TIMA), 0x00);
+        assert!(!timer_requested(&bus));
+
+        tick(&mut bus, 1);
+
+        assert_eq!(bus.read(TIMA), 0x42);
+        assert!(timer_requested(&bus));
+
+        // Counting resumes from TMA
+        tick(&mut bus, 3);
+        assert_eq!(bus.read(TIMA), 0x43);
+    }
+
+    #[test]
+    fn test_repeated_overflow() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0xfe, 0xfe);
+
+        tick(&mut bus, 1);
+
+        // Two ticks of 16 cycles from TMA to the overflow, then the reload
+        for _ in 0..10 {
+            bus.interrupts.flag = 0;
+
+            tick(&mut bus, 7);
+            assert_eq!(bus.read(TIMA), 0x00);
+            assert!(!timer_requested(&bus));
+
+            tick(&mut bus, 1);
+            assert_eq!(bus.read(TIMA), 0xfe);
+            assert!(timer_requested(&bus));
+        }
+    }
+
+    #[test]
+    fn test_write_tima_during_overflow_cancels_reload() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0xff, 0x42);
+
+        tick(&mut bus, 4);
+        bus.write(TIMA, 0x10);
+
+        tick(&mut bus, 1);
+
+        assert_eq!(bus.read(TIMA), 0x10);
+        assert!(!timer_requested(&bus));
+    }
+
+    #[test]
+    fn test_write_tima_during_reload_is_ignored() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0xff, 0x42);
+
+        tick(&mut bus, 5);
+        bus.write(TIMA, 0x10);
+
+        assert_eq!(bus.read(TIMA), 0x42);
+        assert!(timer_requested(&bus));
+    }
+
+    #[test]
+    fn test_write_tma_during_reload() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0xff, 0x42);
+
+        tick(&mut bus, 5);
+        bus.write(TMA, 0x55);
+
+        assert_eq!(bus.read(TIMA), 0x55);
+        assert_eq!(bus.read(TMA), 0x55);
+    }
+
+    #[test]
+    fn test_div_write_falling_edge() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0x00, 0x00);
+
+        // Bit 3 of the counter is set halfway through the period
+        tick(&mut bus, 2);
+        bus.write(DIV, 0x00);
+
+        assert_eq!(bus.read(TIMA), 0x01);
+
+        // The period restarts from the reset
+        tick(&mut bus, 3);
+        assert_eq!(bus.read(TIMA), 0x01);
+
+        tick(&mut bus, 1);
+        assert_eq!(bus.read(TIMA), 0x02);
+    }
+
+    #[test]
+    fn test_div_write_without_edge() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0x00, 0x00);
+
+        tick(&mut bus, 1);
+        bus.write(DIV, 0x00);
+
+        assert_eq!(bus.read(TIMA), 0x00);
+    }
+
+    #[test]
+    fn test_div_write_overflow() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0xff, 0x42);
+
+        tick(&mut bus, 2);
+        bus.write(DIV, 0x00);
+
+        assert_eq!(bus.read(TIMA), 0x00);
+
+        tick(&mut bus, 1);
+
+        assert_eq!(bus.read(TIMA), 0x42);
+        assert!(timer_requested(&bus));
+    }
+
+    #[test]
+    fn test_tac_disable_falling_edge() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0x00, 0x00);
+
+        tick(&mut bus, 2);
+        bus.write(TAC, 0x01);
+
+        assert_eq!(bus.read(TIMA), 0x01);
+    }
+
+    #[test]
+    fn test_tac_rate_change_falling_edge() {
+        let mut bus = Bus::default();
+        start(&mut bus, 0x00, 0x00);
+
+        // Bit 3 is set and bit 5 is not
+        tick(&mut bus, 2);
+        bus.write(TAC, 0x06);
+
+        assert_eq!(bus.read(TIMA), 0x01);
+
+        // Bit 3 is clear, switching to bit 3 from a set bit 5
+        let mut bus = Bus::default();
+        start(&mut bus, 0x00, 0x00);
+        bus.write(TAC, 0x06);
+
+        tick(&mut bus, 8);
+        bus.write(TAC, 0x05);
+
+        assert_eq!(bus.read(TIMA), 0x01);
+    }
+
+    #[test]
+    fn test_interrupt_wakes_halt() {
+        let mut cpu = SM83::new();
+
+        let rom = create_rom(vec![
+            0x76, // HALT
+            0x00, // NOP
+        ]);
+
+        cpu.bus.load_rom(&rom).unwrap();
+        cpu.bus.interrupts.write_enable(Interrupt::Timer.bit());
+        start(&mut cpu.bus, 0x00, 0x00);
+
+        // Sleeps to the overflow, then to the reload, then wakes up for the NOP
+        let mut steps = 0;
+        let elapsed = cpu
+            .run_until(|cpu| {
+                steps += 1;
+                cpu.pc == 0x102
+            })
+            .unwrap();
+
+        assert_eq!(steps, 5);
+        assert_eq!(elapsed, 256 * 16 + 4 + 4);
+        assert!(timer_requested(&cpu.bus));
+        assert_eq!(cpu.state, CpuState::Running);
+    }
+}